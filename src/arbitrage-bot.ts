@@ -1,39 +1,167 @@
 import 'dotenv/config';
-import { Connection, Keypair } from '@solana/web3.js';
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
 import bs58 from 'bs58';
 import express from 'express';
 import cors from 'cors';
 import WebSocket, { WebSocketServer } from 'ws';
 import http from 'http';
+import fs from 'fs';
+import crypto from 'crypto';
 import { logger } from './utils/logger.js';
-import { Scanner, TRADING_PAIRS, DEX_LIST } from './scanner.js';
-import { Executor } from './executor.js';
-import { ArbitrageOpportunity } from './profit-calculator.js';
+import { configureHttp } from './utils/http.js';
+import { RpcPool } from './utils/rpc-pool.js';
+import { BoundedQueue } from './utils/bounded-queue.js';
+import { LogWriter } from './utils/log-writer.js';
+import { Watchdog } from './utils/watchdog.js';
+import { initTracing, shutdownTracing, withSpan } from './utils/tracing.js';
+import { Scanner, ScannerOptions, ScanReport } from './scanner.js';
+import { Executor, ExecutionResult } from './executor.js';
+import { parsePriorityFeeStrategy, isHeliusRpc } from './priority-fee.js';
+import { MarketRegistry } from './market-registry.js';
+import { showLookupTable } from './lookup-table.js';
+import { verifyProgramIds } from './program-check.js';
+import { KAMINO_MAIN_MARKET, tvlDropPercent } from './kamino-flash-loan.js';
+import { createNonceAccounts } from './nonce-manager.js';
+import { recordSnapshot, saveSnapshot, loadSnapshot, replaySnapshot, logReplayReport } from './snapshot.js';
+import { Sweeper, SweepOverrides } from './sweeper.js';
+import { ProfitWithdrawer } from './withdrawer.js';
+import { SlackNotifier } from './slack-notifier.js';
+import { RaydiumClient } from './dex-integrations/raydium.js';
+import { JupiterClient } from './dex-integrations/jupiter.js';
+import { tokenRegistry } from './token-registry.js';
+import { fetchWalletInventory, logWalletInventory } from './wallet-inventory.js';
+import { ArbitrageOpportunity, describeRoute, opportunityScore, routeKey, setFlashLoanFee, FLASH_LOAN_FEE } from './profit-calculator.js';
+import { applyConfigFile, loadConfig, validateConfig, minProfitForOpportunity, flashLoanLimit } from './config.js';
 
 // ============================================
 // CONFIGURATION
 // ============================================
 
-const CONFIG = {
-  // RPC Settings
-  RPC_URL: process.env.RPC_URL || 'https://api.mainnet-beta.solana.com',
-  MAX_RPC_REQUESTS_PER_SEC: parseInt(process.env.MAX_RPC_REQUESTS_PER_SEC || '20'),
-  
-  // Scanning (5 seconds to avoid Jupiter rate limits)
-  SCAN_INTERVAL_MS: parseInt(process.env.SCAN_INTERVAL_MS || '5000'),
-  
-  // Profit thresholds
-  MIN_PROFIT_USD: parseFloat(process.env.MIN_PROFIT_USD || '10'),
-  MAX_SLIPPAGE_TOLERANCE: parseFloat(process.env.MAX_SLIPPAGE_TOLERANCE || '0.003'),
-  
-  // Execution
-  DRY_RUN: process.env.DRY_RUN !== 'false',
-  AUTO_EXECUTE: process.env.AUTO_EXECUTE === 'true',
-  
-  // API Server
-  API_PORT: parseInt(process.env.API_PORT || '3001'),
-  ENABLE_API: process.env.ENABLE_API !== 'false',
-};
+/**
+ * Load `--config-file <path>` into process.env before the config is read
+ */
+function loadConfigFile(): void {
+  const index = process.argv.indexOf('--config-file');
+  if (index === -1) {
+    return;
+  }
+
+  const path = process.argv[index + 1];
+  try {
+    if (!path) {
+      throw new Error('missing path');
+    }
+    applyConfigFile(path);
+  } catch (e) {
+    logger.error(`Failed to load config file ${path ?? ''}: ${e instanceof Error ? e.message : e}`);
+    process.exit(1);
+  }
+}
+
+loadConfigFile();
+
+const CONFIG = loadConfig();
+
+// Slack on top of the slowest trade (every fee escalation timing out) before a shutdown force-exits
+const SHUTDOWN_MARGIN_MS = 30_000;
+
+// Repeat the SOL reserve warning (and Slack notice) at most this often
+const LOW_BALANCE_ALERT_INTERVAL_MS = 10 * 60_000;
+
+/**
+ * One-line scan diagnostics: pair outcomes, per-DEX quote misses and duration
+ */
+function formatScanReport(report: ScanReport): string {
+  const outcomes = Object.entries(report.outcomes).map(([outcome, n]) => `${outcome} ${n}`).join(', ');
+  const dexes = Object.entries(report.perDex)
+    .map(([dex, s]) => `${dex} ${s!.quotes}/${s!.quotes + s!.misses}`)
+    .join(', ');
+  const unconfirmed = report.outcomes.unconfirmed ?? 0;
+  const candidates = unconfirmed + report.opportunities.length;
+  const falsePositives = CONFIG.CONFIRM_OPPORTUNITIES && candidates > 0
+    ? ` | ${(unconfirmed / candidates * 100).toFixed(0)}% false positives`
+    : '';
+  return `${report.opportunities.length} opportunities in ${report.durationMs}ms | pairs: ${outcomes || 'none'} | quotes: ${dexes || 'none'}${falsePositives}${report.errors.length > 0 ? ` | ${report.errors.length} errors` : ''}`;
+}
+
+/**
+ * Kamino markets listed in KAMINO_MARKETS, or the main market when unset or discovered ("auto")
+ */
+function configuredMarkets(): PublicKey[] {
+  const markets = CONFIG.KAMINO_MARKETS === 'auto'
+    ? []
+    : CONFIG.KAMINO_MARKETS.split(',').map(a => a.trim()).filter(Boolean).map(a => new PublicKey(a));
+  return markets.length > 0 ? markets : [KAMINO_MAIN_MARKET];
+}
+
+/**
+ * Execution priority of an opportunity right now (higher runs first)
+ */
+function scoreOpportunity(opp: ArbitrageOpportunity): number {
+  return opportunityScore(
+    opp,
+    Date.now(),
+    CONFIG.MAX_QUOTE_AGE_SECONDS * 1000,
+    CONFIG.SCORE_PROFIT_WEIGHT,
+    CONFIG.SCORE_PROBABILITY_WEIGHT
+  );
+}
+
+function hasCycleLimits(): boolean {
+  return CONFIG.MAX_EXECUTIONS_PER_CYCLE > 0 || CONFIG.MAX_FLASH_USD_PER_CYCLE > 0;
+}
+
+function scannerOptions(): ScannerOptions {
+  return {
+    divergenceThreshold: CONFIG.JUPITER_DIVERGENCE_THRESHOLD,
+    pairs: CONFIG.ARB_PAIRS,
+    jupiter: {
+      baseUrl: CONFIG.JUPITER_API_URL || undefined,
+      apiKey: CONFIG.JUPITER_API_KEY || undefined,
+    },
+    priceFeed: CONFIG.PRICE_FEED,
+    skipToken2022: CONFIG.SKIP_TOKEN_2022,
+    maxPriceAgeMs: CONFIG.MAX_QUOTE_AGE_SECONDS * 1000,
+    gasEstimateLamports: CONFIG.GAS_ESTIMATE_LAMPORTS,
+    confirmWithQuotes: CONFIG.CONFIRM_OPPORTUNITIES,
+    triangularRoutes: CONFIG.TRIANGULAR_ROUTES,
+  };
+}
+
+/**
+ * How long a graceful shutdown waits before force-exiting: long enough for a trade that
+ * resends through every fee escalation and times out on each, plus SHUTDOWN_MARGIN_MS
+ */
+function shutdownTimeoutMs(): number {
+  return CONFIG.CONFIRM_TIMEOUT_MS * (CONFIG.FEE_ESCALATION_RETRIES + 1) + SHUTDOWN_MARGIN_MS;
+}
+
+/**
+ * Minimum profit for an opportunity: pair override, then route override, then MIN_PROFIT_USD
+ */
+function minProfitFor(opp: ArbitrageOpportunity): number {
+  return minProfitForOpportunity(CONFIG, opp);
+}
+
+// Upper bound for /api/executions?limit=
+const MAX_EXECUTIONS_LIMIT = 500;
+
+/**
+ * Check an Authorization header against API_TOKEN (always true when no token is configured)
+ */
+function isAuthorized(header: string | undefined): boolean {
+  return !CONFIG.API_TOKEN || hasApiToken(header);
+}
+
+/**
+ * Authorization header carries `Bearer <API_TOKEN>` (always false when no token is configured)
+ */
+function hasApiToken(header: string | undefined): boolean {
+  if (!CONFIG.API_TOKEN || !header) return false;
+  const expected = Buffer.from(`Bearer ${CONFIG.API_TOKEN}`);
+  const actual = Buffer.from(header);
+  return actual.length === expected.length && crypto.timingSafeEqual(actual, expected);
+}
 
 // ============================================
 // GLOBAL STATE
@@ -45,8 +173,28 @@ interface BotStats {
   opportunitiesDetected: number;
   tradesExecuted: number;
   tradesSuccessful: number;
+  tradesFailed: number;
   totalProfitUsd: number;
+  totalLossUsd: number;
+  skippedHighImpact: number;
+  skippedStale: number;
+  withdrawnSol: number;
+  withdrawnUsdc: number;
+  // Network fees paid by landed trades
+  totalFeesLamports: number;
+  // Cumulative realized balance change per mint (UI units)
+  tokenProfits: Record<string, number>;
+  // Price-move triggered rescans, how many of their opportunities reached an executor, and total wait
+  priceTriggers: number;
+  triggeredExecutions: number;
+  triggerLatencyMsTotal: number;
+  // Failed trades per ExecutionErrorKind
+  failuresByKind: Record<string, number>;
+  // Opportunities carried to the next cycle because a per-cycle limit was reached
+  deferredByCycleLimit: number;
   lastScanTime: number;
+  // Diagnostics of the most recent full scan
+  lastScanReport: ScanReport | null;
   recentOpportunities: ArbitrageOpportunity[];
 }
 
@@ -56,62 +204,479 @@ const stats: BotStats = {
   opportunitiesDetected: 0,
   tradesExecuted: 0,
   tradesSuccessful: 0,
+  tradesFailed: 0,
   totalProfitUsd: 0,
+  totalLossUsd: 0,
+  skippedHighImpact: 0,
+  skippedStale: 0,
+  withdrawnSol: 0,
+  withdrawnUsdc: 0,
+  totalFeesLamports: 0,
+  priceTriggers: 0,
+  triggeredExecutions: 0,
+  triggerLatencyMsTotal: 0,
+  tokenProfits: {},
+  failuresByKind: {},
+  deferredByCycleLimit: 0,
   lastScanTime: 0,
+  lastScanReport: null,
   recentOpportunities: [],
 };
 
+// Counters carried across restarts (plus tokenProfits and failuresByKind); startTime and recent opportunities are per-run
+const PERSISTED_STATS_KEYS = [
+  'totalScans',
+  'opportunitiesDetected',
+  'tradesExecuted',
+  'tradesSuccessful',
+  'tradesFailed',
+  'totalProfitUsd',
+  'totalLossUsd',
+  'skippedHighImpact',
+  'skippedStale',
+  'withdrawnSol',
+  'withdrawnUsdc',
+  'totalFeesLamports',
+  'priceTriggers',
+  'triggeredExecutions',
+  'triggerLatencyMsTotal',
+  'deferredByCycleLimit',
+] as const;
+
+/**
+ * Load persisted counters into stats (JSON; the bot writes this file itself, so unlike
+ * --config-file it isn't TOML)
+ * A missing or corrupted file leaves the defaults in place
+ */
+function loadStats(path: string): void {
+  if (!fs.existsSync(path)) {
+    return;
+  }
+
+  try {
+    const saved = JSON.parse(fs.readFileSync(path, 'utf8'));
+    for (const key of PERSISTED_STATS_KEYS) {
+      if (typeof saved[key] === 'number') {
+        stats[key] = saved[key];
+      }
+    }
+    if (saved.tokenProfits && typeof saved.tokenProfits === 'object') {
+      stats.tokenProfits = saved.tokenProfits;
+    }
+    if (saved.failuresByKind && typeof saved.failuresByKind === 'object') {
+      stats.failuresByKind = saved.failuresByKind;
+    }
+    logger.info(`Loaded stats from ${path}: ${stats.tradesExecuted} trades, $${stats.totalProfitUsd.toFixed(2)} profit`);
+  } catch (e) {
+    logger.warn(`Stats file ${path} is corrupted, starting fresh: ${e}`);
+  }
+}
+
+/**
+ * Save persisted counters (write to a temp file, then rename)
+ */
+function saveStats(path: string): void {
+  const saved: Record<string, number | Record<string, number>> = {};
+  for (const key of PERSISTED_STATS_KEYS) {
+    saved[key] = stats[key];
+  }
+  saved.tokenProfits = stats.tokenProfits;
+  saved.failuresByKind = stats.failuresByKind;
+
+  try {
+    fs.writeFileSync(`${path}.tmp`, JSON.stringify(saved, null, 2));
+    fs.renameSync(`${path}.tmp`, path);
+  } catch (e) {
+    logger.warn(`Failed to save stats to ${path}: ${e}`);
+  }
+}
+
+/**
+ * One line of the arbitrage history log
+ */
+function historyEntry(opp: ArbitrageOpportunity, result: ExecutionResult) {
+  return {
+    timestamp: new Date().toISOString(),
+    pair: opp.pair,
+    buyDex: opp.buyDex,
+    sellDex: opp.sellDex,
+    flashAmount: opp.flashAmount,
+    expectedProfit: opp.calculation.netProfit,
+    success: result.success,
+    skipReason: result.skipReason,
+    signature: result.txSignature,
+    actualProfit: result.actualProfit,
+    profitBreakdown: result.profitBreakdown,
+    feeLamports: result.feeLamports,
+    timings: result.timings,
+    error: result.error,
+    executionTimeMs: result.executionTimeMs,
+    opportunity: opp,
+  };
+}
+
+// Latency samples (ms) of recent landed trades, for p50/p95 in the stats display
+const LATENCY_SAMPLES = 500;
+const detectToSubmitMs: number[] = [];
+const submitToConfirmMs: number[] = [];
+
+function addSample(samples: number[], value: number): void {
+  samples.push(value);
+  if (samples.length > LATENCY_SAMPLES) {
+    samples.shift();
+  }
+}
+
+function percentile(samples: number[], p: number): number {
+  if (samples.length === 0) return 0;
+  const sorted = [...samples].sort((a, b) => a - b);
+  return sorted[Math.min(sorted.length - 1, Math.floor((p / 100) * sorted.length))];
+}
+
+/**
+ * Record an executed trade
+ * Profit is whatever the executor realized; losses are tracked separately
+ */
+function recordTrade(result: ExecutionResult): void {
+  stats.tradesExecuted++;
+  if (result.success) {
+    stats.tradesSuccessful++;
+    const timings = result.timings;
+    if (timings?.submittedAt !== undefined && timings.confirmedAt !== undefined) {
+      addSample(detectToSubmitMs, timings.submittedAt - timings.detectedAt);
+      addSample(submitToConfirmMs, timings.confirmedAt - timings.submittedAt);
+    }
+  } else {
+    stats.tradesFailed++;
+    const kind = result.error?.kind ?? 'other';
+    stats.failuresByKind[kind] = (stats.failuresByKind[kind] ?? 0) + 1;
+  }
+
+  const profit = result.actualProfit || 0;
+  if (profit >= 0) {
+    stats.totalProfitUsd += profit;
+  } else {
+    stats.totalLossUsd += -profit;
+  }
+  stats.totalFeesLamports += result.feeLamports ?? 0;
+
+  for (const delta of result.profitBreakdown ?? []) {
+    stats.tokenProfits[delta.mint] = (stats.tokenProfits[delta.mint] ?? 0) + delta.amount;
+  }
+}
+
+/**
+ * Count a pre-send skip (high impact, stale) or record the attempted trade
+ * @returns false for skips
+ */
+function recordExecution(result: ExecutionResult): boolean {
+  if (result.skipReason === 'high_impact') {
+    stats.skippedHighImpact++;
+    return false;
+  }
+  if (result.skipReason === 'stale') {
+    stats.skippedStale++;
+    return false;
+  }
+  recordTrade(result);
+  return true;
+}
+
 // ============================================
 // MAIN BOT CLASS
 // ============================================
 
 class ArbitrageBot {
   private connection: Connection;
+  private rpcPool: RpcPool;
   private keypair: Keypair;
   private scanner: Scanner;
   private executor: Executor;
+  private sweeper: Sweeper;
+  private withdrawer: ProfitWithdrawer | null = null;
   private running = false;
   private scanTimer: NodeJS.Timeout | null = null;
+  // Opportunities waiting for an executor, highest score first; a full queue makes the scanner wait
+  private executionQueue = new BoundedQueue<ArbitrageOpportunity>(
+    64,
+    (a, b) => scoreOpportunity(b) - scoreOpportunity(a)
+  );
+  private executors: Promise<void> = Promise.resolve();
+  // Trades an executor worker is currently sending
+  private inFlight = 0;
+  private historyLog: LogWriter | null = CONFIG.ARBITRAGE_LOG_PATH ? new LogWriter(CONFIG.ARBITRAGE_LOG_PATH) : null;
+  private watchdog = new Watchdog(CONFIG.WATCHDOG_STALL_SECONDS);
+  // Pairs with a price-triggered rescan in progress, and when each triggered opportunity's price moved
+  private triggeredPairs: Set<string> = new Set();
+  private triggerTimes: WeakMap<ArbitrageOpportunity, number> = new WeakMap();
+  private slack: SlackNotifier | null = CONFIG.SLACK_WEBHOOK_URL ? new SlackNotifier(CONFIG.SLACK_WEBHOOK_URL) : null;
+  private lastLowBalanceAlert = 0;
+  // Kamino deposit TVL (USD) per market when first loaded, for spotting a sudden drop
+  private tvlBaseline: Map<string, number> = new Map();
+  // When execution was paused (null = executing); scanning continues while paused
+  private pausedAt: number | null = CONFIG.START_PAUSED ? Date.now() : null;
+  // Executions and flash notional queued in the current scan cycle, and (with limits) the cycle's
+  // opportunities collected until the scan finishes so the most profitable are queued first
+  private cycleUsage = { executions: 0, flashUsd: 0 };
+  private cycleBatch: ArbitrageOpportunity[] | null = null;
+  // Over a cycle limit, queued first in the next cycle unless found again or too old by then
+  private deferred: ArbitrageOpportunity[] = [];
 
   constructor() {
-    // Initialize connection
-    this.connection = new Connection(CONFIG.RPC_URL, 'confirmed');
+    // Initialize connection (HTTP requests fail over across the pool; WebSocket stays on RPC_URL)
+    this.rpcPool = new RpcPool([CONFIG.RPC_URL, ...CONFIG.FALLBACK_RPC_URLS]);
+    this.connection = new Connection(CONFIG.RPC_URL, {
+      commitment: CONFIG.COMMITMENT,
+      fetch: this.rpcPool.fetch as any,
+    });
 
     // Load wallet
-    const privateKey = process.env.WALLET_PRIVATE_KEY;
-    if (!privateKey) {
+    if (!CONFIG.WALLET_PRIVATE_KEY) {
       throw new Error('WALLET_PRIVATE_KEY not set in environment');
     }
-    this.keypair = Keypair.fromSecretKey(bs58.decode(privateKey));
+    this.keypair = Keypair.fromSecretKey(bs58.decode(CONFIG.WALLET_PRIVATE_KEY));
 
     // Initialize components
-    this.scanner = new Scanner(this.connection);
-    this.executor = new Executor(this.connection, this.keypair, CONFIG.DRY_RUN);
+    this.scanner = new Scanner(this.connection, scannerOptions());
+    this.executor = new Executor(this.connection, this.keypair, {
+      dryRun: CONFIG.DRY_RUN,
+      dryRunVerbose: CONFIG.DRY_RUN_VERBOSE,
+      maxPriceImpactPct: CONFIG.MAX_PRICE_IMPACT_PCT,
+      priorityFeeMicroLamports: CONFIG.PRIORITY_FEE_MICRO_LAMPORTS,
+      priorityFeeStrategy: parsePriorityFeeStrategy(
+        CONFIG.PRIORITY_FEE_STRATEGY,
+        CONFIG.PRIORITY_FEE_MICRO_LAMPORTS,
+        CONFIG.SWAP_PRIORITY_LEVEL
+      ),
+      heliusRpcUrl: isHeliusRpc(CONFIG.RPC_URL) ? CONFIG.RPC_URL : undefined,
+      exactOutRepay: CONFIG.EXACT_OUT_REPAY,
+      swapOptions: {
+        slippageBps: Math.round(CONFIG.MAX_SLIPPAGE_TOLERANCE * 10_000),
+        priorityLevel: CONFIG.SWAP_PRIORITY_LEVEL,
+        maxPriorityMicroLamports: CONFIG.SWAP_MAX_PRIORITY_MICRO_LAMPORTS,
+        wrapUnwrapSol: CONFIG.SWAP_WRAP_UNWRAP_SOL,
+      },
+      kamino: {
+        ...(CONFIG.KAMINO_MARKETS === 'auto'
+          ? { registry: new MarketRegistry(this.connection, CONFIG.KAMINO_REGISTRY_TTL_MS) }
+          : { markets: configuredMarkets() }),
+        commitment: CONFIG.COMMITMENT,
+        skipPreflight: CONFIG.SKIP_PREFLIGHT,
+        confirmTimeoutMs: CONFIG.CONFIRM_TIMEOUT_MS,
+        referrer: CONFIG.KAMINO_REFERRER ? new PublicKey(CONFIG.KAMINO_REFERRER) : undefined,
+      },
+      lookupTableFile: CONFIG.LOOKUP_TABLE_FILE || undefined,
+      maxOpportunityAgeMs: CONFIG.MAX_QUOTE_AGE_SECONDS * 1000,
+      maxRetries: CONFIG.MAX_RETRIES,
+      nonceAccounts: CONFIG.NONCE_ACCOUNTS.map(a => new PublicKey(a)),
+      feeEscalationRetries: CONFIG.FEE_ESCALATION_RETRIES,
+      feeEscalationStepMicroLamports: CONFIG.FEE_ESCALATION_STEP_MICRO_LAMPORTS,
+      minSolReserveLamports: CONFIG.MIN_SOL_RESERVE_LAMPORTS,
+    });
+
+    // Leftover balances are swapped via Raydium, valued with Jupiter prices
+    const raydium = new RaydiumClient(this.connection);
+    const keepMints = new Set(
+      ['USDC', ...CONFIG.ARB_PAIRS.map(p => p.pair.split('/')[0])]
+        .map(symbol => raydium.getTokenInfo(symbol)?.mint)
+        .filter((mint): mint is string => !!mint)
+    );
+    this.sweeper = new Sweeper(
+      this.connection,
+      this.keypair,
+      raydium,
+      new JupiterClient({
+        baseUrl: CONFIG.JUPITER_API_URL || undefined,
+        apiKey: CONFIG.JUPITER_API_KEY || undefined,
+      }),
+      {
+        targetMint: new PublicKey(CONFIG.PROFIT_TARGET_MINT),
+        dustUsd: CONFIG.SWEEP_DUST_USD,
+        closeEmptyAccounts: CONFIG.SWEEP_CLOSE_ACCOUNTS,
+        keepMints,
+        dryRun: CONFIG.DRY_RUN,
+        minSolReserveLamports: CONFIG.MIN_SOL_RESERVE_LAMPORTS,
+        swapOptions: {
+          slippageBps: Math.round(CONFIG.MAX_SLIPPAGE_TOLERANCE * 10_000),
+          priorityLevel: CONFIG.SWAP_PRIORITY_LEVEL,
+          maxPriorityMicroLamports: CONFIG.SWAP_MAX_PRIORITY_MICRO_LAMPORTS,
+          wrapUnwrapSol: true,
+        },
+      },
+      mint => this.executor.isMintInFlight(mint)
+    );
+
+    if (CONFIG.PROFIT_WITHDRAW_ADDRESS) {
+      this.withdrawer = new ProfitWithdrawer(
+        this.connection,
+        this.keypair,
+        {
+          destination: new PublicKey(CONFIG.PROFIT_WITHDRAW_ADDRESS),
+          targetSol: CONFIG.HOT_WALLET_TARGET_SOL,
+          marginSol: CONFIG.HOT_WALLET_MARGIN_SOL,
+          targetUsdc: CONFIG.HOT_WALLET_TARGET_USDC,
+          marginUsdc: CONFIG.HOT_WALLET_MARGIN_USDC,
+          dryRun: CONFIG.DRY_RUN,
+        },
+        () => this.executor.hasTradesInFlight() || this.sweeper.isRunning()
+      );
+    }
   }
 
   async start(): Promise<void> {
     this.printBanner();
     
     logger.info(`RPC: ${CONFIG.RPC_URL.substring(0, 50)}...`);
+    if (CONFIG.FALLBACK_RPC_URLS.length > 0) {
+      logger.info(`Fallback RPCs: ${CONFIG.FALLBACK_RPC_URLS.length}`);
+    }
     logger.info(`Wallet: ${this.keypair.publicKey.toBase58()}`);
-    logger.info(`Mode: ${CONFIG.DRY_RUN ? 'DRY RUN' : 'LIVE'}`);
-    logger.info(`Auto Execute: ${CONFIG.AUTO_EXECUTE}`);
+    logger.info(`Mode: ${CONFIG.DRY_RUN ? (CONFIG.DRY_RUN_VERBOSE ? 'DRY RUN (simulate)' : 'DRY RUN') : 'LIVE'}`);
+    logger.info(`Auto Execute: ${CONFIG.AUTO_EXECUTE}${CONFIG.AUTO_EXECUTE ? ` (${CONFIG.MAX_CONCURRENT_EXECUTIONS} workers)` : ''}`);
+    if (CONFIG.SCORE_PROBABILITY_WEIGHT > 0) {
+      logger.info(`Queue Score: profit^${CONFIG.SCORE_PROFIT_WEIGHT} × probability^${CONFIG.SCORE_PROBABILITY_WEIGHT}`);
+    }
+    if (hasCycleLimits()) {
+      logger.info(`Per-Cycle Limits: ${CONFIG.MAX_EXECUTIONS_PER_CYCLE || 'unlimited'} executions | $${CONFIG.MAX_FLASH_USD_PER_CYCLE ? CONFIG.MAX_FLASH_USD_PER_CYCLE.toLocaleString() : 'unlimited'} flash notional`);
+    }
+    if (this.pausedAt !== null) {
+      logger.warn('Execution PAUSED at startup: resume with POST /api/control/resume or SIGUSR1');
+    }
     logger.info(`Min Profit: $${CONFIG.MIN_PROFIT_USD}`);
+    for (const [route, value] of Object.entries(CONFIG.ROUTE_MIN_PROFIT_USD)) {
+      logger.info(`Min Profit (${route}): $${value}`);
+    }
+    logger.info(`Max Price Impact: ${CONFIG.MAX_PRICE_IMPACT_PCT}%`);
+    logger.info(`Priority Fee: ${CONFIG.PRIORITY_FEE_STRATEGY === 'static' ? `${CONFIG.PRIORITY_FEE_MICRO_LAMPORTS} micro-lamports/CU` : CONFIG.PRIORITY_FEE_STRATEGY}${CONFIG.PRIORITY_FEE_STRATEGY === 'estimate' && isHeliusRpc(CONFIG.RPC_URL) ? ' (Helius)' : ''}`);
+    logger.info(`Swap Slippage: ${Math.round(CONFIG.MAX_SLIPPAGE_TOLERANCE * 10_000)} bps`);
+    logger.info(`Max Flash Loan: $${CONFIG.MAX_FLASH_LOAN_USD.toLocaleString()} | Gas Estimate: ${CONFIG.GAS_ESTIMATE_LAMPORTS} lamports`);
+    logger.info(`Kamino Markets: ${CONFIG.KAMINO_MARKETS || 'main'}${CONFIG.KAMINO_REFERRER ? ` | Referrer: ${CONFIG.KAMINO_REFERRER}` : ''}`);
+    logger.info(`TVL Check: ${CONFIG.TVL_CHECK_SCANS > 0 ? `every ${CONFIG.TVL_CHECK_SCANS} scans` : 'startup only'}, alert on a ${CONFIG.TVL_DROP_ALERT_PERCENT}% drop`);
+    logger.info(`Pairs: ${CONFIG.ARB_PAIRS.filter(p => p.enabled).map(p => p.pair).join(', ')}`);
     logger.info(`Scan Interval: ${CONFIG.SCAN_INTERVAL_MS}ms`);
+    logger.info(`Commitment: ${CONFIG.COMMITMENT}${CONFIG.SKIP_PREFLIGHT ? ' (skip preflight)' : ''}`);
+    if (CONFIG.WATCHDOG_STALL_SECONDS > 0) {
+      logger.info(`Watchdog: exit after ${CONFIG.WATCHDOG_STALL_SECONDS}s without a scan`);
+    }
+    if (CONFIG.NONCE_ACCOUNTS.length > 0) {
+      logger.info(`Durable Nonces: ${CONFIG.NONCE_ACCOUNTS.length} account(s)`);
+    }
+    logger.info(`Max Quote Age: ${CONFIG.MAX_QUOTE_AGE_SECONDS}s | Max Retries: ${CONFIG.MAX_RETRIES} | Fee Escalation: ${CONFIG.FEE_ESCALATION_RETRIES} × +${CONFIG.FEE_ESCALATION_STEP_MICRO_LAMPORTS}`);
+    logger.info(`Price Feed: ${CONFIG.PRICE_FEED ? 'WebSocket' : 'polling'}${CONFIG.PRICE_TRIGGER_BPS > 0 ? ` (rescan on ${CONFIG.PRICE_TRIGGER_BPS} bps moves)` : ''}`);
+    if (CONFIG.ARBITRAGE_LOG_PATH) {
+      logger.info(`Arbitrage Log: ${CONFIG.ARBITRAGE_LOG_PATH}`);
+    }
+    if (CONFIG.SLACK_WEBHOOK_URL) {
+      logger.info('Slack Notifications: enabled');
+    }
     console.log('');
 
+    // Hardcoded addresses may be stale after a program upgrade or migration
+    const problems = await verifyProgramIds(this.connection, configuredMarkets()).catch(e => {
+      logger.warn(`Program ID check failed: ${e}`);
+      return [];
+    });
+    if (problems.length > 0) {
+      logger.warn(`⚠️  ${problems.length} program/account check(s) failed, trades through them will fail`);
+    }
+
     // Initialize DEX clients
     await this.scanner.initialize();
     await this.executor.initialize();
+    await this.refreshFlashLimit();
+    await this.checkProtocolTvl();
+    if (CONFIG.ATA_PREFLIGHT) {
+      await this.preflightAtas();
+    }
 
     // Start API server if enabled
     if (CONFIG.ENABLE_API) {
       this.startApiServer();
     }
 
-    // Start scanning loop
+    // Scanner produces opportunities, executor workers consume them
     this.running = true;
-    await this.scanLoop();
+    this.watchdog.start();
+    if (CONFIG.PRICE_TRIGGER_BPS > 0 && !this.scanner.onPriceMove(CONFIG.PRICE_TRIGGER_BPS, pair => this.onPriceTrigger(pair))) {
+      logger.warn('Price feed unavailable, price-triggered rescans disabled');
+    }
+    this.executors = Promise.all(
+      Array.from({ length: CONFIG.MAX_CONCURRENT_EXECUTIONS }, () => this.executeLoop())
+    ).then(() => {});
+    await Promise.all([this.scanLoop(), this.statsLoop(), this.sweepLoop(), this.executors]);
+  }
+
+  /**
+   * Create token accounts for USDC and every pair token up front so the first trade doesn't wait on them
+   */
+  private async preflightAtas(): Promise<void> {
+    const symbols = Array.from(new Set(['USDC', ...CONFIG.ARB_PAIRS.filter(p => p.enabled).flatMap(p => p.pair.split('/'))]));
+    try {
+      const { missing, rentLamports, created } = await this.executor.preflightAtas(symbols);
+      const rentSol = (rentLamports / LAMPORTS_PER_SOL).toFixed(6);
+      if (missing === 0) {
+        logger.debug('Token accounts: all present');
+      } else if (CONFIG.DRY_RUN) {
+        logger.info(`[DRY RUN] ${missing} token account(s) missing, creating them would cost ${rentSol} SOL rent`);
+      } else if (created) {
+        logger.success(`Created ${missing} token account(s) for ${rentSol} SOL rent`);
+      } else {
+        logger.warn(`Failed to create ${missing} token account(s); trades will retry`);
+      }
+    } catch (e) {
+      logger.warn(`Token account preflight failed: ${e}`);
+    }
+  }
+
+  /**
+   * Log Kamino deposit TVL and alert when it drops TVL_DROP_ALERT_PERCENT below each market's baseline
+   * (its TVL when first loaded), comparing only markets present in both snapshots
+   * Distinguishes "protocol down or drained" from "nothing to arbitrage" when scans come up empty
+   */
+  private async checkProtocolTvl(): Promise<void> {
+    try {
+      const perMarket = await this.executor.getKaminoTvl();
+      const loaded = new Map(Array.from(perMarket).filter((entry): entry is [string, number] => entry[1] !== null));
+      const failed = Array.from(perMarket.keys()).filter(address => !loaded.has(address));
+      if (loaded.size === 0) {
+        logger.warn('Kamino TVL: no markets loaded');
+        return;
+      }
+      const total = Array.from(loaded.values()).reduce((sum, tvl) => sum + tvl, 0);
+      const markets = Array.from(loaded.entries())
+        .map(([address, tvl]) => `${address.substring(0, 8)} $${Math.round(tvl).toLocaleString()}`)
+        .join(', ');
+      logger.info(`Kamino TVL: $${Math.round(total).toLocaleString()} (${markets})`);
+
+      for (const [address, tvl] of loaded) {
+        if (!this.tvlBaseline.has(address)) {
+          this.tvlBaseline.set(address, tvl);
+        }
+      }
+      // A market that failed to reload would look like a drop in the total
+      if (failed.length > 0) {
+        logger.warn(`Kamino TVL: ${failed.length} market(s) failed to reload (${failed.map(a => a.substring(0, 8)).join(', ')}), skipping the drop check`);
+        return;
+      }
+      const drop = tvlDropPercent(this.tvlBaseline, loaded);
+      if (drop && drop.dropPercent >= CONFIG.TVL_DROP_ALERT_PERCENT) {
+        const message = `Kamino TVL fell ${drop.dropPercent.toFixed(1)}% since its baseline ($${Math.round(drop.before).toLocaleString()} → $${Math.round(drop.after).toLocaleString()}), possible oracle or market incident`;
+        logger.warn(`⚠️  ${message}`);
+        void this.slack?.notifyText(`⚠️ ${message}`);
+      }
+    } catch (e) {
+      logger.warn(`Kamino TVL check failed: ${e}`);
+    }
+  }
+
+  /**
+   * Warn (and notify Slack, at most every LOW_BALANCE_ALERT_INTERVAL_MS) that trades are being skipped to protect the SOL reserve
+   */
+  private alertLowFeeBalance(reason: string): void {
+    if (Date.now() - this.lastLowBalanceAlert < LOW_BALANCE_ALERT_INTERVAL_MS) return;
+    this.lastLowBalanceAlert = Date.now();
+    logger.warn(`⚠️  SOL reserve reached, skipping trades: ${reason}`);
+    void this.slack?.notifyText(`⚠️ SOL reserve reached on ${this.keypair.publicKey.toBase58()}, trades are being skipped: ${reason}`);
   }
 
   private printBanner(): void {
@@ -125,47 +690,33 @@ class ArbitrageBot {
 
   private async scanLoop(): Promise<void> {
     while (this.running) {
+      this.watchdog.heartbeat();
       try {
         const scanStart = Date.now();
         stats.totalScans++;
         stats.lastScanTime = scanStart;
+        this.cycleUsage = { executions: 0, flashUsd: 0 };
+        this.cycleBatch = hasCycleLimits() ? [] : null;
+
+        // Scan all pairs, handing each opportunity off as soon as its pair is scanned
+        stats.lastScanReport = await withSpan('scan', {}, async span => {
+          const report = await this.scanner.scanAllPairs(opp => this.handleOpportunity(opp));
+          span.setAttributes({
+            'scan.opportunities_found': report.opportunities.length,
+            'scan.pairs_quoted': Object.values(report.perDex).reduce((max, s) => Math.max(max, s!.quotes + s!.misses), 0),
+            'scan.duration_ms': report.durationMs,
+          });
+          return report;
+        });
 
-        // Scan all pairs
-        const opportunities = await this.scanner.scanAllPairs();
-
-        // Filter by minimum profit
-        const profitableOpportunities = opportunities.filter(
-          opp => opp.calculation.netProfit >= CONFIG.MIN_PROFIT_USD
-        );
-
-        if (profitableOpportunities.length > 0) {
-          stats.opportunitiesDetected += profitableOpportunities.length;
-          
-          // Keep recent opportunities for API
-          stats.recentOpportunities = [
-            ...profitableOpportunities,
-            ...stats.recentOpportunities,
-          ].slice(0, 100);
-
-          // Log opportunities
-          for (const opp of profitableOpportunities) {
-            this.logOpportunity(opp);
-
-            // Execute if auto-execute is enabled
-            if (CONFIG.AUTO_EXECUTE) {
-              const result = await this.executor.execute(opp);
-              stats.tradesExecuted++;
-              if (result.success) {
-                stats.tradesSuccessful++;
-                stats.totalProfitUsd += result.actualProfit || 0;
-              }
-            }
-          }
+        if (this.cycleBatch) {
+          const batch = this.cycleBatch;
+          this.cycleBatch = null;
+          await this.queueWithinCycleLimits(this.withDeferred(batch));
         }
 
-        // Log periodic stats
-        if (stats.totalScans % 60 === 0) {
-          this.logStats();
+        if (CONFIG.TVL_CHECK_SCANS > 0 && stats.totalScans % CONFIG.TVL_CHECK_SCANS === 0) {
+          void this.checkProtocolTvl();
         }
 
         // Wait for next scan
@@ -180,6 +731,327 @@ class ArbitrageBot {
     }
   }
 
+  /**
+   * Rescan a pair right away after its pool price moved, instead of waiting for the next full scan
+   */
+  private onPriceTrigger(pair: string): void {
+    if (!this.running || this.triggeredPairs.has(pair)) return;
+    this.triggeredPairs.add(pair);
+    stats.priceTriggers++;
+    const triggeredAt = Date.now();
+    logger.debug(`${pair}: price moved ${CONFIG.PRICE_TRIGGER_BPS} bps, rescanning`);
+
+    this.scanner.scanPair(pair)
+      .then(opp => {
+        if (opp) {
+          this.triggerTimes.set(opp, triggeredAt);
+          return this.handleOpportunity(opp);
+        }
+      })
+      .catch(e => logger.error(`Triggered scan error for ${pair}: ${e}`))
+      .finally(() => this.triggeredPairs.delete(pair));
+  }
+
+  /**
+   * Record a scanned opportunity and queue it for execution if it clears its route's minimum profit
+   */
+  private async handleOpportunity(opp: ArbitrageOpportunity): Promise<void> {
+    if (opp.calculation.netProfit < minProfitFor(opp)) {
+      return;
+    }
+
+    stats.opportunitiesDetected++;
+
+    // Keep recent opportunities for API
+    stats.recentOpportunities = [opp, ...stats.recentOpportunities].slice(0, 100);
+
+    this.logOpportunity(opp);
+
+    // Hand off to the executor workers if auto-execute is enabled
+    if (CONFIG.AUTO_EXECUTE && this.pausedAt === null) {
+      if (this.cycleBatch) {
+        this.cycleBatch.push(opp);
+      } else if (hasCycleLimits()) {
+        // Price-triggered between scans: counts toward the current cycle
+        await this.queueWithinCycleLimits([opp]);
+      } else {
+        await this.executionQueue.push(opp);
+      }
+    }
+  }
+
+  /**
+   * A cycle's opportunities plus those deferred from earlier cycles that are still fresh
+   * A route found again by this scan replaces its deferred copy
+   */
+  private withDeferred(batch: ArbitrageOpportunity[]): ArbitrageOpportunity[] {
+    const scanned = new Set(batch.map(routeKey));
+    const maxAgeMs = CONFIG.MAX_QUOTE_AGE_SECONDS * 1000;
+    const carried = this.deferred.filter(o => !scanned.has(routeKey(o)) && Date.now() - o.timestamp <= maxAgeMs);
+    this.deferred = [];
+    return [...batch, ...carried];
+  }
+
+  /**
+   * Queue opportunities highest score first until a per-cycle limit is reached
+   * The rest are deferred to the next cycle (see withDeferred)
+   */
+  private async queueWithinCycleLimits(opportunities: ArbitrageOpportunity[]): Promise<void> {
+    const deferred: ArbitrageOpportunity[] = [];
+    for (const opp of [...opportunities].sort((a, b) => scoreOpportunity(b) - scoreOpportunity(a))) {
+      const overExecutions = CONFIG.MAX_EXECUTIONS_PER_CYCLE > 0
+        && this.cycleUsage.executions >= CONFIG.MAX_EXECUTIONS_PER_CYCLE;
+      const overCapital = CONFIG.MAX_FLASH_USD_PER_CYCLE > 0
+        && this.cycleUsage.flashUsd + opp.flashAmount > CONFIG.MAX_FLASH_USD_PER_CYCLE;
+      if (overExecutions || overCapital) {
+        deferred.push(opp);
+        continue;
+      }
+      this.cycleUsage.executions++;
+      this.cycleUsage.flashUsd += opp.flashAmount;
+      await this.executionQueue.push(opp);
+    }
+
+    if (deferred.length > 0) {
+      this.deferred.push(...deferred);
+      stats.deferredByCycleLimit += deferred.length;
+      logger.warn(`Cycle limit reached (${this.cycleUsage.executions} executions, $${this.cycleUsage.flashUsd.toLocaleString()} flash), deferred to next cycle: ${deferred.map(o => `${o.pair} $${o.calculation.netProfit.toFixed(2)}`).join(', ')}`);
+    }
+  }
+
+  /**
+   * Persist stats, log them and refresh the flash limit on a timer independent of scanning
+   */
+  private async statsLoop(): Promise<void> {
+    let ticks = 0;
+    while (this.running) {
+      await this.sleep(CONFIG.SCAN_INTERVAL_MS * 10);
+      if (!this.running) break;
+      ticks++;
+
+      await this.withdrawProfits();
+
+      // Persist stats
+      if (CONFIG.STATS_FILE) {
+        saveStats(CONFIG.STATS_FILE);
+      }
+
+      // Log periodic stats
+      if (ticks % 6 === 0) {
+        this.logStats();
+        await this.refreshFlashLimit();
+        await this.executor.refreshLookupTable();
+      }
+    }
+  }
+
+  /**
+   * Send SOL/USDC above the hot wallet targets to the withdraw address
+   */
+  private async withdrawProfits(): Promise<void> {
+    if (!this.withdrawer) return;
+    try {
+      const { sol, usdc } = await this.withdrawer.withdrawExcess();
+      stats.withdrawnSol += sol;
+      stats.withdrawnUsdc += usdc;
+    } catch (e) {
+      logger.error(`Withdraw error: ${e}`);
+    }
+  }
+
+  /**
+   * Periodically swap leftover token balances into the profit target mint
+   */
+  private async sweepLoop(): Promise<void> {
+    if (CONFIG.PROFIT_SWEEP_INTERVAL_MS <= 0) return;
+
+    while (this.running) {
+      await this.sleep(CONFIG.PROFIT_SWEEP_INTERVAL_MS);
+      if (!this.running) break;
+      try {
+        await this.sweeper.sweep();
+      } catch (e) {
+        logger.error(`Sweep error: ${e}`);
+      }
+    }
+  }
+
+  /**
+   * Scan once and simulate the full transaction of the top opportunities (`simulate` command)
+   * Nothing is sent, whatever DRY_RUN says
+   * @returns Number of simulations that failed or couldn't be run
+   */
+  async simulateTop(count: number): Promise<number> {
+    await this.scanner.initialize();
+    await this.executor.initialize(true);
+    await this.refreshFlashLimit();
+
+    const report = await this.scanner.scanAllPairs();
+    const top = report.opportunities
+      .filter(opp => opp.calculation.netProfit >= minProfitFor(opp))
+      .sort((a, b) => b.calculation.netProfit - a.calculation.netProfit)
+      .slice(0, count);
+    logger.info(`Simulating ${top.length} of ${report.opportunities.length} opportunities`);
+
+    let failed = 0;
+    for (const opp of top) {
+      this.logOpportunity(opp);
+      const simulation = await this.executor.simulate(opp);
+      if (!simulation || simulation.error) {
+        failed++;
+        logger.error(`   ${opp.pair} ${describeRoute(opp)}: ${simulation?.error?.message ?? 'could not simulate'}`);
+        continue;
+      }
+      logger.success(`   ${opp.pair} ${describeRoute(opp)}: ${simulation.unitsConsumed} CU, simulated $${simulation.usdcDelta!.toFixed(4)} vs expected $${opp.calculation.netProfit.toFixed(2)}`);
+    }
+    logger.info(`Simulations: ${top.length - failed} succeeded, ${failed} failed`);
+    return failed;
+  }
+
+  /**
+   * Re-scan one pair every interval and print its pool prices, spread and scan outcome (`watch` command)
+   * Marks the tick where it turns into an opportunity; with execute, trades it like the main loop (honoring DRY_RUN)
+   */
+  async watchPair(pair: string, intervalMs: number, options: { json: boolean; execute: boolean }): Promise<void> {
+    await this.scanner.initialize();
+    await this.refreshFlashLimit();
+    if (options.execute) {
+      await this.executor.initialize();
+    }
+
+    let wasOpportunity = false;
+    while (true) {
+      const { opportunity, outcome, prices } = await this.scanner.inspectPair(pair);
+      const values = Object.values(prices) as number[];
+      const spreadPercent = values.length >= 2 ? Math.max(...values) / Math.min(...values) - 1 : null;
+      const minProfit = opportunity ? minProfitFor(opportunity) : null;
+
+      if (options.json) {
+        console.log(JSON.stringify({
+          time: new Date().toISOString(),
+          pair,
+          prices,
+          spreadPercent,
+          outcome,
+          route: opportunity ? `${opportunity.buyDex}-${opportunity.sellDex}` : null,
+          flashAmount: opportunity?.flashAmount ?? null,
+          netProfit: opportunity?.calculation.netProfit ?? null,
+          minProfit,
+        }));
+      } else {
+        const priceText = Object.entries(prices).map(([dex, price]) => `${dex} $${price!.toFixed(6)}`).join(' | ');
+        const line = `[${new Date().toISOString()}] ${pair} ${priceText || 'no prices'}` +
+          ` | spread ${spreadPercent === null ? '-' : `${(spreadPercent * 100).toFixed(3)}%`} | ${outcome}` +
+          (opportunity ? ` | $${opportunity.flashAmount.toFixed(0)} → $${opportunity.calculation.netProfit.toFixed(2)} (min $${minProfit})` : '');
+        if (opportunity && !wasOpportunity) {
+          logger.opportunity(`${line}  ◀ became profitable`);
+        } else {
+          logger.info(line);
+        }
+      }
+      wasOpportunity = opportunity !== null;
+
+      if (options.execute && opportunity && opportunity.calculation.netProfit >= minProfit!) {
+        recordExecution(await this.executor.execute(opportunity));
+      }
+      await this.sleep(intervalMs);
+    }
+  }
+
+  /**
+   * Run one sweep (`sweep` command); in dry run only prints what would be swapped
+   */
+  async sweepOnce(overrides: SweepOverrides = {}): Promise<void> {
+    logger.info(`Sweeping token balances into ${CONFIG.PROFIT_TARGET_MINT}${CONFIG.DRY_RUN ? ' (DRY RUN)' : ''}`);
+    await this.sweeper.sweep(overrides);
+  }
+
+  /**
+   * Executor worker: take the most profitable queued opportunity until the queue is closed and drained
+   */
+  private async executeLoop(): Promise<void> {
+    let opp: ArbitrageOpportunity | null;
+    while ((opp = await this.executionQueue.pop()) !== null) {
+      // Queued before a pause
+      if (this.pausedAt !== null) {
+        logger.debug(`${opp.pair}: Execution paused, dropping queued opportunity`);
+        continue;
+      }
+      this.inFlight++;
+      try {
+        const triggeredAt = this.triggerTimes.get(opp);
+        if (triggeredAt !== undefined) {
+          stats.triggeredExecutions++;
+          stats.triggerLatencyMsTotal += Date.now() - triggeredAt;
+        }
+        const result = await withSpan('execute', {
+          pair: opp.pair,
+          'execute.route': `${opp.buyDex}-${opp.sellDex}`,
+          'execute.expected_profit_usd': opp.calculation.netProfit,
+        }, async span => {
+          const executed = await this.executor.execute(opp);
+          span.setAttributes({ 'execute.success': executed.success, 'execute.error_kind': executed.error?.kind ?? '' });
+          return executed;
+        });
+        this.historyLog?.write(historyEntry(opp, result));
+        if (!recordExecution(result)) {
+          continue;
+        }
+        if (result.success && result.txSignature) {
+          void this.slack?.notifyTrade(opp, result);
+        }
+        if (result.error?.kind === 'insufficient_fee_balance') {
+          this.alertLowFeeBalance(result.error.message);
+        }
+      } catch (e) {
+        logger.error(`Execution error for ${opp.pair}: ${e}`);
+      } finally {
+        this.inFlight--;
+      }
+    }
+  }
+
+  /**
+   * Stop executing opportunities (scanning, stats and caches keep running)
+   * @returns false if already paused
+   */
+  pause(source: string): boolean {
+    if (this.pausedAt !== null) return false;
+    this.pausedAt = Date.now();
+    logger.warn(`Execution paused (${source})`);
+    void this.slack?.notifyText(`⏸️ Execution paused (${source})`);
+    return true;
+  }
+
+  /**
+   * @returns false if not paused
+   */
+  resume(source: string): boolean {
+    if (this.pausedAt === null) return false;
+    const pausedFor = this.formatUptime(Date.now() - this.pausedAt);
+    this.pausedAt = null;
+    logger.success(`Execution resumed after ${pausedFor} (${source})`);
+    void this.slack?.notifyText(`▶️ Execution resumed after ${pausedFor} (${source})`);
+    return true;
+  }
+
+  togglePause(source: string): void {
+    if (!this.pause(source)) {
+      this.resume(source);
+    }
+  }
+
+  /**
+   * Cap sizing by the configured max and the Kamino USDC reserve's available liquidity
+   */
+  private async refreshFlashLimit(): Promise<void> {
+    const available = await this.executor.getFlashLiquidity().catch(() => 0);
+    const limit = flashLoanLimit(CONFIG, available);
+    this.scanner.setMaxFlashAmount(limit);
+    setFlashLoanFee(this.executor.getFlashLoanFee());
+    logger.debug(`Flash loan limit: $${limit.toLocaleString()} (reserve: $${available.toLocaleString()}), fee ${(FLASH_LOAN_FEE * 100).toFixed(4)}%`);
+  }
+
   private logOpportunity(opp: ArbitrageOpportunity): void {
     logger.opportunity(`OPPORTUNITY: ${opp.pair} ${(opp.spreadPercent * 100).toFixed(2)}% spread`);
     logger.info(`   Buy: ${opp.buyDex} ($${opp.buyPrice.toFixed(4)})`);
@@ -195,7 +1067,44 @@ class ArbitrageBot {
       ? (stats.tradesSuccessful / stats.tradesExecuted * 100).toFixed(1)
       : '0.0';
     
-    logger.stats(`Stats: ${stats.totalScans} scans | ${stats.opportunitiesDetected} opportunities | ${stats.tradesExecuted} executed | $${stats.totalProfitUsd.toFixed(2)} profit | ${uptime} uptime`);
+    const netProfit = stats.totalProfitUsd - stats.totalLossUsd;
+    const avgProfit = stats.tradesExecuted > 0 ? netProfit / stats.tradesExecuted : 0;
+    
+    logger.stats(`Stats: ${stats.totalScans} scans | ${stats.opportunitiesDetected} opportunities | ${uptime} uptime`);
+    if (stats.lastScanReport) {
+      logger.stats(`Last scan: ${formatScanReport(stats.lastScanReport)}`);
+    }
+    if (this.pausedAt !== null) {
+      logger.stats(`Execution PAUSED since ${new Date(this.pausedAt).toISOString()} (${this.formatUptime(Date.now() - this.pausedAt)})`);
+    }
+    logger.stats(`Arbitrage: ${stats.tradesExecuted} executed | ${stats.tradesSuccessful} ok | ${stats.tradesFailed} failed (${successRate}% success) | ${stats.skippedHighImpact} high-impact skips | ${stats.skippedStale} stale skips`);
+    logger.stats(`Arbitrage P&L: $${stats.totalProfitUsd.toFixed(2)} profit | $${stats.totalLossUsd.toFixed(2)} loss | $${netProfit.toFixed(2)} net | $${avgProfit.toFixed(2)}/trade`);
+    logger.stats(`Fees: ${(stats.totalFeesLamports / LAMPORTS_PER_SOL).toFixed(6)} SOL paid by ${stats.tradesSuccessful} landed trades (included in P&L)`);
+    const tokenProfits = Object.entries(stats.tokenProfits).filter(([, amount]) => amount !== 0);
+    if (tokenProfits.length > 0) {
+      logger.stats(`Token P&L: ${tokenProfits.map(([mint, amount]) => `${mint.substring(0, 8)} ${amount >= 0 ? '+' : ''}${amount}`).join(' | ')}`);
+    }
+    if (detectToSubmitMs.length > 0) {
+      logger.stats(`Latency (last ${detectToSubmitMs.length} landed): detect→submit p50 ${percentile(detectToSubmitMs, 50)}ms p95 ${percentile(detectToSubmitMs, 95)}ms | submit→confirm p50 ${percentile(submitToConfirmMs, 50)}ms p95 ${percentile(submitToConfirmMs, 95)}ms`);
+    }
+    if (CONFIG.PRICE_TRIGGER_BPS > 0) {
+      const avgLatency = stats.triggeredExecutions > 0 ? stats.triggerLatencyMsTotal / stats.triggeredExecutions : 0;
+      logger.stats(`Price triggers: ${stats.priceTriggers} rescans | ${stats.triggeredExecutions} executed | ${avgLatency.toFixed(0)}ms avg trigger-to-execution`);
+    }
+    if (hasCycleLimits()) {
+      logger.stats(`Cycle limits: ${stats.deferredByCycleLimit} opportunities deferred`);
+    }
+    const failures = Object.entries(stats.failuresByKind).sort(([, a], [, b]) => b - a);
+    if (failures.length > 0) {
+      logger.stats(`Failures: ${failures.map(([kind, count]) => `${kind} ${count}`).join(' | ')}`);
+    }
+    if (CONFIG.PROFIT_WITHDRAW_ADDRESS) {
+      logger.stats(`Withdrawn: ${stats.withdrawnSol.toFixed(4)} SOL | $${stats.withdrawnUsdc.toFixed(2)} USDC`);
+    }
+    if (CONFIG.FALLBACK_RPC_URLS.length > 0) {
+      const rpc = this.rpcPool.getStats();
+      logger.stats(`RPC: ${rpc.active.substring(0, 50)} | ${rpc.failovers} failovers`);
+    }
   }
 
   private formatUptime(ms: number): string {
@@ -215,10 +1124,87 @@ class ArbitrageBot {
       methods: ['GET', 'POST'],
     }));
 
+    app.use('/api', (req, res, next) => {
+      if (req.path === '/health' || isAuthorized(req.headers.authorization)) {
+        next();
+        return;
+      }
+      res.status(401).json({ error: 'unauthorized' });
+    });
+
     app.get('/api/stats', (req, res) => {
       res.json(this.getBotData());
     });
 
+    app.get('/api/status', async (req, res) => {
+      const balance = await this.connection.getBalance(this.keypair.publicKey).catch(() => null);
+      res.json({
+        status: !this.running ? 'stopped' : this.pausedAt !== null ? 'paused' : 'running',
+        mode: CONFIG.DRY_RUN ? 'dry-run' : CONFIG.AUTO_EXECUTE ? 'live' : 'monitor',
+        uptime: Math.floor((Date.now() - stats.startTime) / 1000),
+        lastScanTime: stats.lastScanTime || null,
+        paused: this.pausedAt !== null,
+        pausedAt: this.pausedAt,
+        wallet: this.keypair.publicKey.toBase58(),
+        solBalance: balance === null ? null : balance / LAMPORTS_PER_SOL,
+      });
+    });
+
+    // Control endpoints change trading, so they always need the token (never the open fallback)
+    if (CONFIG.API_TOKEN) {
+      app.use('/api/control', (req, res, next) => {
+        if (hasApiToken(req.headers.authorization)) {
+          next();
+          return;
+        }
+        res.status(401).json({ error: 'unauthorized' });
+      });
+
+      app.post('/api/control/pause', (req, res) => {
+        this.pause('API');
+        res.json({ paused: true, pausedAt: this.pausedAt });
+      });
+
+      app.post('/api/control/resume', (req, res) => {
+        this.resume('API');
+        res.json({ paused: false });
+      });
+    } else {
+      logger.warn('API_TOKEN not set: /api/control/pause and /api/control/resume are disabled (SIGUSR1 still toggles)');
+    }
+
+    app.get('/api/scan', (req, res) => {
+      const report = stats.lastScanReport;
+      res.json(report && {
+        timestamp: stats.lastScanTime,
+        opportunities: report.opportunities.length,
+        outcomes: report.outcomes,
+        perDex: report.perDex,
+        durationMs: report.durationMs,
+        errors: report.errors,
+      });
+    });
+
+    app.get('/api/watchlist', (req, res) => {
+      res.json({
+        pairs: CONFIG.ARB_PAIRS.map(p => ({
+          pair: p.pair,
+          enabled: p.enabled,
+          minProfitUsd: p.minProfitUsd ?? CONFIG.MIN_PROFIT_USD,
+          maxFlashAmount: p.maxFlashAmount ?? CONFIG.MAX_FLASH_LOAN_USD,
+        })),
+      });
+    });
+
+    app.get('/api/executions', (req, res) => {
+      if (!this.historyLog) {
+        res.status(404).json({ error: 'ARBITRAGE_LOG_PATH not set' });
+        return;
+      }
+      const limit = Math.min(Math.max(parseInt(String(req.query.limit ?? '')) || 20, 1), MAX_EXECUTIONS_LIMIT);
+      res.json({ executions: this.historyLog.tail(limit) });
+    });
+
     app.get('/api/opportunities', (req, res) => {
       res.json({
         count: stats.recentOpportunities.length,
@@ -248,7 +1234,12 @@ class ArbitrageBot {
     // Create WebSocket server
     const wss = new WebSocketServer({ server });
 
-    wss.on('connection', (ws: WebSocket) => {
+    wss.on('connection', (ws: WebSocket, req: http.IncomingMessage) => {
+      const token = new URL(req.url ?? '/', 'http://localhost').searchParams.get('token');
+      if (!isAuthorized(req.headers.authorization ?? (token ? `Bearer ${token}` : undefined))) {
+        ws.close(1008, 'unauthorized');
+        return;
+      }
       logger.info('[WS] Client connected');
       this.wsClients.add(ws);
 
@@ -264,21 +1255,21 @@ class ArbitrageBot {
       });
     });
 
-    server.listen(CONFIG.API_PORT, '0.0.0.0', () => {
-      logger.success(`API + WebSocket server running on port ${CONFIG.API_PORT}`);
+    server.listen(CONFIG.API_PORT, CONFIG.API_BIND_ADDR, () => {
+      logger.success(`API + WebSocket server running on ${CONFIG.API_BIND_ADDR}:${CONFIG.API_PORT}${CONFIG.API_TOKEN ? ' (token required)' : ''}`);
     });
   }
 
   private getBotData() {
     return {
       botData: {
-        status: this.running ? 'running' : 'stopped',
+        status: !this.running ? 'stopped' : this.pausedAt !== null ? 'paused' : 'running',
         uptime: Math.floor((Date.now() - stats.startTime) / 1000),
         totalScans: stats.totalScans,
         opportunitiesFound: stats.opportunitiesDetected,
         executedTrades: stats.tradesExecuted,
-        failedTrades: stats.tradesExecuted - stats.tradesSuccessful,
-        totalProfit: stats.totalProfitUsd,
+        failedTrades: stats.tradesFailed,
+        totalProfit: stats.totalProfitUsd - stats.totalLossUsd,
         todayProfit: stats.totalProfitUsd,
         solPrice: 0,
       },
@@ -313,11 +1304,35 @@ class ArbitrageBot {
     }
   }
 
-  stop(): void {
+  /**
+   * Stop scanning, drop queued opportunities and wait for in-flight trades (flash loan, swaps,
+   * wSOL unwrap), sweeps and withdrawals to land or fail, then log and persist the final stats
+   */
+  async stop(): Promise<void> {
     this.running = false;
+    this.watchdog.stop();
     if (this.scanTimer) {
       clearTimeout(this.scanTimer);
     }
+    this.executionQueue.close();
+    this.scanner.stop().catch(() => {});
+    const dropped = this.executionQueue.clear();
+    if (dropped > 0) {
+      logger.info(`Dropped ${dropped} queued opportunities`);
+    }
+    if (this.inFlight > 0) {
+      logger.info(`Waiting for ${this.inFlight} in-flight trade(s)...`);
+    }
+    if (this.sweeper.isRunning()) {
+      logger.info('Waiting for the running sweep...');
+    }
+    await Promise.all([this.executors, this.sweeper.idle(), this.withdrawer?.idle()]);
+    this.historyLog?.flush();
+    this.logStats();
+    if (CONFIG.STATS_FILE) {
+      saveStats(CONFIG.STATS_FILE);
+    }
+    await shutdownTracing();
     logger.info('Bot stopped');
   }
 
@@ -331,21 +1346,160 @@ class ArbitrageBot {
 // ============================================
 
 async function main(): Promise<void> {
+  // `alt show`: print the lookup table and exit
+  if (process.argv[2] === 'alt') {
+    if (process.argv[3] !== 'show' || !CONFIG.LOOKUP_TABLE_FILE) {
+      logger.error('Usage: LOOKUP_TABLE_FILE=<path> npm run alt -- show');
+      process.exit(1);
+    }
+    await showLookupTable(new Connection(CONFIG.RPC_URL, 'confirmed'), CONFIG.LOOKUP_TABLE_FILE);
+    return;
+  }
+
+  // `nonce create <count>`: create durable nonce accounts owned by the wallet and exit
+  if (process.argv[2] === 'nonce') {
+    const count = parseInt(process.argv[4] ?? '');
+    if (process.argv[3] !== 'create' || !(count >= 1 && count <= 20) || !CONFIG.WALLET_PRIVATE_KEY) {
+      logger.error('Usage: npm run nonce -- create <count> (1-20, needs WALLET_PRIVATE_KEY)');
+      process.exit(1);
+    }
+    const payer = Keypair.fromSecretKey(bs58.decode(CONFIG.WALLET_PRIVATE_KEY));
+    const created = await createNonceAccounts(new Connection(CONFIG.RPC_URL, 'confirmed'), payer, count);
+    logger.info(`NONCE_ACCOUNTS=${[...CONFIG.NONCE_ACCOUNTS, ...created.map(a => a.toBase58())].join(',')}`);
+    return;
+  }
+
   try {
+    validateConfig(CONFIG);
+    configureHttp({ timeoutMs: CONFIG.HTTP_TIMEOUT_MS, maxRetries: CONFIG.MAX_RETRIES });
+
+    // Every pair token needs a mint and decimals before any client is built
+    const unknown = await tokenRegistry.resolve(
+      Array.from(new Set(CONFIG.ARB_PAIRS.flatMap(p => p.pair.split('/')))),
+      new JupiterClient({ baseUrl: CONFIG.JUPITER_API_URL || undefined, apiKey: CONFIG.JUPITER_API_KEY || undefined }),
+      CONFIG.TOKEN_LIST_FILE || undefined
+    );
+    if (unknown.length > 0) {
+      throw new Error(`Unknown tokens in ARB_PAIRS: ${unknown.join(', ')}`);
+    }
+
+    // `sweep [--min-usd <n>] [--close]`: swap leftover balances once and exit
+    if (process.argv[2] === 'sweep') {
+      const overrides: SweepOverrides = {};
+      const index = process.argv.indexOf('--min-usd');
+      if (index >= 0) {
+        const minUsd = parseFloat(process.argv[index + 1] ?? '');
+        if (!(minUsd >= 0)) {
+          logger.error('Usage: npm run sweep -- [--min-usd <n>] [--close]');
+          process.exit(1);
+        }
+        overrides.dustUsd = minUsd;
+      }
+      if (process.argv.includes('--close')) {
+        overrides.closeEmptyAccounts = true;
+      }
+      await new ArbitrageBot().sweepOnce(overrides);
+      return;
+    }
+
+    // `balance`: list the wallet's SOL and token accounts and exit
+    if (process.argv[2] === 'balance') {
+      if (!CONFIG.WALLET_PRIVATE_KEY) {
+        throw new Error('WALLET_PRIVATE_KEY not set in environment');
+      }
+      const keypair = Keypair.fromSecretKey(bs58.decode(CONFIG.WALLET_PRIVATE_KEY));
+      logWalletInventory(await fetchWalletInventory(new Connection(CONFIG.RPC_URL, CONFIG.COMMITMENT), keypair.publicKey));
+      return;
+    }
+
+    // `watch <pair> [--interval <s>] [--json] [--execute]`: re-scan one pair until interrupted
+    if (process.argv[2] === 'watch') {
+      const pair = process.argv[3];
+      const index = process.argv.indexOf('--interval');
+      const intervalSeconds = index >= 0 ? parseFloat(process.argv[index + 1] ?? '') : 5;
+      if (!pair || !CONFIG.ARB_PAIRS.some(p => p.pair === pair) || !(intervalSeconds > 0)) {
+        logger.error('Usage: npm run watch -- <pair from ARB_PAIRS> [--interval <seconds>] [--json] [--execute]');
+        process.exit(1);
+      }
+      await new ArbitrageBot().watchPair(pair, intervalSeconds * 1000, {
+        json: process.argv.includes('--json'),
+        execute: process.argv.includes('--execute'),
+      });
+      return;
+    }
+
+    // `simulate [count]`: simulate the top opportunities of one scan without sending; exit 1 if any fail
+    if (process.argv[2] === 'simulate') {
+      const count = parseInt(process.argv[3] ?? '5');
+      if (!(count >= 1)) {
+        logger.error('Usage: npm run simulate -- [count]');
+        process.exit(1);
+      }
+      const failed = await new ArbitrageBot().simulateTop(count);
+      process.exit(failed > 0 ? 1 : 0);
+    }
+
+    // `snapshot <dir>`: record every pair's pool prices once and exit
+    if (process.argv[2] === 'snapshot') {
+      const dir = process.argv[3];
+      if (!dir) {
+        logger.error('Usage: npm run snapshot -- <dir>');
+        process.exit(1);
+      }
+      const connection = new Connection(CONFIG.RPC_URL, CONFIG.COMMITMENT);
+      const pairs = CONFIG.ARB_PAIRS.filter(p => p.enabled).map(p => p.pair);
+      const snapshot = await recordSnapshot(connection, new Scanner(connection, { ...scannerOptions(), priceFeed: false }), pairs);
+      logger.success(`Snapshot of ${Object.keys(snapshot.pairs).length} pairs at slot ${snapshot.slot}: ${saveSnapshot(dir, snapshot)}`);
+      return;
+    }
+
+    // `replay --snapshot <dir>`: scan a recorded snapshot offline (never executes) and exit
+    if (process.argv[2] === 'replay') {
+      const index = process.argv.indexOf('--snapshot');
+      const dir = index >= 0 ? process.argv[index + 1] : undefined;
+      if (!dir) {
+        logger.error('Usage: npm run replay -- --snapshot <dir>');
+        process.exit(1);
+      }
+      const report = await replaySnapshot(
+        new Connection(CONFIG.RPC_URL, CONFIG.COMMITMENT),
+        loadSnapshot(dir),
+        scannerOptions(),
+        CONFIG.MAX_FLASH_LOAN_USD,
+        minProfitFor
+      );
+      logReplayReport(report);
+      return;
+    }
+
+    if (CONFIG.STATS_FILE) {
+      loadStats(CONFIG.STATS_FILE);
+    }
+    if (CONFIG.OTLP_ENDPOINT) {
+      initTracing(CONFIG.OTLP_ENDPOINT);
+    }
     const bot = new ArbitrageBot();
     
-    // Handle shutdown
-    process.on('SIGINT', () => {
-      logger.info('Received SIGINT, shutting down...');
-      bot.stop();
-      process.exit(0);
-    });
+    // Handle shutdown: let in-flight trades finish, force exit on timeout or a second signal
+    let stopping = false;
+    const shutdown = (signal: string) => {
+      if (stopping) {
+        logger.warn(`Received ${signal} again, exiting now`);
+        process.exit(1);
+      }
+      stopping = true;
+      logger.info(`Received ${signal}, shutting down...`);
+      setTimeout(() => {
+        logger.error(`Shutdown still waiting after ${shutdownTimeoutMs() / 1000}s, exiting`);
+        process.exit(1);
+      }, shutdownTimeoutMs()).unref();
+      bot.stop().finally(() => process.exit(0));
+    };
+    process.on('SIGINT', () => shutdown('SIGINT'));
+    process.on('SIGTERM', () => shutdown('SIGTERM'));
 
-    process.on('SIGTERM', () => {
-      logger.info('Received SIGTERM, shutting down...');
-      bot.stop();
-      process.exit(0);
-    });
+    // `kill -USR1 <pid>` pauses or resumes execution
+    process.on('SIGUSR1', () => bot.togglePause('SIGUSR1'));
 
     await bot.start();
 
@@ -0,0 +1,429 @@
+/**
+ * Bot Configuration
+ * Settings from env vars (with .env and `--config-file` filling the gaps) or built in code with
+ * configBuilder(), validated before anything connects
+ */
+
+import { Commitment, Keypair, PublicKey } from '@solana/web3.js';
+import bs58 from 'bs58';
+import fs from 'fs';
+import { parse as parseToml } from 'smol-toml';
+import { DEX_LIST, DEFAULT_PAIR_CONFIGS, ArbitragePairConfig } from './scanner.js';
+import { PriorityLevel } from './dex-integrations/swap-options.js';
+import { parsePriorityFeeStrategy } from './priority-fee.js';
+import { DEFAULT_REGISTRY_TTL_MS } from './market-registry.js';
+import { ArbitrageOpportunity } from './profit-calculator.js';
+
+type Env = Record<string, string | undefined>;
+
+/**
+ * Apply a config file: TOML with one top-level `NAME = value` per env var
+ * Only fills variables not already set, so env (and .env) > config file > defaults
+ * @throws Error when the file is unreadable, not valid TOML, or has a table instead of a value
+ */
+export function applyConfigFile(path: string, env: Env = process.env): void {
+  const values = parseToml(fs.readFileSync(path, 'utf8'));
+  for (const [key, value] of Object.entries(values)) {
+    if (typeof value === 'object' && !Array.isArray(value) && !(value instanceof Date)) {
+      throw new Error(`${key}: expected NAME = value, not a table`);
+    }
+    if (env[key] === undefined) {
+      env[key] = Array.isArray(value) ? value.join(',') : String(value);
+    }
+  }
+}
+
+/**
+ * Parse per-route profit thresholds from env, e.g. RAYDIUM_ORCA_MIN_PROFIT_USD=15
+ * Keys use the same `buyDex-sellDex` format as MIN_SPREAD_THRESHOLDS
+ */
+function parseRouteMinProfit(env: Env): Record<string, number> {
+  const thresholds: Record<string, number> = {};
+  for (const buyDex of DEX_LIST) {
+    for (const sellDex of DEX_LIST) {
+      const value = env[`${buyDex.toUpperCase()}_${sellDex.toUpperCase()}_MIN_PROFIT_USD`];
+      if (value !== undefined) {
+        thresholds[`${buyDex}-${sellDex}`] = parseFloat(value);
+      }
+    }
+  }
+  return thresholds;
+}
+
+/**
+ * Parse per-pair config from ARB_PAIRS, e.g. "SOL/USDC:10:500000,WIF/USDC:5:50000:false"
+ * Format: PAIR[:minProfitUsd[:maxFlashUsd[:enabled]]], empty fields use global defaults
+ */
+function parseArbPairs(env: Env): ArbitragePairConfig[] {
+  const raw = env.ARB_PAIRS;
+  if (!raw) {
+    return DEFAULT_PAIR_CONFIGS;
+  }
+
+  return raw.split(',').map(entry => entry.trim()).filter(Boolean).map(entry => {
+    const [pair, minProfit, maxFlash, enabled] = entry.split(':');
+    return {
+      pair,
+      minProfitUsd: minProfit ? parseFloat(minProfit) : undefined,
+      maxFlashAmount: maxFlash ? parseFloat(maxFlash) : undefined,
+      enabled: enabled !== 'false',
+    };
+  });
+}
+
+// Sanity cap for any profit threshold, catches typos like an extra zero
+const MAX_MIN_PROFIT_USD = 10_000;
+
+/**
+ * Read settings from env vars, with defaults for anything unset
+ * Flags like --dry-run-verbose and --start-paused come from argv
+ */
+export function loadConfig(env: Env = process.env, argv: string[] = process.argv) {
+  return {
+    // RPC Settings
+    RPC_URL: env.RPC_URL || 'https://api.mainnet-beta.solana.com',
+    // Base58 secret key of the trading wallet
+    WALLET_PRIVATE_KEY: env.WALLET_PRIVATE_KEY || '',
+    // Tried in order when RPC_URL returns 429/5xx or times out
+    FALLBACK_RPC_URLS: (env.FALLBACK_RPC_URLS || '').split(',').map(u => u.trim()).filter(Boolean),
+    MAX_RPC_REQUESTS_PER_SEC: parseInt(env.MAX_RPC_REQUESTS_PER_SEC || '20'),
+
+    // HTTP APIs (Raydium, Jupiter)
+    HTTP_TIMEOUT_MS: parseInt(env.HTTP_TIMEOUT_MS || '5000'),
+    // Retries for HTTP API requests, and rebroadcasts of the flash loan transaction
+    MAX_RETRIES: parseInt(env.MAX_RETRIES || '3'),
+    // Streamed prices and queued opportunities older than this are not acted on
+    MAX_QUOTE_AGE_SECONDS: parseFloat(env.MAX_QUOTE_AGE_SECONDS || '10'),
+
+    // Scanning (5 seconds to avoid Jupiter rate limits)
+    SCAN_INTERVAL_MS: parseInt(env.SCAN_INTERVAL_MS || '5000'),
+    // Only re-quote pairs whose Jupiter price moved this much (0 = quote every scan)
+    JUPITER_DIVERGENCE_THRESHOLD: parseFloat(env.JUPITER_DIVERGENCE_THRESHOLD || '0.002'),
+    JUPITER_API_URL: env.JUPITER_API_URL || '',
+    JUPITER_API_KEY: env.JUPITER_API_KEY || '',
+    // Symbol → mint/decimals for pair tokens beyond the built-ins, resolved from Jupiter's token list (unset = no cache)
+    TOKEN_LIST_FILE: env.TOKEN_LIST_FILE || '',
+    // Stream Orca pool prices over the RPC WebSocket
+    PRICE_FEED: env.PRICE_FEED === 'true',
+    // Rescan a pair immediately when its streamed price moves this many bps (0 = off, needs PRICE_FEED)
+    PRICE_TRIGGER_BPS: parseFloat(env.PRICE_TRIGGER_BPS || '0'),
+
+    // Profit thresholds
+    MIN_PROFIT_USD: parseFloat(env.MIN_PROFIT_USD || '10'),
+    ROUTE_MIN_PROFIT_USD: parseRouteMinProfit(env),
+    ARB_PAIRS: parseArbPairs(env),
+    // Skip pairs with Token-2022 mints (transfer fees, extensions)
+    SKIP_TOKEN_2022: env.SKIP_TOKEN_2022 === 'true',
+    MAX_SLIPPAGE_TOLERANCE: parseFloat(env.MAX_SLIPPAGE_TOLERANCE || '0.003'),
+    MAX_PRICE_IMPACT_PCT: parseFloat(env.MAX_PRICE_IMPACT_PCT || '2'),
+
+    // Sizing
+    MAX_FLASH_LOAN_USD: parseFloat(env.MAX_FLASH_LOAN_USD || '1000000'),
+    // Expected lamports per trade (base + priority fee + tip); smaller opportunities can't cover it
+    GAS_ESTIMATE_LAMPORTS: parseInt(env.GAS_ESTIMATE_LAMPORTS || '50000'),
+    // Confirm pool-price candidates with real swap quotes before reporting them
+    CONFIRM_OPPORTUNITIES: env.CONFIRM_OPPORTUNITIES === 'true',
+    // Also look for USDC → A → B → USDC routes between every two scanned pairs
+    TRIANGULAR_ROUTES: env.TRIANGULAR_ROUTES === 'true',
+
+    // Kamino markets to borrow from: comma-separated addresses, or "auto" to discover on-chain
+    // (unset = main market); the market with the deepest reserve is used per loan
+    KAMINO_MARKETS: env.KAMINO_MARKETS || '',
+    KAMINO_REGISTRY_TTL_MS: parseInt(env.KAMINO_REGISTRY_TTL_MS || String(DEFAULT_REGISTRY_TTL_MS)),
+    // Kamino referrer (base58) for flash loans; its referrer token state must exist for each borrowed reserve
+    KAMINO_REFERRER: env.KAMINO_REFERRER || '',
+    // Log Kamino deposit TVL at startup and every TVL_CHECK_SCANS scans (0 = startup only), and alert
+    // when it falls TVL_DROP_ALERT_PERCENT below the startup value (a drained market or bad oracle)
+    TVL_CHECK_SCANS: parseInt(env.TVL_CHECK_SCANS || '100'),
+    TVL_DROP_ALERT_PERCENT: parseFloat(env.TVL_DROP_ALERT_PERCENT || '50'),
+
+    // Execution
+    DRY_RUN: env.DRY_RUN !== 'false',
+    DRY_RUN_VERBOSE: env.DRY_RUN_VERBOSE === 'true' || argv.includes('--dry-run-verbose'),
+    AUTO_EXECUTE: env.AUTO_EXECUTE === 'true',
+    // Start with execution paused (scanning still runs); resume via the API or SIGUSR1
+    START_PAUSED: env.START_PAUSED === 'true' || argv.includes('--start-paused'),
+    // Create missing token accounts for USDC and the pair tokens at startup (dry run only reports them)
+    ATA_PREFLIGHT: env.ATA_PREFLIGHT !== 'false',
+    // Exit (for a supervisor to restart) when the scan loop makes no progress this long (0 = disabled)
+    WATCHDOG_STALL_SECONDS: parseInt(env.WATCHDOG_STALL_SECONDS || '0'),
+    // Executor workers pulling from the opportunity queue (highest score first)
+    MAX_CONCURRENT_EXECUTIONS: parseInt(env.MAX_CONCURRENT_EXECUTIONS || '1'),
+    // Per scan cycle: most opportunities queued for execution and total flash loan notional (0 = unlimited)
+    MAX_EXECUTIONS_PER_CYCLE: parseInt(env.MAX_EXECUTIONS_PER_CYCLE || '0'),
+    MAX_FLASH_USD_PER_CYCLE: parseFloat(env.MAX_FLASH_USD_PER_CYCLE || '0'),
+    // Queue order: profit^SCORE_PROFIT_WEIGHT × execution probability^SCORE_PROBABILITY_WEIGHT (defaults = by profit)
+    SCORE_PROFIT_WEIGHT: parseFloat(env.SCORE_PROFIT_WEIGHT || '1'),
+    SCORE_PROBABILITY_WEIGHT: parseFloat(env.SCORE_PROBABILITY_WEIGHT || '0'),
+    // Commitment for RPC reads, blockhashes and counting a trade as landed
+    COMMITMENT: (env.COMMITMENT || 'confirmed') as Commitment,
+    SKIP_PREFLIGHT: env.SKIP_PREFLIGHT === 'true',
+    CONFIRM_TIMEOUT_MS: parseInt(env.CONFIRM_TIMEOUT_MS || '30000'),
+    // Resends of a flash loan that didn't land, each adding FEE_ESCALATION_STEP_MICRO_LAMPORTS to the priority fee
+    FEE_ESCALATION_RETRIES: parseInt(env.FEE_ESCALATION_RETRIES || '2'),
+    FEE_ESCALATION_STEP_MICRO_LAMPORTS: parseInt(env.FEE_ESCALATION_STEP_MICRO_LAMPORTS || '1000'),
+    // SOL kept for fees: trades and sweeps whose worst-case fees would dip below this are skipped
+    MIN_SOL_RESERVE_LAMPORTS: parseInt(env.MIN_SOL_RESERVE_LAMPORTS || '10000000'),
+    // Wallet-owned durable nonce accounts (create with `npm run nonce -- create <count>`)
+    NONCE_ACCOUNTS: (env.NONCE_ACCOUNTS || '').split(',').map(a => a.trim()).filter(Boolean),
+    PRIORITY_FEE_MICRO_LAMPORTS: parseInt(env.PRIORITY_FEE_MICRO_LAMPORTS || '0'),
+    // static (PRIORITY_FEE_MICRO_LAMPORTS), percentile:<n> (recent fees on the swap pools), auto (Raydium) or estimate (by profit)
+    PRIORITY_FEE_STRATEGY: env.PRIORITY_FEE_STRATEGY || 'static',
+    EXACT_OUT_REPAY: env.EXACT_OUT_REPAY === 'true',
+    // Raydium auto-fee tier (m, h, vh) and cap for swap transactions
+    SWAP_PRIORITY_LEVEL: (env.SWAP_PRIORITY_LEVEL || 'h') as PriorityLevel,
+    SWAP_MAX_PRIORITY_MICRO_LAMPORTS: parseInt(env.SWAP_MAX_PRIORITY_MICRO_LAMPORTS || '1000000'),
+    SWAP_WRAP_UNWRAP_SOL: env.SWAP_WRAP_UNWRAP_SOL !== 'false',
+    // Swap leftover token balances into PROFIT_TARGET_MINT (0 = only via the `sweep` command)
+    PROFIT_SWEEP_INTERVAL_MS: parseInt(env.PROFIT_SWEEP_INTERVAL_MS || '0'),
+    PROFIT_TARGET_MINT: env.PROFIT_TARGET_MINT || 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v',
+    SWEEP_DUST_USD: parseFloat(env.SWEEP_DUST_USD || '1'),
+    SWEEP_CLOSE_ACCOUNTS: env.SWEEP_CLOSE_ACCOUNTS === 'true',
+    // Move SOL/USDC above the hot wallet targets to this address (unset = never withdraw)
+    PROFIT_WITHDRAW_ADDRESS: env.PROFIT_WITHDRAW_ADDRESS || '',
+    HOT_WALLET_TARGET_SOL: parseFloat(env.HOT_WALLET_TARGET_SOL || '1'),
+    HOT_WALLET_MARGIN_SOL: parseFloat(env.HOT_WALLET_MARGIN_SOL || '0.5'),
+    // Unset = USDC is never withdrawn
+    HOT_WALLET_TARGET_USDC: env.HOT_WALLET_TARGET_USDC ? parseFloat(env.HOT_WALLET_TARGET_USDC) : null,
+    HOT_WALLET_MARGIN_USDC: parseFloat(env.HOT_WALLET_MARGIN_USDC || '100'),
+    // Wallet-owned address lookup table for hot accounts, created on first live run (unset = none)
+    LOOKUP_TABLE_FILE: env.LOOKUP_TABLE_FILE || '',
+
+    // API Server
+    API_PORT: parseInt(env.API_PORT || '3001'),
+    API_BIND_ADDR: env.API_BIND_ADDR || '0.0.0.0',
+    // Required as `Authorization: Bearer <token>` (or ?token= for WebSocket) when set; stats reveal the wallet
+    API_TOKEN: env.API_TOKEN || '',
+    ENABLE_API: env.ENABLE_API !== 'false',
+
+    // Persist counters between restarts (unset = in-memory only)
+    STATS_FILE: env.STATS_FILE || '',
+    // JSON-lines history of every execution attempt for offline analysis (unset = off)
+    ARBITRAGE_LOG_PATH: env.ARBITRAGE_LOG_PATH || '',
+    // Slack incoming webhook notified of every landed trade (unset = off)
+    SLACK_WEBHOOK_URL: env.SLACK_WEBHOOK_URL || '',
+    // OTLP/HTTP traces endpoint for scan and execution spans, e.g. http://localhost:4318/v1/traces (unset = off)
+    OTLP_ENDPOINT: env.OTLP_ENDPOINT || '',
+  };
+}
+
+export type BotConfig = ReturnType<typeof loadConfig>;
+
+/**
+ * Validate configuration before connecting
+ */
+export function validateConfig(config: BotConfig): void {
+  if (!/^https?:\/\//.test(config.RPC_URL)) {
+    throw new Error(`Invalid RPC_URL: "${config.RPC_URL}" (expected an http(s) URL)`);
+  }
+  if (config.WALLET_PRIVATE_KEY) {
+    try {
+      Keypair.fromSecretKey(bs58.decode(config.WALLET_PRIVATE_KEY));
+    } catch {
+      throw new Error('Invalid WALLET_PRIVATE_KEY (expected a base58-encoded 64-byte secret key)');
+    }
+  }
+
+  const thresholds: Record<string, number> = { default: config.MIN_PROFIT_USD, ...config.ROUTE_MIN_PROFIT_USD };
+  for (const pairConfig of config.ARB_PAIRS) {
+    if (pairConfig.minProfitUsd !== undefined) {
+      thresholds[pairConfig.pair] = pairConfig.minProfitUsd;
+    }
+  }
+  for (const [route, value] of Object.entries(thresholds)) {
+    if (isNaN(value) || value < 0 || value > MAX_MIN_PROFIT_USD) {
+      throw new Error(`Invalid min profit for ${route}: ${value} (must be 0-${MAX_MIN_PROFIT_USD})`);
+    }
+  }
+
+  if (!(config.MAX_SLIPPAGE_TOLERANCE > 0 && config.MAX_SLIPPAGE_TOLERANCE < 0.1)) {
+    throw new Error(`Invalid MAX_SLIPPAGE_TOLERANCE: ${config.MAX_SLIPPAGE_TOLERANCE} (must be between 0 and 0.1)`);
+  }
+  if (config.KAMINO_MARKETS && config.KAMINO_MARKETS !== 'auto') {
+    for (const address of config.KAMINO_MARKETS.split(',')) {
+      try {
+        new PublicKey(address.trim());
+      } catch {
+        throw new Error(`Invalid address in KAMINO_MARKETS: "${address}"`);
+      }
+    }
+  }
+  if (config.KAMINO_REFERRER) {
+    try {
+      new PublicKey(config.KAMINO_REFERRER);
+    } catch {
+      throw new Error(`Invalid KAMINO_REFERRER: "${config.KAMINO_REFERRER}"`);
+    }
+  }
+  if (!(config.MAX_RETRIES >= 0 && config.MAX_RETRIES <= 10)) {
+    throw new Error(`Invalid MAX_RETRIES: ${config.MAX_RETRIES} (must be 0-10)`);
+  }
+  if (!(config.MAX_QUOTE_AGE_SECONDS >= 1 && config.MAX_QUOTE_AGE_SECONDS <= 300)) {
+    throw new Error(`Invalid MAX_QUOTE_AGE_SECONDS: ${config.MAX_QUOTE_AGE_SECONDS} (must be 1-300)`);
+  }
+  if (!['processed', 'confirmed', 'finalized'].includes(config.COMMITMENT)) {
+    throw new Error(`Invalid COMMITMENT: ${config.COMMITMENT} (expected processed, confirmed or finalized)`);
+  }
+  if (!(config.CONFIRM_TIMEOUT_MS >= 1000)) {
+    throw new Error(`Invalid CONFIRM_TIMEOUT_MS: ${config.CONFIRM_TIMEOUT_MS} (must be at least 1000)`);
+  }
+  if (!(config.FEE_ESCALATION_RETRIES >= 0 && config.FEE_ESCALATION_RETRIES <= 10) || !(config.FEE_ESCALATION_STEP_MICRO_LAMPORTS >= 0)) {
+    throw new Error(`Invalid FEE_ESCALATION_RETRIES/FEE_ESCALATION_STEP_MICRO_LAMPORTS: ${config.FEE_ESCALATION_RETRIES}/${config.FEE_ESCALATION_STEP_MICRO_LAMPORTS} (retries must be 0-10)`);
+  }
+  if (!(config.TVL_CHECK_SCANS >= 0) || !(config.TVL_DROP_ALERT_PERCENT > 0 && config.TVL_DROP_ALERT_PERCENT <= 100)) {
+    throw new Error(`Invalid TVL_CHECK_SCANS/TVL_DROP_ALERT_PERCENT: ${config.TVL_CHECK_SCANS}/${config.TVL_DROP_ALERT_PERCENT} (percent must be 0-100)`);
+  }
+  if (!(config.MIN_SOL_RESERVE_LAMPORTS >= 0)) {
+    throw new Error(`Invalid MIN_SOL_RESERVE_LAMPORTS: ${config.MIN_SOL_RESERVE_LAMPORTS}`);
+  }
+  for (const address of config.NONCE_ACCOUNTS) {
+    try {
+      new PublicKey(address);
+    } catch {
+      throw new Error(`Invalid address in NONCE_ACCOUNTS: "${address}"`);
+    }
+  }
+  // Scans can legitimately wait on a full execution queue, so leave room for slow trades
+  if (config.WATCHDOG_STALL_SECONDS !== 0 && !(config.WATCHDOG_STALL_SECONDS >= 60)) {
+    throw new Error(`Invalid WATCHDOG_STALL_SECONDS: ${config.WATCHDOG_STALL_SECONDS} (0 to disable, or at least 60)`);
+  }
+  if (!(config.GAS_ESTIMATE_LAMPORTS >= 0)) {
+    throw new Error(`Invalid GAS_ESTIMATE_LAMPORTS: ${config.GAS_ESTIMATE_LAMPORTS}`);
+  }
+  if (!(config.PRICE_TRIGGER_BPS >= 0)) {
+    throw new Error(`Invalid PRICE_TRIGGER_BPS: ${config.PRICE_TRIGGER_BPS}`);
+  }
+  if (config.PRICE_TRIGGER_BPS > 0 && !config.PRICE_FEED) {
+    throw new Error('PRICE_TRIGGER_BPS requires PRICE_FEED=true');
+  }
+  if (!(config.MAX_EXECUTIONS_PER_CYCLE >= 0)) {
+    throw new Error(`Invalid MAX_EXECUTIONS_PER_CYCLE: ${config.MAX_EXECUTIONS_PER_CYCLE}`);
+  }
+  if (!(config.MAX_FLASH_USD_PER_CYCLE >= 0)) {
+    throw new Error(`Invalid MAX_FLASH_USD_PER_CYCLE: ${config.MAX_FLASH_USD_PER_CYCLE}`);
+  }
+  if (!(config.SCORE_PROFIT_WEIGHT >= 0 && config.SCORE_PROBABILITY_WEIGHT >= 0)) {
+    throw new Error(`Invalid SCORE_PROFIT_WEIGHT/SCORE_PROBABILITY_WEIGHT: ${config.SCORE_PROFIT_WEIGHT}/${config.SCORE_PROBABILITY_WEIGHT}`);
+  }
+  if (!(config.MAX_CONCURRENT_EXECUTIONS >= 1)) {
+    throw new Error(`Invalid MAX_CONCURRENT_EXECUTIONS: ${config.MAX_CONCURRENT_EXECUTIONS} (must be at least 1)`);
+  }
+  if (!['m', 'h', 'vh'].includes(config.SWAP_PRIORITY_LEVEL)) {
+    throw new Error(`Invalid SWAP_PRIORITY_LEVEL: ${config.SWAP_PRIORITY_LEVEL} (expected m, h or vh)`);
+  }
+  try {
+    new PublicKey(config.PROFIT_TARGET_MINT);
+  } catch {
+    throw new Error(`Invalid PROFIT_TARGET_MINT: ${config.PROFIT_TARGET_MINT}`);
+  }
+  if (config.PROFIT_WITHDRAW_ADDRESS) {
+    try {
+      new PublicKey(config.PROFIT_WITHDRAW_ADDRESS);
+    } catch {
+      throw new Error(`Invalid PROFIT_WITHDRAW_ADDRESS: ${config.PROFIT_WITHDRAW_ADDRESS}`);
+    }
+    // Always leave enough SOL for fees, tips and rent
+    if (!(config.HOT_WALLET_TARGET_SOL >= 0.05) || !(config.HOT_WALLET_MARGIN_SOL >= 0)) {
+      throw new Error(`Invalid HOT_WALLET_TARGET_SOL/HOT_WALLET_MARGIN_SOL: ${config.HOT_WALLET_TARGET_SOL}/${config.HOT_WALLET_MARGIN_SOL} (target must be at least 0.05 SOL)`);
+    }
+    if (config.HOT_WALLET_TARGET_USDC !== null && !(config.HOT_WALLET_TARGET_USDC >= 0 && config.HOT_WALLET_MARGIN_USDC >= 0)) {
+      throw new Error(`Invalid HOT_WALLET_TARGET_USDC/HOT_WALLET_MARGIN_USDC: ${config.HOT_WALLET_TARGET_USDC}/${config.HOT_WALLET_MARGIN_USDC}`);
+    }
+  }
+  parsePriorityFeeStrategy(config.PRIORITY_FEE_STRATEGY, config.PRIORITY_FEE_MICRO_LAMPORTS, config.SWAP_PRIORITY_LEVEL);
+
+  for (const { pair, maxFlashAmount } of config.ARB_PAIRS) {
+    const [base, quote] = pair.split('/');
+    if (!base || !quote) {
+      throw new Error(`Invalid pair in ARB_PAIRS: "${pair}" (expected BASE/QUOTE)`);
+    }
+    if (base === quote) {
+      throw new Error(`Invalid pair in ARB_PAIRS: "${pair}" uses the same token twice`);
+    }
+    if (maxFlashAmount !== undefined && !(maxFlashAmount > 0)) {
+      throw new Error(`Invalid max flash amount for ${pair}: ${maxFlashAmount}`);
+    }
+  }
+}
+
+/**
+ * Minimum profit for an opportunity: pair override, then route override, then MIN_PROFIT_USD
+ */
+export function minProfitForOpportunity(config: BotConfig, opp: ArbitrageOpportunity): number {
+  const pairConfig = config.ARB_PAIRS.find(p => p.pair === opp.pair);
+  return pairConfig?.minProfitUsd
+    ?? config.ROUTE_MIN_PROFIT_USD[`${opp.buyDex}-${opp.sellDex}`]
+    ?? config.MIN_PROFIT_USD;
+}
+
+/**
+ * Largest flash loan to size for: MAX_FLASH_LOAN_USD, bounded by the reserve's available USDC
+ * (dollars) when that is known
+ */
+export function flashLoanLimit(config: BotConfig, availableUsd: number): number {
+  return availableUsd > 0 ? Math.min(config.MAX_FLASH_LOAN_USD, availableUsd) : config.MAX_FLASH_LOAN_USD;
+}
+
+/**
+ * Build a config in code, without env vars or files
+ * Starts from the defaults; build() requires a wallet and runs validateConfig
+ *
+ * ```ts
+ * const config = configBuilder()
+ *   .rpcUrl('https://my-rpc.example')
+ *   .walletPrivateKey(secret)
+ *   .dryRun(true)
+ *   .pairs(['SOL/USDC', 'JUP/USDC'])
+ *   .set('MIN_PROFIT_USD', 5)
+ *   .build();
+ * ```
+ */
+export class BotConfigBuilder {
+  private config: BotConfig = loadConfig({}, []);
+
+  rpcUrl(url: string): this {
+    this.config.RPC_URL = url;
+    return this;
+  }
+
+  walletPrivateKey(base58: string): this {
+    this.config.WALLET_PRIVATE_KEY = base58;
+    return this;
+  }
+
+  dryRun(dryRun: boolean): this {
+    this.config.DRY_RUN = dryRun;
+    return this;
+  }
+
+  /**
+   * Pairs to scan, as "BASE/QUOTE" (enabled, global thresholds) or full pair configs
+   */
+  pairs(pairs: (string | ArbitragePairConfig)[]): this {
+    this.config.ARB_PAIRS = pairs.map(p => typeof p === 'string' ? { pair: p, enabled: true } : p);
+    return this;
+  }
+
+  /**
+   * Any other setting, by its env var name
+   */
+  set<K extends keyof BotConfig>(key: K, value: BotConfig[K]): this {
+    this.config[key] = value;
+    return this;
+  }
+
+  /**
+   * @throws Error when the wallet is missing or validateConfig rejects a setting
+   */
+  build(): BotConfig {
+    if (!this.config.WALLET_PRIVATE_KEY) {
+      throw new Error('WALLET_PRIVATE_KEY is required');
+    }
+    validateConfig(this.config);
+    return { ...this.config };
+  }
+}
+
+export function configBuilder(): BotConfigBuilder {
+  return new BotConfigBuilder();
+}
@@ -3,8 +3,11 @@
  * Exports all DEX clients for unified access
  */
 
-export { RaydiumClient, type RaydiumPriceQuote } from './raydium.js';
+export { RaydiumClient, type RaydiumPriceQuote, type RaydiumSwapQuote } from './raydium.js';
 export { OrcaClient, type OrcaPriceQuote } from './orca.js';
+export { JupiterClient, type JupiterClientOptions } from './jupiter.js';
+export { DEFAULT_SWAP_OPTIONS, type SwapOptions, type PriorityLevel } from './swap-options.js';
+export type { SwapApi, SwapLegQuote, SwapMode } from './swap-api.js';
 
 // Common price quote interface for all DEXes
 export interface UnifiedPriceQuote {
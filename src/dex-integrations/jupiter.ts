@@ -0,0 +1,93 @@
+/**
+ * Jupiter Price API Integration
+ * Cheap aggregated prices used to pre-filter pairs before per-DEX quoting
+ * Documentation: https://station.jup.ag/docs/apis/price-api-v2
+ */
+
+import { logger } from '../utils/logger.js';
+import { fetchJson } from '../utils/http.js';
+
+// Public endpoint; override for paid (api.jup.ag with key) or self-hosted setups
+export const DEFAULT_JUPITER_API_URL = 'https://api.jup.ag';
+
+// Prices are only reused within a single scan interval
+const PRICE_CACHE_TTL_MS = 2000;
+
+export interface JupiterClientOptions {
+  baseUrl?: string;
+  apiKey?: string;
+}
+
+/**
+ * Jupiter client for aggregated USD prices
+ */
+export class JupiterClient {
+  private baseUrl: string;
+  private headers: Record<string, string>;
+  private priceCache: Map<string, { price: number; fetchedAt: number }> = new Map();
+
+  constructor(options: JupiterClientOptions = {}) {
+    this.baseUrl = (options.baseUrl || DEFAULT_JUPITER_API_URL).replace(/\/+$/, '');
+    this.headers = options.apiKey ? { 'x-api-key': options.apiKey } : {};
+  }
+
+  /**
+   * Fetch a single price to verify the endpoint and key
+   * @returns Round-trip latency in ms
+   */
+  async checkEndpoint(): Promise<number> {
+    const start = Date.now();
+    await fetchJson(`${this.baseUrl}/price/v2?ids=So11111111111111111111111111111111111111112`, { headers: this.headers }, { maxRetries: 0 });
+    return Date.now() - start;
+  }
+
+  /**
+   * Jupiter's verified token list (symbol, mint address and decimals per token)
+   * @throws ApiError when the request fails after retries
+   */
+  async getTokenList(): Promise<{ address: string; symbol: string; decimals: number }[]> {
+    const data = await fetchJson(`${this.baseUrl}/tokens/v1/tagged/verified`, { headers: this.headers });
+    if (!Array.isArray(data)) {
+      throw new Error('Unexpected token list response');
+    }
+    return data;
+  }
+
+  /**
+   * Get USD prices for a set of mints
+   * Cached mints younger than the TTL are served without a request
+   * @param mints Mint addresses (base58)
+   * @returns Map of mint -> price; mints Jupiter doesn't price are omitted
+   * @throws ApiError when the request fails after retries
+   */
+  async getPrices(mints: string[]): Promise<Map<string, number>> {
+    const now = Date.now();
+    const prices = new Map<string, number>();
+    const missing: string[] = [];
+
+    for (const mint of mints) {
+      const cached = this.priceCache.get(mint);
+      if (cached && now - cached.fetchedAt < PRICE_CACHE_TTL_MS) {
+        prices.set(mint, cached.price);
+      } else {
+        missing.push(mint);
+      }
+    }
+
+    if (missing.length === 0) {
+      return prices;
+    }
+
+    const data = await fetchJson(`${this.baseUrl}/price/v2?ids=${missing.join(',')}`, { headers: this.headers });
+    for (const mint of missing) {
+      const price = parseFloat(data.data?.[mint]?.price);
+      if (price > 0) {
+        prices.set(mint, price);
+        this.priceCache.set(mint, { price, fetchedAt: now });
+      }
+    }
+
+    logger.debug(`[Jupiter] Fetched ${missing.length} prices`);
+    return prices;
+  }
+}
@@ -4,34 +4,34 @@
  * Documentation: https://dev.orca.so/
  */
 
-import { Connection, PublicKey } from '@solana/web3.js';
+import { AccountInfo, Connection, PublicKey, TransactionInstruction } from '@solana/web3.js';
 import { 
   WhirlpoolContext, 
   buildWhirlpoolClient, 
   ORCA_WHIRLPOOL_PROGRAM_ID,
   PDAUtil,
+  ParsableWhirlpool,
+  PriceMath,
   swapQuoteByInputToken,
+  swapQuoteByOutputToken,
   IGNORE_CACHE,
 } from '@orca-so/whirlpools-sdk';
 import { Percentage } from '@orca-so/common-sdk';
 import BN from 'bn.js';
+import { SwapOptions } from './swap-options.js';
+import { SwapApi, SwapLegQuote, SwapMode } from './swap-api.js';
+import { PriceSource } from './price-source.js';
+import { tokenRegistry } from '../token-registry.js';
 
 // Orca Whirlpools Config for Mainnet
-const WHIRLPOOLS_CONFIG = new PublicKey('2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ');
-
-// Token mint addresses and decimals
-const TOKEN_INFO: Record<string, { mint: PublicKey; decimals: number }> = {
-  'SOL': { mint: new PublicKey('So11111111111111111111111111111111111111112'), decimals: 9 },
-  'USDC': { mint: new PublicKey('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v'), decimals: 6 },
-  'JUP': { mint: new PublicKey('JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN'), decimals: 6 },
-  'JTO': { mint: new PublicKey('jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL'), decimals: 9 },
-  'BONK': { mint: new PublicKey('DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263'), decimals: 5 },
-  'WIF': { mint: new PublicKey('EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm'), decimals: 6 },
-};
+export const WHIRLPOOLS_CONFIG = new PublicKey('2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ');
 
 // Main tick spacings to check (most liquid pools use these)
 const TICK_SPACINGS = [64, 128, 8, 1];
 
+// getMultipleAccounts limit per request
+const MAX_MULTIPLE_ACCOUNTS = 100;
+
 export interface OrcaPriceQuote {
   pair: string;
   price: number;
@@ -40,10 +40,21 @@ export interface OrcaPriceQuote {
   tickCurrentIndex: number;
 }
 
+export interface OrcaSwapQuote {
+  estimatedAmountIn: BN;
+  estimatedAmountOut: BN;
+  // Slippage-adjusted bound: minimum output for ExactIn, maximum input for ExactOut
+  otherAmountThreshold: BN;
+  priceImpactPct: number;
+  quote: any;
+  whirlpool: any;
+}
+
 /**
  * Orca Whirlpools client for fetching pool prices
  */
-export class OrcaClient {
+export class OrcaClient implements SwapApi, PriceSource {
+  readonly supportsExactOut = true;
   private connection: Connection;
   private ctx: WhirlpoolContext | null = null;
   private client: any = null;
@@ -86,12 +97,20 @@ export class OrcaClient {
       return this.poolCache.get(pair)!;
     }
 
+    await this.resolveWhirlpools([pair]);
+    return this.poolCache.get(pair) ?? null;
+  }
+
+  /**
+   * Candidate whirlpool PDAs for a pair, one per tick spacing in order of typical liquidity
+   */
+  private whirlpoolCandidates(pair: string): PublicKey[] {
     const [base, quote] = pair.split('/');
-    const baseInfo = TOKEN_INFO[base];
-    const quoteInfo = TOKEN_INFO[quote];
+    const baseInfo = tokenRegistry.get(base);
+    const quoteInfo = tokenRegistry.get(quote);
     
     if (!baseInfo || !quoteInfo) {
-      return null;
+      return [];
     }
 
     // Token order matters - smaller pubkey is tokenA
@@ -99,28 +118,46 @@ export class OrcaClient {
       ? [baseInfo.mint, quoteInfo.mint]
       : [quoteInfo.mint, baseInfo.mint];
 
-    // Try tick spacings in order of typical liquidity
-    for (const tickSpacing of TICK_SPACINGS) {
+    return TICK_SPACINGS.map(tickSpacing => PDAUtil.getWhirlpool(
+      ORCA_WHIRLPOOL_PROGRAM_ID,
+      WHIRLPOOLS_CONFIG,
+      tokenMintA,
+      tokenMintB,
+      tickSpacing
+    ).publicKey);
+  }
+
+  /**
+   * Resolve and cache the whirlpools of several pairs with batched getMultipleAccounts calls
+   * instead of one getAccountInfo per pair and tick spacing
+   */
+  async resolveWhirlpools(pairs: string[]): Promise<void> {
+    const pending = pairs
+      .filter(pair => !this.poolCache.has(pair))
+      .map(pair => ({ pair, candidates: this.whirlpoolCandidates(pair) }))
+      .filter(p => p.candidates.length > 0);
+    const addresses = pending.flatMap(p => p.candidates);
+    if (addresses.length === 0) return;
+
+    const infos: (AccountInfo<Buffer> | null)[] = [];
+    for (let i = 0; i < addresses.length; i += MAX_MULTIPLE_ACCOUNTS) {
       try {
-        const pda = PDAUtil.getWhirlpool(
-          ORCA_WHIRLPOOL_PROGRAM_ID,
-          WHIRLPOOLS_CONFIG,
-          tokenMintA,
-          tokenMintB,
-          tickSpacing
-        );
-        
-        const accountInfo = await this.connection.getAccountInfo(pda.publicKey);
-        if (accountInfo) {
-          this.poolCache.set(pair, pda.publicKey);
-          return pda.publicKey;
-        }
+        infos.push(...await this.connection.getMultipleAccountsInfo(addresses.slice(i, i + MAX_MULTIPLE_ACCOUNTS)));
       } catch (e) {
-        // Continue
+        console.error('[Orca] Failed to fetch whirlpool accounts:', e);
+        return;
       }
     }
-    
-    return null;
+
+    let offset = 0;
+    for (const { pair, candidates } of pending) {
+      // First existing pool in tick spacing order
+      const index = candidates.findIndex((_, i) => infos[offset + i] !== null);
+      if (index >= 0) {
+        this.poolCache.set(pair, candidates[index]);
+      }
+      offset += candidates.length;
+    }
   }
 
   /**
@@ -133,8 +170,8 @@ export class OrcaClient {
     }
 
     const [base, quote] = pair.split('/');
-    const baseInfo = TOKEN_INFO[base];
-    const quoteInfo = TOKEN_INFO[quote];
+    const baseInfo = tokenRegistry.get(base);
+    const quoteInfo = tokenRegistry.get(quote);
 
     if (!baseInfo || !quoteInfo) {
       console.error(`[Orca] Unknown tokens in pair: ${pair}`);
@@ -204,64 +241,159 @@ export class OrcaClient {
   }
 
   /**
-   * Build swap transaction for Orca Whirlpool
+   * Whirlpool address for a pair (cached), used by the price feed to subscribe
+   */
+  async getWhirlpoolAddress(pair: string): Promise<PublicKey | null> {
+    return this.findWhirlpool(pair);
+  }
+
+  /**
+   * Spot price (quote per base) and liquidity from raw whirlpool account data
+   * Used for account-change notifications, so no RPC round trip
+   */
+  priceFromAccount(
+    pair: string,
+    address: PublicKey,
+    accountInfo: AccountInfo<Buffer>
+  ): { price: number; liquidity: number } | null {
+    const [base, quote] = pair.split('/');
+    const baseInfo = tokenRegistry.get(base);
+    const quoteInfo = tokenRegistry.get(quote);
+    const data = ParsableWhirlpool.parse(address, accountInfo);
+    if (!baseInfo || !quoteInfo || !data) {
+      return null;
+    }
+
+    // Pool price is tokenB per tokenA; invert when the base token is tokenB
+    const baseIsA = data.tokenMintA.equals(baseInfo.mint);
+    const price = baseIsA
+      ? PriceMath.sqrtPriceX64ToPrice(data.sqrtPrice, baseInfo.decimals, quoteInfo.decimals).toNumber()
+      : 1 / PriceMath.sqrtPriceX64ToPrice(data.sqrtPrice, quoteInfo.decimals, baseInfo.decimals).toNumber();
+
+    if (!isFinite(price) || price <= 0) {
+      return null;
+    }
+    return { price, liquidity: data.liquidity.toNumber() };
+  }
+
+  /**
+   * Find the whirlpool for a mint pair regardless of direction
+   */
+  private async findWhirlpoolForMints(inputMint: PublicKey, outputMint: PublicKey): Promise<PublicKey | null> {
+    const inputSymbol = tokenRegistry.forMint(inputMint)?.symbol;
+    const outputSymbol = tokenRegistry.forMint(outputMint)?.symbol;
+
+    if (!inputSymbol || !outputSymbol) {
+      console.error('[Orca] Unknown token mints');
+      return null;
+    }
+
+    const poolAddress = await this.findWhirlpool(`${inputSymbol}/${outputSymbol}`)
+      || await this.findWhirlpool(`${outputSymbol}/${inputSymbol}`);
+
+    if (!poolAddress) {
+      console.error(`[Orca] No whirlpool found for ${inputSymbol}/${outputSymbol}`);
+    }
+    return poolAddress;
+  }
+
+  /**
+   * Get a swap quote with price impact measured against the pool spot price
    * @param inputMint Input token mint
    * @param outputMint Output token mint
-   * @param amountIn Amount in base units
-   * @param walletPubkey Wallet public key
+   * @param amount Amount in base units: input for ExactIn, output for ExactOut
    * @param slippagePct Slippage percentage (default 1%)
-   * @returns Transaction instructions or null
    */
-  async buildSwapTransaction(
+  async getSwapQuote(
     inputMint: PublicKey,
     outputMint: PublicKey,
-    amountIn: BN,
-    walletPubkey: PublicKey,
-    slippagePct: number = 1
-  ): Promise<any | null> {
+    amount: BN,
+    slippagePct: number = 1,
+    mode: SwapMode = 'ExactIn'
+  ): Promise<OrcaSwapQuote | null> {
     if (!this.client || !this.ctx) {
       await this.initialize();
     }
 
-    try {
-      // Find the whirlpool for this pair
-      const inputSymbol = Object.keys(TOKEN_INFO).find(k => TOKEN_INFO[k].mint.equals(inputMint));
-      const outputSymbol = Object.keys(TOKEN_INFO).find(k => TOKEN_INFO[k].mint.equals(outputMint));
-      
-      if (!inputSymbol || !outputSymbol) {
-        console.error('[Orca] Unknown token mints');
-        return null;
-      }
-
-      const pair = `${inputSymbol}/${outputSymbol}`;
-      const reversePair = `${outputSymbol}/${inputSymbol}`;
-      
-      let poolAddress = await this.findWhirlpool(pair);
-      if (!poolAddress) {
-        poolAddress = await this.findWhirlpool(reversePair);
-      }
-      
-      if (!poolAddress) {
-        console.error(`[Orca] No whirlpool found for ${inputSymbol}/${outputSymbol}`);
-        return null;
-      }
+    const poolAddress = await this.findWhirlpoolForMints(inputMint, outputMint);
+    if (!poolAddress) {
+      return null;
+    }
 
-      const whirlpool = await this.client.getPool(poolAddress);
-      const slippage = Percentage.fromFraction(slippagePct, 100);
+    const whirlpool = await this.client.getPool(poolAddress);
+    const slippage = Percentage.fromFraction(slippagePct, 100);
 
-      // Get swap quote
-      const swapQuote = await swapQuoteByInputToken(
+    const quote = mode === 'ExactIn'
+      ? await swapQuoteByInputToken(
         whirlpool,
         inputMint,
-        amountIn,
+        amount,
+        slippage,
+        ORCA_WHIRLPOOL_PROGRAM_ID,
+        this.ctx!.fetcher,
+        IGNORE_CACHE
+      )
+      : await swapQuoteByOutputToken(
+        whirlpool,
+        outputMint,
+        amount,
         slippage,
         ORCA_WHIRLPOOL_PROGRAM_ID,
         this.ctx!.fetcher,
         IGNORE_CACHE
       );
 
+    // Spot price is tokenB per tokenA in UI units
+    const data = whirlpool.getData();
+    const tokenA = whirlpool.getTokenAInfo();
+    const tokenB = whirlpool.getTokenBInfo();
+    const spotPrice = PriceMath.sqrtPriceX64ToPrice(data.sqrtPrice, tokenA.decimals, tokenB.decimals).toNumber();
+
+    const aToB = tokenA.mint.equals(inputMint);
+    const [inDecimals, outDecimals] = aToB
+      ? [tokenA.decimals, tokenB.decimals]
+      : [tokenB.decimals, tokenA.decimals];
+
+    const amountInUi = quote.estimatedAmountIn.toNumber() / Math.pow(10, inDecimals);
+    const amountOutUi = quote.estimatedAmountOut.toNumber() / Math.pow(10, outDecimals);
+    const spotOut = aToB ? amountInUi * spotPrice : amountInUi / spotPrice;
+    const priceImpactPct = spotOut > 0 ? Math.max(0, (spotOut - amountOutUi) / spotOut * 100) : 0;
+
+    return {
+      estimatedAmountIn: quote.estimatedAmountIn,
+      estimatedAmountOut: quote.estimatedAmountOut,
+      otherAmountThreshold: quote.otherAmountThreshold,
+      priceImpactPct,
+      quote,
+      whirlpool,
+    };
+  }
+
+  /**
+   * Build swap transaction for Orca Whirlpool
+   * @param inputMint Input token mint
+   * @param outputMint Output token mint
+   * @param amount Amount in base units: input for ExactIn, output for ExactOut
+   * @param walletPubkey Wallet public key
+   * @param slippagePct Slippage percentage (default 1%)
+   * @returns Transaction instructions or null
+   */
+  async buildSwapTransaction(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: BN,
+    walletPubkey: PublicKey,
+    slippagePct: number = 1,
+    mode: SwapMode = 'ExactIn'
+  ): Promise<any | null> {
+    try {
+      const swapQuote = await this.getSwapQuote(inputMint, outputMint, amount, slippagePct, mode);
+      if (!swapQuote) {
+        return null;
+      }
+
       // Build swap transaction
-      const swapTx = await whirlpool.swap(swapQuote, walletPubkey);
+      const swapTx = await swapQuote.whirlpool.swap(swapQuote.quote, walletPubkey);
       
       return swapTx;
 
@@ -271,11 +403,50 @@ export class OrcaClient {
     }
   }
 
+  /**
+   * SwapApi: ExactIn quote for a leg
+   */
+  async quote(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    slippageBps: number
+  ): Promise<SwapLegQuote | null> {
+    const quote = await this.getSwapQuote(inputMint, outputMint, new BN(amount), slippageBps / 100);
+    return quote && {
+      inAmount: amount,
+      outAmount: quote.estimatedAmountOut.toNumber(),
+      minOutAmount: quote.otherAmountThreshold.toNumber(),
+      priceImpactPct: quote.priceImpactPct,
+    };
+  }
+
+  /**
+   * SwapApi: swap instructions followed by cleanup (close WSOL accounts etc)
+   * ExactOut spends at most the slippage-adjusted input for exactly `amount` out
+   */
+  async buildSwapInstructions(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    walletPubkey: PublicKey,
+    options: SwapOptions,
+    mode: SwapMode = 'ExactIn'
+  ): Promise<TransactionInstruction[] | null> {
+    const txBuilder = await this.buildSwapTransaction(inputMint, outputMint, new BN(amount), walletPubkey, options.slippageBps / 100, mode);
+    if (!txBuilder) {
+      return null;
+    }
+    // TransactionBuilder.compressIx(false) returns Instruction with instructions array
+    const ix = txBuilder.compressIx(false);
+    return [...ix.instructions, ...(ix.cleanupInstructions ?? [])];
+  }
+
   /**
    * Get token info by symbol
    */
   getTokenInfo(symbol: string): { mint: PublicKey; decimals: number } | null {
-    return TOKEN_INFO[symbol] || null;
+    return tokenRegistry.get(symbol) ?? null;
   }
 
   /**
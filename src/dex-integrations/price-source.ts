@@ -0,0 +1,22 @@
+/**
+ * Price Source Interface
+ * What the scanner needs from a DEX: a pool price and depth for a pair
+ * Implemented by RaydiumClient and OrcaClient; swap in a stub to run the scanner offline
+ */
+
+/**
+ * Pool price (quote token per base token) and liquidity (USD) for a pair
+ */
+export interface PoolPrice {
+  price: number;
+  liquidity: number;
+}
+
+export interface PriceSource {
+  initialize(): Promise<void>;
+
+  /**
+   * Price of the pair's deepest pool on this DEX (null if the pair has no pool)
+   */
+  getPrice(pair: string): Promise<PoolPrice | null>;
+}
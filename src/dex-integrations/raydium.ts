@@ -4,22 +4,27 @@
  * Documentation: https://docs.raydium.io/raydium/traders/trade-api
  */
 
-import { Connection, PublicKey, VersionedTransaction, Transaction } from '@solana/web3.js';
+import {
+  AddressLookupTableAccount,
+  Connection,
+  PublicKey,
+  VersionedTransaction,
+  Transaction,
+  TransactionInstruction,
+  ComputeBudgetProgram,
+} from '@solana/web3.js';
 import { logger } from '../utils/logger.js';
+import { fetchJson, ApiError } from '../utils/http.js';
+import { SwapOptions, DEFAULT_SWAP_OPTIONS, PriorityLevel } from './swap-options.js';
+import { SwapApi, SwapLegQuote, SwapMode } from './swap-api.js';
+import { PriceSource } from './price-source.js';
+import { tokenRegistry } from '../token-registry.js';
+
+export type { SwapMode };
 
 const RAYDIUM_API_URL = 'https://transaction-v1.raydium.io';
 const RAYDIUM_PRIORITY_FEE_URL = 'https://api-v3.raydium.io/main/auto-fee';
 
-// Token mint addresses and decimals
-const TOKEN_INFO: Record<string, { mint: string; decimals: number }> = {
-  'SOL': { mint: 'So11111111111111111111111111111111111111112', decimals: 9 },
-  'USDC': { mint: 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v', decimals: 6 },
-  'JUP': { mint: 'JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN', decimals: 6 },
-  'JTO': { mint: 'jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL', decimals: 9 },
-  'BONK': { mint: 'DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263', decimals: 5 },
-  'WIF': { mint: 'EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm', decimals: 6 },
-};
-
 export interface RaydiumPriceQuote {
   pair: string;
   price: number;
@@ -28,13 +33,44 @@ export interface RaydiumPriceQuote {
   poolType: string;
 }
 
+export interface RaydiumSwapQuote {
+  mode: SwapMode;
+  inputAmount: number;
+  outputAmount: number;
+  // Slippage-adjusted bounds: otherAmountThreshold is min output for ExactIn, max input for ExactOut
+  minOutputAmount: number;
+  maxInputAmount: number;
+  priceImpactPct: number;
+  raw: any;
+}
+
+const SWAP_MODE_PATH: Record<SwapMode, string> = {
+  ExactIn: 'swap-base-in',
+  ExactOut: 'swap-base-out',
+};
+
+/**
+ * Raydium's recommended compute unit price (micro-lamports) for a fee tier
+ * @returns null if the auto-fee endpoint is unavailable
+ */
+export async function fetchRaydiumPriorityFee(level: PriorityLevel): Promise<number | null> {
+  try {
+    const feeData = await fetchJson(RAYDIUM_PRIORITY_FEE_URL, {}, { maxRetries: 0 });
+    return feeData.data?.default?.[level] || null;
+  } catch (e) {
+    return null;
+  }
+}
+
 /**
  * Raydium DEX client using Trade API
  * https://docs.raydium.io/raydium/traders/trade-api
  */
-export class RaydiumClient {
+export class RaydiumClient implements SwapApi, PriceSource {
+  readonly supportsExactOut = true;
   private connection: Connection;
   private initialized = false;
+  private lookupTables: Map<string, AddressLookupTableAccount> = new Map();
 
   constructor(connection: Connection) {
     this.connection = connection;
@@ -52,8 +88,8 @@ export class RaydiumClient {
    */
   async getPrice(pair: string): Promise<RaydiumPriceQuote | null> {
     const [base, quote] = pair.split('/');
-    const baseInfo = TOKEN_INFO[base];
-    const quoteInfo = TOKEN_INFO[quote];
+    const baseInfo = this.getTokenInfo(base);
+    const quoteInfo = this.getTokenInfo(quote);
 
     if (!baseInfo || !quoteInfo) {
       console.error(`[Raydium] Unknown tokens in pair: ${pair}`);
@@ -68,12 +104,7 @@ export class RaydiumClient {
       
       const url = `${RAYDIUM_API_URL}/compute/swap-base-in?inputMint=${baseInfo.mint}&outputMint=${quoteInfo.mint}&amount=${inputAmount}&slippageBps=50&txVersion=V0`;
       
-      const response = await fetch(url);
-      if (!response.ok) {
-        throw new Error(`HTTP ${response.status}`);
-      }
-      
-      const data = await response.json();
+      const data = await fetchJson(url);
       
       if (!data.success || !data.data) {
         console.error(`[Raydium] ${pair}: API returned no data`);
@@ -122,52 +153,84 @@ export class RaydiumClient {
     return results;
   }
 
+  /**
+   * Get a swap quote using Raydium Trade API
+   * @param inputMint Input token mint address
+   * @param outputMint Output token mint address
+   * @param amount Amount in base units: input for ExactIn, output for ExactOut
+   * @param slippageBps Slippage in basis points (default 50 = 0.5%)
+   * @param mode Swap mode (default ExactIn)
+   * @returns Quote with amounts, slippage bounds and price impact (percent), or null
+   * @throws ApiError when rate limited after retries or no route exists
+   */
+  async getSwapQuote(
+    inputMint: string,
+    outputMint: string,
+    amount: number,
+    slippageBps: number = 50,
+    mode: SwapMode = 'ExactIn'
+  ): Promise<RaydiumSwapQuote | null> {
+    const quoteUrl = `${RAYDIUM_API_URL}/compute/${SWAP_MODE_PATH[mode]}?inputMint=${inputMint}&outputMint=${outputMint}&amount=${amount}&slippageBps=${slippageBps}&txVersion=V0`;
+
+    const quoteData = await fetchJson(quoteUrl);
+    if (!quoteData.success || !quoteData.data) {
+      if (/route/i.test(String(quoteData.msg))) {
+        throw new ApiError('no_route', `No route: ${quoteData.msg}`);
+      }
+      console.error('[Raydium] Quote API returned no data');
+      return null;
+    }
+
+    const inputAmount = Number(quoteData.data.inputAmount);
+    const outputAmount = Number(quoteData.data.outputAmount);
+    const threshold = Number(quoteData.data.otherAmountThreshold);
+
+    return {
+      mode,
+      inputAmount,
+      outputAmount,
+      minOutputAmount: mode === 'ExactIn' ? threshold : outputAmount,
+      maxInputAmount: mode === 'ExactIn' ? inputAmount : threshold,
+      // API reports impact as a percentage, e.g. 0.15 for 0.15%
+      priceImpactPct: parseFloat(String(quoteData.data.priceImpactPct ?? 0)) || 0,
+      raw: quoteData,
+    };
+  }
+
   /**
    * Build swap transaction using Raydium Trade API
    * @param inputMint Input token mint address
    * @param outputMint Output token mint address  
-   * @param amountIn Amount in base units (lamports)
+   * @param amount Amount in base units: input for ExactIn, output for ExactOut
    * @param walletPubkey Wallet public key
-   * @param slippageBps Slippage in basis points (default 50 = 0.5%)
+   * @param options Slippage, priority fee tier/cap and SOL wrapping
+   * @param mode Swap mode (default ExactIn)
    * @returns Serialized transaction buffer or null
    */
   async buildSwapTransaction(
     inputMint: string,
     outputMint: string,
-    amountIn: number,
+    amount: number,
     walletPubkey: PublicKey,
-    slippageBps: number = 50
+    options: SwapOptions = DEFAULT_SWAP_OPTIONS,
+    mode: SwapMode = 'ExactIn'
   ): Promise<Buffer | null> {
     try {
       // Step 1: Get swap quote
-      const quoteUrl = `${RAYDIUM_API_URL}/compute/swap-base-in?inputMint=${inputMint}&outputMint=${outputMint}&amount=${amountIn}&slippageBps=${slippageBps}&txVersion=V0`;
-      
-      const quoteResponse = await fetch(quoteUrl);
-      if (!quoteResponse.ok) {
-        console.error(`[Raydium] Quote API error: ${quoteResponse.status}`);
-        return null;
-      }
-      
-      const quoteData = await quoteResponse.json();
-      if (!quoteData.success || !quoteData.data) {
-        console.error('[Raydium] Quote API returned no data');
+      const quote = await this.getSwapQuote(inputMint, outputMint, amount, options.slippageBps, mode);
+      if (!quote) {
         return null;
       }
+      const quoteData = quote.raw;
 
-      // Step 2: Get priority fee
-      let priorityFee = 100000; // Default 0.0001 SOL
-      try {
-        const feeResponse = await fetch(RAYDIUM_PRIORITY_FEE_URL);
-        if (feeResponse.ok) {
-          const feeData = await feeResponse.json();
-          priorityFee = feeData.data?.default?.h || 100000;
-        }
-      } catch (e) {
-        // Use default fee
-      }
+      // Step 2: Get priority fee for the configured tier, capped
+      const priorityFee = Math.min(
+        (await fetchRaydiumPriorityFee(options.priorityLevel)) ?? 100000, // Default 0.0001 SOL
+        options.maxPriorityMicroLamports
+      );
 
       // Step 3: Build transaction
-      const txResponse = await fetch(`${RAYDIUM_API_URL}/transaction/swap-base-in`, {
+      const txData = await fetchJson(`${RAYDIUM_API_URL}/transaction/${SWAP_MODE_PATH[mode]}`, {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({
@@ -175,17 +238,11 @@ export class RaydiumClient {
           swapResponse: quoteData,
           txVersion: 'V0',
           wallet: walletPubkey.toBase58(),
-          wrapSol: inputMint === TOKEN_INFO['SOL'].mint,
-          unwrapSol: outputMint === TOKEN_INFO['SOL'].mint,
+          wrapSol: options.wrapUnwrapSol && inputMint === tokenRegistry.mint('SOL').toBase58(),
+          unwrapSol: options.wrapUnwrapSol && outputMint === tokenRegistry.mint('SOL').toBase58(),
         }),
       });
 
-      if (!txResponse.ok) {
-        console.error(`[Raydium] Transaction API error: ${txResponse.status}`);
-        return null;
-      }
-
-      const txData = await txResponse.json();
       if (!txData.success || !txData.data || txData.data.length === 0) {
         console.error('[Raydium] Transaction API returned no data');
         return null;
@@ -200,10 +257,147 @@ export class RaydiumClient {
     }
   }
 
+  /**
+   * SwapApi: ExactIn quote for a leg
+   */
+  async quote(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    slippageBps: number
+  ): Promise<SwapLegQuote | null> {
+    const quote = await this.getSwapQuote(inputMint.toBase58(), outputMint.toBase58(), amount, slippageBps);
+    return quote && {
+      inAmount: quote.inputAmount,
+      outAmount: quote.outputAmount,
+      minOutAmount: quote.minOutputAmount,
+      priceImpactPct: quote.priceImpactPct,
+    };
+  }
+
+  /**
+   * SwapApi: build the swap transaction and unpack its instructions
+   */
+  async buildSwapInstructions(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    walletPubkey: PublicKey,
+    options: SwapOptions,
+    mode: SwapMode = 'ExactIn'
+  ): Promise<TransactionInstruction[] | null> {
+    const txBuffer = await this.buildSwapTransaction(
+      inputMint.toBase58(),
+      outputMint.toBase58(),
+      amount,
+      walletPubkey,
+      options,
+      mode
+    );
+    if (!txBuffer) {
+      return null;
+    }
+    return this.extractInstructions(VersionedTransaction.deserialize(txBuffer));
+  }
+
+  /**
+   * SwapApi: lookup tables seen in Raydium swap transactions so far
+   */
+  getLookupTables(): AddressLookupTableAccount[] {
+    return Array.from(this.lookupTables.values());
+  }
+
+  /**
+   * Fetch a lookup table once; entries are append-only so cached copies stay valid
+   */
+  private async getLookupTable(address: PublicKey): Promise<AddressLookupTableAccount | null> {
+    const cached = this.lookupTables.get(address.toBase58());
+    if (cached) {
+      return cached;
+    }
+    const { value } = await this.connection.getAddressLookupTable(address);
+    if (value) {
+      this.lookupTables.set(address.toBase58(), value);
+    }
+    return value;
+  }
+
+  /**
+   * Extract instructions from a VersionedTransaction
+   * Handles both static accounts and address lookup tables
+   * Compute budget instructions are dropped (the caller sets its own)
+   */
+  private async extractInstructions(tx: VersionedTransaction): Promise<TransactionInstruction[]> {
+    const message = tx.message;
+    const instructions: TransactionInstruction[] = [];
+    
+    // Get all account keys (static + from lookup tables)
+    let allAccountKeys: PublicKey[] = [...message.staticAccountKeys];
+    
+    // If there are address lookup tables, resolve them
+    if (message.addressTableLookups && message.addressTableLookups.length > 0) {
+      for (const lookup of message.addressTableLookups) {
+        try {
+          const table = await this.getLookupTable(lookup.accountKey);
+          if (table) {
+            // Add writable accounts
+            for (const idx of lookup.writableIndexes) {
+              allAccountKeys.push(table.state.addresses[idx]);
+            }
+            // Add readonly accounts
+            for (const idx of lookup.readonlyIndexes) {
+              allAccountKeys.push(table.state.addresses[idx]);
+            }
+          }
+        } catch (e) {
+          logger.warn(`[Raydium] Failed to resolve lookup table: ${e}`);
+        }
+      }
+    }
+    
+    // Convert each compiled instruction to TransactionInstruction
+    for (const ix of message.compiledInstructions) {
+      const programId = allAccountKeys[ix.programIdIndex];
+      if (!programId) {
+        logger.warn(`[Raydium] Missing programId at index ${ix.programIdIndex}`);
+        continue;
+      }
+
+      // Duplicate compute budget instructions would fail the transaction
+      if (programId.equals(ComputeBudgetProgram.programId)) {
+        continue;
+      }
+      
+      const keys = ix.accountKeyIndexes.map(idx => {
+        const pubkey = allAccountKeys[idx];
+        if (!pubkey) {
+          logger.warn(`[Raydium] Missing account at index ${idx}`);
+          return null;
+        }
+        return {
+          pubkey,
+          isSigner: idx < message.header.numRequiredSignatures,
+          isWritable: idx < message.header.numRequiredSignatures - message.header.numReadonlySignedAccounts ||
+                     (idx >= message.header.numRequiredSignatures && 
+                      idx < allAccountKeys.length - message.header.numReadonlyUnsignedAccounts),
+        };
+      }).filter(k => k !== null) as { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[];
+      
+      instructions.push(new TransactionInstruction({
+        programId,
+        keys,
+        data: Buffer.from(ix.data),
+      }));
+    }
+    
+    return instructions;
+  }
+
   /**
    * Get token info by symbol
    */
   getTokenInfo(symbol: string): { mint: string; decimals: number } | null {
-    return TOKEN_INFO[symbol] || null;
+    const info = tokenRegistry.get(symbol);
+    return info ? { mint: info.mint.toBase58(), decimals: info.decimals } : null;
   }
 }
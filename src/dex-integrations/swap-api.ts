@@ -0,0 +1,55 @@
+/**
+ * Swap API Interface
+ * What the executor needs from a DEX: quote a leg and build its instructions
+ * Implemented by RaydiumClient and OrcaClient; swap in a stub to run the executor offline
+ */
+
+import { AddressLookupTableAccount, PublicKey, TransactionInstruction } from '@solana/web3.js';
+import { SwapOptions } from './swap-options.js';
+
+/**
+ * ExactIn fixes the input amount; ExactOut fixes the output amount
+ */
+export type SwapMode = 'ExactIn' | 'ExactOut';
+
+/**
+ * Quote for a single swap leg (amounts in base units)
+ */
+export interface SwapLegQuote {
+  inAmount: number;
+  outAmount: number;
+  // Output at the slippage limit
+  minOutAmount: number;
+  priceImpactPct: number;
+}
+
+export interface SwapApi {
+  readonly supportsExactOut: boolean;
+
+  initialize(): Promise<void>;
+
+  quote(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    slippageBps: number
+  ): Promise<SwapLegQuote | null>;
+
+  /**
+   * Swap instructions ready to embed in the flash loan transaction
+   * Compute budget instructions are stripped; the executor sets its own
+   */
+  buildSwapInstructions(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    walletPubkey: PublicKey,
+    options: SwapOptions,
+    mode?: SwapMode
+  ): Promise<TransactionInstruction[] | null>;
+
+  /**
+   * Lookup tables the built instructions may reference (DEXes that ship v0 transactions)
+   */
+  getLookupTables?(): AddressLookupTableAccount[];
+}
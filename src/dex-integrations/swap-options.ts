@@ -0,0 +1,22 @@
+/**
+ * Swap request settings shared by the DEX clients
+ */
+
+// Raydium auto-fee tiers: medium, high, very high
+export type PriorityLevel = 'm' | 'h' | 'vh';
+
+export interface SwapOptions {
+  slippageBps: number;
+  priorityLevel: PriorityLevel;
+  // Cap on the compute unit price taken from the fee API
+  maxPriorityMicroLamports: number;
+  // Wrap/unwrap native SOL around the swap
+  wrapUnwrapSol: boolean;
+}
+
+export const DEFAULT_SWAP_OPTIONS: SwapOptions = {
+  slippageBps: 100,
+  priorityLevel: 'h',
+  maxPriorityMicroLamports: 1_000_000,
+  wrapUnwrapSol: true,
+};
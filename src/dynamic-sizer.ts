@@ -24,6 +24,11 @@ export const MAX_AMOUNT = 100_000_000;   // $100M maximum (effectively no limit)
  */
 export const MAX_LIQUIDITY_RATIO = 0.10; // 10% of smallest pool
 
+/**
+ * Golden-section search step ratio (1/phi)
+ */
+const GOLDEN_RATIO = (Math.sqrt(5) - 1) / 2;
+
 /**
  * Slippage thresholds
  */
@@ -136,9 +141,68 @@ export function estimateNetProfit(
   return grossProfit - fees - slippageCost;
 }
 
+/**
+ * Golden-section search for the flash loan amount that maximizes profit
+ * Net profit (linear spread gain minus cubic slippage cost) is unimodal in amount,
+ * so the search converges on the optimum: amount* = liquidity * sqrt((spread - fees) / 6)
+ * @param maxAmount Upper bound, e.g. configured max flash loan or reserve liquidity
+ */
+export function findOptimalAmount(
+  minPoolLiquidity: number,
+  spreadPercent: number,
+  totalFeesPercent: number = 0.008,
+  maxAmount: number = MAX_AMOUNT
+): { amount: number; expectedProfit: number; slippagePercent: number } {
+  const upper = Math.min(maxAmount, minPoolLiquidity * MAX_LIQUIDITY_RATIO, MAX_AMOUNT);
+  if (minPoolLiquidity <= 0 || upper < MIN_AMOUNT || spreadPercent <= totalFeesPercent) {
+    return { amount: 0, expectedProfit: 0, slippagePercent: 0 };
+  }
+
+  const profitAt = (amount: number) =>
+    estimateNetProfit(amount, spreadPercent, minPoolLiquidity, totalFeesPercent);
+
+  let lo = MIN_AMOUNT;
+  let hi = upper;
+  let x1 = hi - GOLDEN_RATIO * (hi - lo);
+  let x2 = lo + GOLDEN_RATIO * (hi - lo);
+  let f1 = profitAt(x1);
+  let f2 = profitAt(x2);
+
+  // Stop once the bracket is within $1 or 0.1% of the upper bound
+  while (hi - lo > Math.max(1, hi * 0.001)) {
+    if (f1 < f2) {
+      lo = x1;
+      x1 = x2;
+      f1 = f2;
+      x2 = lo + GOLDEN_RATIO * (hi - lo);
+      f2 = profitAt(x2);
+    } else {
+      hi = x2;
+      x2 = x1;
+      f2 = f1;
+      x1 = hi - GOLDEN_RATIO * (hi - lo);
+      f1 = profitAt(x1);
+    }
+  }
+
+  const amount = Math.floor((lo + hi) / 2);
+  const expectedProfit = profitAt(amount);
+
+  if (expectedProfit <= 0) {
+    return { amount: 0, expectedProfit: 0, slippagePercent: 0 };
+  }
+
+  return {
+    amount,
+    expectedProfit,
+    slippagePercent: Math.pow(amount / minPoolLiquidity, 2) * 2,
+  };
+}
+
 /**
  * Find the optimal flash loan amount that maximizes profit
- * Tests multiple amounts and picks the one with highest net profit
+ * Tests a fixed grid of amounts and picks the one with highest net profit
+ * Kept for debugging/comparison; the scanner uses findOptimalAmount
  */
 export function findOptimalAmountIterative(
   pair: string,
@@ -0,0 +1,140 @@
+/**
+ * Execution Errors
+ * Typed failure categories for arbitrage attempts, so retries and stats can tell a closed spread
+ * from a slippage revert from an expired blockhash
+ */
+
+export type ExecutionErrorKind =
+  // Spread closed or prices too old by the time we executed
+  | 'stale'
+  // Expected profit doesn't cover tip, fees or gas
+  | 'unprofitable'
+  // A swap leg would move its pool more than allowed
+  | 'high_impact'
+  // A swap filled worse than its minimum output (or repayment fell short)
+  | 'slippage_exceeded'
+  // Not enough liquidity in the flash loan reserve
+  | 'insufficient_liquidity'
+  // Wallet token accounts were missing and couldn't be created
+  | 'missing_ata'
+  // Wallet can't cover fees, rent or a transfer
+  | 'insufficient_funds'
+  // Paying this trade's worst-case fees would take SOL below the configured reserve
+  | 'insufficient_fee_balance'
+  // Simulation (or the landed transaction) failed for a reason not covered above
+  | 'simulation_failed'
+  // Transaction didn't land before its blockhash (or nonce) was used up
+  | 'blockhash_expired'
+  // RPC/API request failed or confirmation timed out
+  | 'rpc'
+  // Swap instructions or the transaction couldn't be prepared
+  | 'build_failed'
+  | 'other';
+
+export class ExecutionError extends Error {
+  readonly kind: ExecutionErrorKind;
+  // Custom program error code, when the failure came from an instruction
+  readonly code?: number;
+
+  constructor(kind: ExecutionErrorKind, message: string, code?: number) {
+    super(message);
+    this.name = 'ExecutionError';
+    this.kind = kind;
+    this.code = code;
+  }
+
+  /**
+   * Worth sending again as-is (with a fresh blockhash)
+   */
+  get retryable(): boolean {
+    return this.kind === 'blockhash_expired' || this.kind === 'rpc';
+  }
+
+  toJSON(): { kind: ExecutionErrorKind; message: string; code?: number } {
+    return { kind: this.kind, message: this.message, code: this.code };
+  }
+}
+
+/**
+ * Anchor error names in program logs ("Error Code: <Name>"), per category
+ * Covers the Kamino lending, Raydium and Orca Whirlpool programs the flash loan calls
+ */
+const LOG_ERROR_NAMES: [ExecutionErrorKind, string[]][] = [
+  ['slippage_exceeded', [
+    'AmountOutBelowMinimum',     // Orca Whirlpool
+    'AmountInAboveMaximum',      // Orca Whirlpool
+    'TooLittleOutputReceived',   // Raydium CLMM
+    'TooMuchInputPaid',          // Raydium CLMM
+    'ExceededSlippage',          // Raydium AMM/CPMM
+  ]],
+  ['insufficient_liquidity', [
+    'InsufficientLiquidity',     // Kamino reserve
+  ]],
+  ['stale', [
+    'ReserveStale',              // Kamino reserve needs a refresh
+    'PriceTooOld',               // Kamino oracle
+  ]],
+];
+
+// Raw log fragments from the runtime and SPL token program
+const LOG_FRAGMENTS: [ExecutionErrorKind, string][] = [
+  ['insufficient_funds', 'insufficient lamports'],
+  ['insufficient_funds', 'Error: insufficient funds'],
+  ['blockhash_expired', 'Blockhash not found'],
+];
+
+// SPL token program custom error codes
+const TOKEN_ERROR_INSUFFICIENT_FUNDS = 1;
+
+/**
+ * Map a Solana transaction error (TransactionError object or its JSON string) plus program logs
+ * to an execution error category
+ */
+export function classifyTransactionError(err: unknown, logs: string[] = [], message?: string): ExecutionError {
+  const text = typeof err === 'string' ? err : JSON.stringify(err ?? null);
+  const description = message ?? text;
+
+  for (const [kind, names] of LOG_ERROR_NAMES) {
+    for (const name of names) {
+      if (logs.some(line => line.includes(`Error Code: ${name}`)) || text.includes(name)) {
+        return new ExecutionError(kind, `${description} (${name})`, customCode(err));
+      }
+    }
+  }
+  for (const [kind, fragment] of LOG_FRAGMENTS) {
+    if (logs.some(line => line.includes(fragment))) {
+      return new ExecutionError(kind, description, customCode(err));
+    }
+  }
+
+  if (text.includes('BlockhashNotFound')) {
+    return new ExecutionError('blockhash_expired', description);
+  }
+  if (text.includes('InsufficientFundsForFee') || text.includes('InsufficientFundsForRent')) {
+    return new ExecutionError('insufficient_funds', description);
+  }
+
+  const code = customCode(err);
+  if (code === TOKEN_ERROR_INSUFFICIENT_FUNDS) {
+    // A swap leg received less than the next leg (or the repay) spends
+    return new ExecutionError('slippage_exceeded', `${description} (token insufficient funds)`, code);
+  }
+  return new ExecutionError('simulation_failed', description, code);
+}
+
+/**
+ * Custom program error code from {"InstructionError":[index,{"Custom":code}]}
+ */
+function customCode(err: unknown): number | undefined {
+  let parsed = err;
+  if (typeof err === 'string') {
+    try {
+      parsed = JSON.parse(err);
+    } catch {
+      return undefined;
+    }
+  }
+  const instructionError = (parsed as { InstructionError?: [number, unknown] } | null)?.InstructionError;
+  const detail = instructionError?.[1] as { Custom?: number } | undefined;
+  return typeof detail?.Custom === 'number' ? detail.Custom : undefined;
+}
@@ -5,6 +5,7 @@
  */
 
 import { 
+  AddressLookupTableAccount,
   Connection, 
   Keypair, 
   PublicKey, 
@@ -12,47 +13,105 @@ import {
   VersionedTransaction,
   TransactionMessage,
   SystemProgram,
+  ComputeBudgetProgram,
   LAMPORTS_PER_SOL,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from '@solana/web3.js';
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token';
-
-// Helper to derive ATA (compatible with all spl-token versions)
-function getATA(mint: PublicKey, owner: PublicKey): PublicKey {
-  const [ata] = PublicKey.findProgramAddressSync(
-    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
-    ASSOCIATED_TOKEN_PROGRAM_ID
-  );
-  return ata;
-}
+import {
+  createAssociatedTokenAccountIdempotentInstruction,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ACCOUNT_SIZE,
+} from '@solana/spl-token';
 import { logger } from './utils/logger.js';
-import { ArbitrageOpportunity, calculateJitoTip, calculateNetProfitAfterTip } from './profit-calculator.js';
-import { KaminoFlashLoanClient } from './kamino-flash-loan.js';
+import { withSpan } from './utils/tracing.js';
+import { ArbitrageOpportunity, calculateJitoTip, calculateNetProfitAfterTip, calculateProfit, calculateTriangularProfit, describeRoute } from './profit-calculator.js';
+import { KaminoFlashLoanClient, KaminoClientOptions, FlashLoanParams, FlashLoanResult, decodeAvailableLiquidity } from './kamino-flash-loan.js';
 import { RaydiumClient } from './dex-integrations/raydium.js';
 import { OrcaClient } from './dex-integrations/orca.js';
-import BN from 'bn.js';
+import { SwapOptions, DEFAULT_SWAP_OPTIONS } from './dex-integrations/swap-options.js';
+import { SwapApi, SwapLegQuote } from './dex-integrations/swap-api.js';
+import { isWsol, buildWrapSolInstructions, buildUnwrapSolInstructions } from './utils/wsol.js';
+import { getMintInfo, getAtaForProgram } from './utils/token-program.js';
+import { LookupTableManager } from './lookup-table.js';
+import { ProfitTracker, TokenDelta } from './profit-tracker.js';
+import { PriorityFeeResolver, PriorityFeeStrategy } from './priority-fee.js';
+import { NonceManager, NonceLease } from './nonce-manager.js';
+import { ExecutionError, classifyTransactionError } from './execution-error.js';
+import { ApiError } from './utils/http.js';
+import { tokenRegistry, formatTokenAmount } from './token-registry.js';
+
+// Base fee per signature (lamports)
+const BASE_SIGNATURE_FEE_LAMPORTS = 5000;
+
+// After a reserve came up short, loans it can't cover are skipped without re-reading it for this long
+const RESERVE_COOLDOWN_MS = 60_000;
+
+// Rent-exempt minimum of a token account, held while the in-transaction wSOL account exists
+const TOKEN_ACCOUNT_RENT_LAMPORTS = 2_039_280;
+
+// Smallest share of the planned loan worth executing when the reserve is short
+const MIN_SHRUNK_LOAN_FRACTION = 0.5;
 
 // Jito tip account (mainnet)
 const JITO_TIP_ACCOUNT = new PublicKey('96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5');
 
-// Token mint addresses
-const TOKEN_MINTS: Record<string, PublicKey> = {
-  'SOL': new PublicKey('So11111111111111111111111111111111111111112'),
-  'USDC': new PublicKey('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v'),
-  'JUP': new PublicKey('JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN'),
-  'JTO': new PublicKey('jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL'),
-  'BONK': new PublicKey('DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263'),
-  'WIF': new PublicKey('EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm'),
-};
-
-// Token decimals
-const TOKEN_DECIMALS: Record<string, number> = {
-  'SOL': 9,
-  'USDC': 6,
-  'JUP': 6,
-  'JTO': 9,
-  'BONK': 5,
-  'WIF': 6,
-};
+// ComputeBudget SetComputeUnitPrice instruction discriminator
+const SET_COMPUTE_UNIT_PRICE_TAG = 3;
+
+/**
+ * Replace any compute unit price instruction with one at the given price
+ */
+function withComputeUnitPrice(instructions: TransactionInstruction[], microLamports: number): TransactionInstruction[] {
+  const rest = instructions.filter(ix =>
+    !(ix.programId.equals(ComputeBudgetProgram.programId) && ix.data[0] === SET_COMPUTE_UNIT_PRICE_TAG)
+  );
+  return [ComputeBudgetProgram.setComputeUnitPrice({ microLamports }), ...rest];
+}
+
+/**
+ * Wrap an unexpected exception: API failures count as RPC errors
+ */
+function toExecutionError(e: unknown): ExecutionError {
+  if (e instanceof ExecutionError) return e;
+  return new ExecutionError(e instanceof ApiError ? 'rpc' : 'other', String(e));
+}
+
+/**
+ * The opportunity at a smaller flash amount, its profit recomputed at the same prices and slippage
+ * (including the middle leg's fee on a triangular route)
+ */
+export function shrinkOpportunity(opportunity: ArbitrageOpportunity, flashAmount: number): ArbitrageOpportunity {
+  const slippage = opportunity.calculation.slippageCost / opportunity.flashAmount;
+  const calculation = opportunity.crossLeg
+    ? calculateTriangularProfit(
+      flashAmount,
+      opportunity.buyPrice,
+      opportunity.sellPrice,
+      opportunity.buyDex,
+      opportunity.crossLeg.dex,
+      opportunity.sellDex,
+      slippage
+    )
+    : calculateProfit(flashAmount, opportunity.buyPrice, opportunity.sellPrice, opportunity.buyDex, opportunity.sellDex, slippage);
+  return { ...opportunity, flashAmount, calculation };
+}
+
+/**
+ * Pipeline timestamps (ms since epoch) of a live execution, for detection → landing latency
+ */
+export interface ExecutionTimings {
+  // Prices behind the opportunity were scanned
+  detectedAt: number;
+  startedAt: number;
+  simulatedAt?: number;
+  // Last (re)send accepted by the RPC
+  submittedAt?: number;
+  // Landed status observed, and the slot it landed in
+  confirmedAt?: number;
+  confirmedSlot?: number;
+}
 
 /**
  * Execution result
@@ -61,10 +120,62 @@ export interface ExecutionResult {
   success: boolean;
   txSignature?: string;
   actualProfit?: number;
-  error?: string;
+  error?: ExecutionError;
+  skipReason?: 'high_impact' | 'stale';
+  // Realized per-asset balance changes behind actualProfit
+  profitBreakdown?: TokenDelta[];
+  // Network fee (base + priority) charged for the landed transaction
+  feeLamports?: number;
+  timings?: ExecutionTimings;
   executionTimeMs: number;
 }
 
+/**
+ * Outcome of simulating an opportunity's full flash loan transaction
+ */
+export interface SimulationReport {
+  unitsConsumed: number;
+  error?: ExecutionError;
+  // USDC balance change (excludes gas, tip and base token leftovers); set when the simulation succeeded
+  usdcDelta?: number;
+}
+
+/**
+ * Executor options
+ */
+export interface ExecutorOptions {
+  dryRun?: boolean;
+  // In dry run, build and simulate the transaction and print program logs
+  dryRunVerbose?: boolean;
+  maxPriceImpactPct?: number;
+  // Static fee, and the fallback when a dynamic strategy can't fetch fees
+  priorityFeeMicroLamports?: number;
+  // How the compute unit price is chosen (default: static priorityFeeMicroLamports)
+  priorityFeeStrategy?: PriorityFeeStrategy;
+  // Helius RPC URL enabling getPriorityFeeEstimate for the estimate strategy
+  heliusRpcUrl?: string;
+  // Sell only enough base token to repay the flash loan (ExactOut); leftover stays as base token
+  exactOutRepay?: boolean;
+  swapOptions?: SwapOptions;
+  // Per-DEX swap implementations, keyed by DEX name (defaults to the Raydium/Orca clients)
+  swapApis?: Record<string, SwapApi>;
+  kamino?: KaminoClientOptions;
+  // State file for the wallet's address lookup table (unset = no table)
+  lookupTableFile?: string;
+  // Opportunities scanned longer ago than this are skipped as stale (default: no limit)
+  maxOpportunityAgeMs?: number;
+  // Rebroadcasts of the flash loan transaction while waiting for it to land
+  maxRetries?: number;
+  // Wallet-owned durable nonce accounts; transactions use one instead of a blockhash when free
+  nonceAccounts?: PublicKey[];
+  // Resends of a flash loan that didn't land, each at a higher priority fee (default: 0)
+  feeEscalationRetries?: number;
+  // Compute unit price added per resend (default: 1000 micro-lamports)
+  feeEscalationStepMicroLamports?: number;
+  // SOL (lamports) a trade's worst-case fees may never dip into (default: 0)
+  minSolReserveLamports?: number;
+}
+
 /**
  * Executor statistics
  */
@@ -73,6 +184,8 @@ export interface ExecutorStats {
   tradesSuccessful: number;
   tradesFailed: number;
   totalProfitUsd: number;
+  skippedHighImpact: number;
+  skippedStale: number;
 }
 
 /**
@@ -83,39 +196,125 @@ export class Executor {
   private connection: Connection;
   private keypair: Keypair;
   private dryRun: boolean;
+  private dryRunVerbose: boolean;
+  private maxPriceImpactPct: number;
+  private priorityFee: PriorityFeeResolver;
+  private exactOutRepay: boolean;
+  private swapOptions: SwapOptions;
+  // Flash token → liquidity last seen when its reserve couldn't cover a loan
+  private reserveCooldowns: Map<string, { available: bigint; until: number }> = new Map();
   private kaminoClient: KaminoFlashLoanClient;
-  private raydiumClient: RaydiumClient;
-  private orcaClient: OrcaClient;
+  private swapApis: Record<string, SwapApi>;
+  private lookupTable: LookupTableManager | null;
+  private nonces: NonceManager | null;
+  private feeEscalationRetries: number;
+  private feeEscalationStepMicroLamports: number;
+  private profitTracker: ProfitTracker;
+  private maxOpportunityAgeMs: number;
+  private minSolReserveLamports: number;
+  // Mints touched by live trades currently in progress (count per mint)
+  private inFlightMints: Map<string, number> = new Map();
   private stats: ExecutorStats = {
     tradesExecuted: 0,
     tradesSuccessful: 0,
     tradesFailed: 0,
     totalProfitUsd: 0,
+    skippedHighImpact: 0,
+    skippedStale: 0,
   };
 
-  constructor(connection: Connection, keypair: Keypair, dryRun: boolean = true) {
+  constructor(connection: Connection, keypair: Keypair, options: ExecutorOptions = {}) {
     this.connection = connection;
     this.keypair = keypair;
-    this.dryRun = dryRun;
-    this.kaminoClient = new KaminoFlashLoanClient(connection);
-    this.raydiumClient = new RaydiumClient(connection);
-    this.orcaClient = new OrcaClient(connection);
+    this.dryRun = options.dryRun ?? true;
+    this.dryRunVerbose = options.dryRunVerbose ?? false;
+    this.maxPriceImpactPct = options.maxPriceImpactPct ?? 2;
+    this.exactOutRepay = options.exactOutRepay ?? false;
+    this.swapOptions = options.swapOptions ?? DEFAULT_SWAP_OPTIONS;
+    const staticFee = options.priorityFeeMicroLamports ?? 0;
+    this.priorityFee = new PriorityFeeResolver(
+      connection,
+      options.priorityFeeStrategy ?? { type: 'static', microLamports: staticFee },
+      staticFee,
+      this.swapOptions.maxPriorityMicroLamports,
+      options.heliusRpcUrl ?? null
+    );
+    this.kaminoClient = new KaminoFlashLoanClient(connection, { ...options.kamino, maxRetries: options.maxRetries });
+    this.swapApis = options.swapApis ?? {
+      raydium: new RaydiumClient(connection),
+      orca: new OrcaClient(connection),
+    };
+    this.profitTracker = new ProfitTracker(connection, keypair.publicKey);
+    this.maxOpportunityAgeMs = options.maxOpportunityAgeMs ?? Infinity;
+    this.minSolReserveLamports = options.minSolReserveLamports ?? 0;
+    this.lookupTable = options.lookupTableFile
+      ? new LookupTableManager(connection, keypair, options.lookupTableFile)
+      : null;
+    this.feeEscalationRetries = options.feeEscalationRetries ?? 0;
+    this.feeEscalationStepMicroLamports = options.feeEscalationStepMicroLamports ?? 1000;
+    this.nonces = options.nonceAccounts?.length
+      ? new NonceManager(connection, keypair, options.nonceAccounts)
+      : null;
     
-    if (dryRun) {
+    if (this.dryRun) {
       logger.warn('Executor running in DRY RUN mode - no transactions will be sent');
     }
   }
 
   /**
    * Initialize the executor
+   * @param loadOnly Only load an existing lookup table, never create or extend it (default: in dry run)
    */
-  async initialize(): Promise<void> {
+  async initialize(loadOnly = this.dryRun): Promise<void> {
     await this.kaminoClient.initialize();
-    await this.raydiumClient.initialize();
-    await this.orcaClient.initialize();
+    for (const api of Object.values(this.swapApis)) {
+      await api.initialize();
+    }
+    await this.refreshLookupTable(loadOnly);
     logger.info('Executor initialized');
   }
 
+  /**
+   * Create or extend the wallet's lookup table with the accounts our transactions reference
+   * Dry runs only load an existing table so nothing is spent
+   */
+  async refreshLookupTable(loadOnly = this.dryRun): Promise<void> {
+    if (!this.lookupTable) return;
+
+    try {
+      if (loadOnly) {
+        await this.lookupTable.load();
+        return;
+      }
+
+      const ataMints = tokenRegistry.all().map(t => t.mint).filter(mint => !isWsol(mint));
+      const walletAtas = await Promise.all(ataMints.map(mint => this.getAta(mint)));
+      await this.lookupTable.ensure([
+        TOKEN_PROGRAM_ID,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        SystemProgram.programId,
+        ComputeBudgetProgram.programId,
+        SYSVAR_INSTRUCTIONS_PUBKEY,
+        JITO_TIP_ACCOUNT,
+        ...tokenRegistry.all().map(t => t.mint),
+        ...walletAtas,
+        ...await this.kaminoClient.getHotAccounts(['USDC']),
+      ]);
+    } catch (e) {
+      logger.warn(`[Executor] Lookup table refresh failed: ${e}`);
+    }
+  }
+
+  /**
+   * Our table plus any the swap APIs' instructions may reference
+   */
+  private lookupTables(): AddressLookupTableAccount[] {
+    const tables = Object.values(this.swapApis).flatMap(api => api.getLookupTables?.() ?? []);
+    const own = this.lookupTable?.getTable();
+    return own ? [own, ...tables] : tables;
+  }
+
   // Cache SOL price for Jito tip calculation
   private solPriceUsd: number = 100; // Default, updated from scanner
 
@@ -143,7 +342,57 @@ export class Executor {
       logger.warn(`[Skip] ${opportunity.pair}: Not profitable after Jito tip ($${jitoTipUsd.toFixed(2)})`);
       return {
         success: false,
-        error: 'Not profitable after Jito tip',
+        error: new ExecutionError('unprofitable', 'Not profitable after Jito tip'),
+        executionTimeMs: Date.now() - startTime,
+      };
+    }
+
+    // Prices behind the opportunity may be too old to act on (e.g. after waiting in the queue)
+    const ageMs = startTime - opportunity.timestamp;
+    if (ageMs > this.maxOpportunityAgeMs) {
+      this.stats.skippedStale++;
+      logger.warn(`[Skip] ${opportunity.pair}: Stale - scanned ${(ageMs / 1000).toFixed(1)}s ago`);
+      return {
+        success: false,
+        error: new ExecutionError('stale', `Opportunity data is ${(ageMs / 1000).toFixed(1)}s old`),
+        skipReason: 'stale',
+        executionTimeMs: Date.now() - startTime,
+      };
+    }
+
+    // Re-quote both legs: the spread may have closed since the scan
+    const { priceImpactPct, expectedUsdcOut, worstCaseUsdcOut } = await this.quoteRoute(opportunity);
+    const usdcAmountIn = BigInt(Math.floor(opportunity.flashAmount * 1_000_000));
+    const repayAmount = usdcAmountIn + this.kaminoClient.calculateFee(usdcAmountIn);
+    if (expectedUsdcOut !== null && expectedUsdcOut <= Number(repayAmount)) {
+      this.stats.skippedStale++;
+      logger.warn(`[Skip] ${opportunity.pair}: Stale - current quotes return $${(expectedUsdcOut / 1_000_000).toFixed(2)} for $${opportunity.flashAmount.toFixed(2)}`);
+      return {
+        success: false,
+        error: new ExecutionError('stale', 'Opportunity no longer profitable at current quotes'),
+        skipReason: 'stale',
+        executionTimeMs: Date.now() - startTime,
+      };
+    }
+
+    // Skip if either leg would move the pool too much
+    if (priceImpactPct > this.maxPriceImpactPct) {
+      this.stats.skippedHighImpact++;
+      logger.warn(`[Skip] ${opportunity.pair}: Price impact too high: ${priceImpactPct.toFixed(2)}%`);
+      return {
+        success: false,
+        error: new ExecutionError('high_impact', `Price impact too high: ${priceImpactPct.toFixed(2)}%`),
+        skipReason: 'high_impact',
+        executionTimeMs: Date.now() - startTime,
+      };
+    }
+
+    // Skip if both legs filling at the slippage limit can't repay the flash loan
+    if (worstCaseUsdcOut !== null && worstCaseUsdcOut < Number(repayAmount)) {
+      logger.warn(`[Skip] ${opportunity.pair}: Worst-case output $${(worstCaseUsdcOut / 1_000_000).toFixed(2)} below repayment at ${this.swapOptions.slippageBps} bps slippage`);
+      return {
+        success: false,
+        error: new ExecutionError('slippage_exceeded', 'Worst-case output below flash loan repayment'),
         executionTimeMs: Date.now() - startTime,
       };
     }
@@ -157,18 +406,30 @@ export class Executor {
 
     // In dry run mode, just log and return
     if (this.dryRun) {
+      const simulation = this.dryRunVerbose ? await this.simulate(opportunity) : null;
       const executionTimeMs = Date.now() - startTime;
       logger.info('   [DRY RUN] Trade not executed');
+      if (simulation?.error) {
+        return { success: false, error: simulation.error, executionTimeMs };
+      }
       return {
         success: true,
         executionTimeMs,
-        actualProfit: opportunity.calculation.netProfit,
+        actualProfit: simulation?.usdcDelta ?? opportunity.calculation.netProfit,
       };
     }
 
+    // Keep the profit sweeper off these mints until the trade settles
+    const symbols = [...opportunity.pair.split('/'), ...(opportunity.crossLeg ? [opportunity.crossLeg.token] : [])];
+    const mints = symbols.map(symbol => tokenRegistry.get(symbol)?.mint.toBase58()).filter(Boolean) as string[];
+    for (const mint of mints) {
+      this.inFlightMints.set(mint, (this.inFlightMints.get(mint) ?? 0) + 1);
+    }
+
     try {
       // Execute the flash loan arbitrage
-      const result = await this.executeFlashLoanArbitrage(opportunity);
+      const timings: ExecutionTimings = { detectedAt: opportunity.timestamp, startedAt: startTime };
+      const result = { ...await this.executeFlashLoanArbitrage(opportunity, timings), timings };
       
       const executionTimeMs = Date.now() - startTime;
 
@@ -180,9 +441,12 @@ export class Executor {
         logger.info(`   Signature: ${result.txSignature}`);
         logger.info(`   Actual profit: $${result.actualProfit?.toFixed(2) || 'N/A'}`);
         logger.info(`   Execution time: ${executionTimeMs}ms`);
+        if (timings.submittedAt !== undefined && timings.confirmedAt !== undefined) {
+          logger.info(`   Latency: detect→submit ${timings.submittedAt - timings.detectedAt}ms | submit→confirm ${timings.confirmedAt - timings.submittedAt}ms (slot ${timings.confirmedSlot})`);
+        }
       } else {
         this.stats.tradesFailed++;
-        logger.error(`Trade failed: ${result.error}`);
+        logger.error(`Trade failed [${result.error?.kind ?? 'other'}]: ${result.error?.message}`);
       }
 
       return { ...result, executionTimeMs };
@@ -193,10 +457,87 @@ export class Executor {
       logger.error(`Execution error: ${e}`);
       return {
         success: false,
-        error: String(e),
+        error: toExecutionError(e),
         executionTimeMs,
       };
+    } finally {
+      for (const mint of mints) {
+        const count = (this.inFlightMints.get(mint) ?? 1) - 1;
+        if (count > 0) {
+          this.inFlightMints.set(mint, count);
+        } else {
+          this.inFlightMints.delete(mint);
+        }
+      }
+    }
+  }
+
+  /**
+   * Check the wallet's token accounts and the flash reserve's liquidity with one getMultipleAccounts
+   * call, creating missing accounts and shrinking the loan to what the reserve can lend
+   * A reserve that can't lend MIN_SHRUNK_LOAN_FRACTION of the loan starts a cooldown during which
+   * loans above the liquidity it had are rejected without another read
+   * @returns The opportunity to execute (resized if the reserve was short), or the error to fail with
+   */
+  private async precheckAccounts(
+    opportunity: ArbitrageOpportunity
+  ): Promise<{ opportunity: ArbitrageOpportunity; error?: undefined } | { error: ExecutionError }> {
+    const [baseToken] = opportunity.pair.split('/');
+    const amountLamports = BigInt(Math.floor(opportunity.flashAmount * 1_000_000));
+    const cooldown = this.reserveCooldowns.get('USDC');
+    if (cooldown && Date.now() < cooldown.until && amountLamports > cooldown.available) {
+      return { error: new ExecutionError('insufficient_liquidity', `USDC reserve short (${cooldown.available} available), cooling down`) };
+    }
+
+    // Flash borrow destination and swap accounts must exist before the loan transaction
+    const owner = this.keypair.publicKey;
+    const symbols = ['USDC', baseToken, ...(opportunity.crossLeg ? [opportunity.crossLeg.token] : [])];
+    const mints = symbols.map(symbol => tokenRegistry.mint(symbol)).filter(m => !m.equals(tokenRegistry.mint('SOL')));
+    const programs = await Promise.all(mints.map(mint => getMintInfo(this.connection, mint).then(i => i.programId)));
+    const atas = mints.map((mint, i) => getAtaForProgram(mint, owner, programs[i]));
+    const reserve = await this.kaminoClient.getFlashReserve('USDC');
+
+    const accounts = await this.connection.getMultipleAccountsInfo(reserve ? [...atas, reserve] : atas);
+    const createIxs: TransactionInstruction[] = [];
+    atas.forEach((ata, i) => {
+      if (!accounts[i]) {
+        createIxs.push(createAssociatedTokenAccountIdempotentInstruction(owner, ata, owner, mints[i], programs[i]));
+      }
+    });
+
+    // Unreadable reserve: simulation will catch a short one
+    const reserveInfo = reserve ? accounts[atas.length] : null;
+    const available = reserveInfo ? decodeAvailableLiquidity(reserveInfo.data) : null;
+    if (available !== null && available < amountLamports) {
+      if (available < (amountLamports * BigInt(Math.round(MIN_SHRUNK_LOAN_FRACTION * 100))) / 100n) {
+        this.reserveCooldowns.set('USDC', { available, until: Date.now() + RESERVE_COOLDOWN_MS });
+        logger.warn(`[Executor] USDC reserve ${reserve!.toBase58().substring(0, 8)} has ${formatTokenAmount(available, 'USDC')}, loan needs ${formatTokenAmount(amountLamports, 'USDC')}`);
+        return { error: new ExecutionError('insufficient_liquidity', `USDC reserve has ${available}, loan needs ${amountLamports}`) };
+      }
+      // Swap legs are re-quoted for the new size when the instructions are built
+      const flashAmount = Math.floor(Number(available) / 1_000_000);
+      logger.warn(`[Executor] USDC reserve short, shrinking loan from $${opportunity.flashAmount} to $${flashAmount}`);
+      opportunity = shrinkOpportunity(opportunity, flashAmount);
     }
+
+    if (createIxs.length > 0 && !await this.createAtas(createIxs)) {
+      return { error: new ExecutionError('missing_ata', 'Failed to create token accounts') };
+    }
+    return { opportunity };
+  }
+
+  /**
+   * Whether a live trade involving this mint is in progress
+   */
+  isMintInFlight(mint: string): boolean {
+    return this.inFlightMints.has(mint);
+  }
+
+  /**
+   * Whether any live trade is in progress
+   */
+  hasTradesInFlight(): boolean {
+    return this.inFlightMints.size > 0;
   }
 
   /**
@@ -204,51 +545,175 @@ export class Executor {
    * Flow: Flash Borrow USDC → Buy token on cheap DEX → Sell token on expensive DEX → Repay flash loan
    */
   private async executeFlashLoanArbitrage(
-    opportunity: ArbitrageOpportunity
+    opportunity: ArbitrageOpportunity,
+    timings: ExecutionTimings
   ): Promise<ExecutionResult> {
     const [baseToken] = opportunity.pair.split('/');
-    
-    // Calculate flash loan amount in USDC lamports (6 decimals)
-    const flashAmountLamports = BigInt(Math.floor(opportunity.flashAmount * 1_000_000));
+    // What the sell leg sells: the base token, or the cross token on a triangular route
+    const heldToken = opportunity.crossLeg?.token ?? baseToken;
+    let nonce: NonceLease | null = null;
 
     try {
+      // Kamino reverts with InsufficientLiquidity when the reserve can't cover the borrow
+      const precheck = await this.precheckAccounts(opportunity);
+      if (precheck.error) {
+        return { success: false, error: precheck.error, executionTimeMs: 0 };
+      }
+      opportunity = precheck.opportunity;
+
+      // Calculate flash loan amount in USDC lamports (6 decimals)
+      const flashAmountLamports = BigInt(Math.floor(opportunity.flashAmount * 1_000_000));
+
       // Build swap instructions using Jupiter
       // In a full implementation, we would:
       // 1. Get swap instruction for buying baseToken with USDC on buyDex
       // 2. Get swap instruction for selling baseToken for USDC on sellDex
       
-      const swapInstructions = await this.buildSwapInstructions(opportunity);
+      const { instructions: swapInstructions, priorityFeeMicroLamports } = await this.buildSwapInstructions(opportunity);
       
-      if (!swapInstructions || swapInstructions.length === 0) {
+      if (swapInstructions.length === 0) {
         return {
           success: false,
-          error: 'Failed to build swap instructions',
+          error: new ExecutionError('build_failed', 'Failed to build swap instructions'),
           executionTimeMs: 0,
         };
       }
 
-      // Execute flash loan with swap instructions
-      const result = await this.kaminoClient.executeFlashLoan({
+      // Lease a durable nonce before simulating so the simulated message matches the sent one
+      // (none free: fall back to a recent blockhash)
+      nonce = this.nonces ? await this.nonces.lease() : null;
+      if (this.nonces && !nonce) {
+        logger.debug('[Executor] No durable nonce free, using a recent blockhash');
+      }
+
+      const flashParams = {
         tokenSymbol: 'USDC',
         amountLamports: flashAmountLamports,
         borrowerKeypair: this.keypair,
         customInstructions: swapInstructions,
+        lookupTables: this.lookupTables(),
+        nonce: nonce ?? undefined,
+      };
+
+      // Snapshot everything the wallet holds for realized profit
+      const holdingsBefore = await this.profitTracker.snapshot();
+
+      // Snapshot the borrowed and sold token balances for the simulated projection
+      // (the sold token only changes when ExactOut leaves part of it unsold)
+      // wSOL and native SOL count as one asset, so a SOL base also tracks the wallet's lamports,
+      // which already include the tx fee and Jito tip
+      const owner = this.keypair.publicKey;
+      const baseIsSol = isWsol(tokenRegistry.mint(heldToken));
+      const usdcAta = await this.getAta(tokenRegistry.mint('USDC'));
+      const baseAta = await this.getAta(tokenRegistry.mint(heldToken));
+      const baseUnit = Math.pow(10, tokenRegistry.get(heldToken)!.decimals);
+      const heldPrice = opportunity.crossLeg ? opportunity.sellPrice / opportunity.crossLeg.rate : opportunity.sellPrice;
+      const balanceBefore = await this.getTokenBalance(usdcAta);
+      const baseBalanceBefore = await this.getTokenBalance(baseAta)
+        + (baseIsSol ? BigInt(await this.connection.getBalance(owner)) : 0n);
+      const deltaUsd = (usdc: bigint, base: bigint) =>
+        Number(usdc - balanceBefore) / 1_000_000 +
+        (Number(base - baseBalanceBefore) / baseUnit) * heldPrice;
+
+      // Simulate and check the projected balance delta covers flash fee + gas
+      const simulation = await withSpan('execute.simulate', { pair: opportunity.pair }, async span => {
+        const simulated = await this.kaminoClient.simulateFlashLoan(
+          flashParams,
+          baseIsSol ? [usdcAta, baseAta, owner] : [usdcAta, baseAta]
+        );
+        span.setAttribute('simulate.units_consumed', simulated?.unitsConsumed ?? 0);
+        return simulated;
+      });
+      timings.simulatedAt = Date.now();
+      if (!simulation || simulation.error) {
+        return {
+          success: false,
+          error: simulation
+            ? classifyTransactionError(simulation.error, simulation.logs, `Simulation failed: ${simulation.error}`)
+            : new ExecutionError('build_failed', 'Simulation failed: could not build transaction'),
+          executionTimeMs: 0,
+        };
+      }
+
+      const gasLamports = this.estimateGasLamports(simulation.unitsConsumed, priorityFeeMicroLamports);
+      const gasUsd = (gasLamports / LAMPORTS_PER_SOL) * this.solPriceUsd;
+      const flashFeeUsd = Number(this.kaminoClient.calculateFee(flashAmountLamports)) / 1_000_000;
+      // The wSOL ATA is closed by the trade, so a missing SOL balance means zero
+      const projectedBase = baseIsSol
+        ? (simulation.postTokenBalances.get(baseAta.toBase58()) ?? 0n)
+          + (simulation.postLamports.get(owner.toBase58()) ?? 0n)
+        : simulation.postTokenBalances.get(baseAta.toBase58()) ?? baseBalanceBefore;
+      const projectedProfit = deltaUsd(
+        simulation.postTokenBalances.get(usdcAta.toBase58()) ?? balanceBefore,
+        projectedBase
+      );
+
+      logger.info(`[Executor] Estimated gas: ${gasLamports} lamports ($${gasUsd.toFixed(4)})`);
+      logger.info(`[Executor] Simulated profit: $${projectedProfit.toFixed(4)}`);
+      if (projectedProfit < flashFeeUsd + gasUsd) {
+        return {
+          success: false,
+          error: new ExecutionError('unprofitable', `Simulated profit $${projectedProfit.toFixed(4)} below flash fee + gas ($${(flashFeeUsd + gasUsd).toFixed(4)})`),
+          executionTimeMs: 0,
+        };
+      }
+
+      // Fees must never eat into the SOL reserve; the balance comes from the holdings snapshot
+      const tipLamports = Math.floor(calculateJitoTip(opportunity.calculation.netProfit, this.solPriceUsd) * LAMPORTS_PER_SOL);
+      const worstCaseLamports = BigInt(gasLamports + tipLamports + (baseIsSol ? TOKEN_ACCOUNT_RENT_LAMPORTS : 0));
+      if (holdingsBefore.lamports - worstCaseLamports < BigInt(this.minSolReserveLamports)) {
+        const message = `Balance ${holdingsBefore.lamports} lamports minus worst-case fees ${worstCaseLamports} would go below the ${this.minSolReserveLamports} lamport reserve`;
+        logger.warn(`[Skip] ${opportunity.pair}: ${message}`);
+        return {
+          success: false,
+          error: new ExecutionError('insufficient_fee_balance', message),
+          executionTimeMs: 0,
+        };
+      }
+
+      // Execute flash loan with swap instructions
+      const { result, error } = await withSpan('execute.submit', { pair: opportunity.pair }, async span => {
+        const sent = await this.sendWithRetry(flashParams, priorityFeeMicroLamports);
+        span.setAttributes({ 'submit.success': sent.result.success, 'submit.signature': sent.result.signature ?? '' });
+        return sent;
       });
+      timings.submittedAt = result.submittedAt;
+      timings.confirmedAt = result.confirmedAt;
+      timings.confirmedSlot = result.slot;
 
       if (result.success) {
-        // Calculate actual profit (flash amount returned + profit - fees)
-        const actualProfit = opportunity.calculation.netProfit;
+        // Realized profit is the change across all wallet holdings; tx fee and tip are in the SOL delta
+        const report = this.profitTracker.diff(
+          holdingsBefore,
+          await this.profitTracker.snapshot(),
+          new Map([
+            [tokenRegistry.mint('USDC').toBase58(), 1],
+            [tokenRegistry.mint(heldToken).toBase58(), heldPrice],
+          ]),
+          this.solPriceUsd
+        );
+        for (const delta of report.deltas) {
+          logger.info(`[Executor]   ${tokenRegistry.forMint(delta.mint)?.symbol ?? delta.mint.substring(0, 8)}: ${delta.amount >= 0 ? '+' : ''}${delta.amount} (${delta.usd === null ? 'unpriced' : `$${delta.usd.toFixed(4)}`})`);
+        }
+        const actualProfit = report.netUsd;
+        const feeLamports = (await this.fetchTransactionMeta(result.signature!))?.fee;
+        if (actualProfit < 0) {
+          logger.warn(`[Executor] Realized loss: $${actualProfit.toFixed(4)} (expected $${opportunity.calculation.netProfit.toFixed(2)})`);
+        }
         
         return {
           success: true,
           txSignature: result.signature,
           actualProfit,
+          profitBreakdown: report.deltas,
+          feeLamports,
           executionTimeMs: 0,
         };
       } else {
         return {
           success: false,
-          error: result.error,
+          txSignature: result.signature,
+          error: error ?? undefined,
           executionTimeMs: 0,
         };
       }
@@ -256,27 +721,331 @@ export class Executor {
     } catch (e) {
       return {
         success: false,
-        error: String(e),
+        error: toExecutionError(e),
         executionTimeMs: 0,
       };
+    } finally {
+      if (nonce) {
+        this.nonces!.release(nonce);
+      }
     }
   }
 
+  /**
+   * Send the flash loan, resending with a higher priority fee and a fresh blockhash while it fails to land
+   * Only sends that can no longer land are retried: a dropped transaction, or a timed-out one whose
+   * durable nonce the resend shares (so at most one of them can execute)
+   */
+  private async sendWithRetry(
+    params: FlashLoanParams,
+    priorityFeeMicroLamports: number
+  ): Promise<{ result: FlashLoanResult; error: ExecutionError | null }> {
+    let fee = priorityFeeMicroLamports;
+    let result = await this.kaminoClient.executeFlashLoan(params);
+    let error = result.success ? null : await this.flashLoanError(result);
+
+    for (let attempt = 1; attempt <= this.feeEscalationRetries && error; attempt++) {
+      const retryable = error.kind === 'blockhash_expired'
+        || (error.kind === 'rpc' && result.status === 'timeout' && params.nonce !== undefined);
+      if (!retryable) break;
+
+      fee = Math.min(fee + this.feeEscalationStepMicroLamports, this.swapOptions.maxPriorityMicroLamports);
+      logger.warn(`[Executor] ${error.message}; resending (${attempt}/${this.feeEscalationRetries}) at ${fee} micro-lamports/CU`);
+      params = { ...params, customInstructions: withComputeUnitPrice(params.customInstructions, fee) };
+      result = await this.kaminoClient.executeFlashLoan(params);
+      error = result.success ? null : await this.flashLoanError(result);
+    }
+
+    if (fee !== priorityFeeMicroLamports) {
+      logger.info(`[Executor] Final priority fee: ${fee} micro-lamports/CU (${result.success ? 'landed' : 'not landed'})`);
+    }
+    return { result, error };
+  }
+
+  /**
+   * Classify a flash loan that didn't succeed: expired, timed out, or failed on-chain
+   * (on-chain failures are classified from the landed transaction's logs)
+   */
+  private async flashLoanError(result: FlashLoanResult): Promise<ExecutionError> {
+    const message = result.error ?? 'Flash loan failed';
+    switch (result.status) {
+      case 'dropped':
+        return new ExecutionError('blockhash_expired', message);
+      case 'timeout':
+        return new ExecutionError('rpc', message);
+      case 'failed': {
+        const meta = await this.fetchTransactionMeta(result.signature!);
+        return classifyTransactionError(result.err, meta?.logs, message);
+      }
+      default:
+        return result.logs
+          ? classifyTransactionError(result.error, result.logs, message)
+          : new ExecutionError(message.startsWith('Failed to build') ? 'build_failed' : 'rpc', message);
+    }
+  }
+
+  /**
+   * Network fee and program logs of a landed transaction (null if it can't be fetched)
+   */
+  private async fetchTransactionMeta(signature: string): Promise<{ fee: number; logs: string[] } | null> {
+    try {
+      const tx = await this.connection.getTransaction(signature, {
+        maxSupportedTransactionVersion: 0,
+        commitment: 'confirmed',
+      });
+      return tx?.meta ? { fee: tx.meta.fee, logs: tx.meta.logMessages ?? [] } : null;
+    } catch (e) {
+      logger.debug(`[Executor] Could not fetch transaction ${signature}: ${e}`);
+      return null;
+    }
+  }
+
+  /**
+   * Build the full flash loan transaction, simulate it and print program logs and the USDC balance change
+   * Signature verification is skipped so nothing is spent
+   * @returns null when the transaction couldn't be built or simulated
+   */
+  async simulate(opportunity: ArbitrageOpportunity): Promise<SimulationReport | null> {
+    const { instructions: swapInstructions } = await this.buildSwapInstructions(opportunity);
+    if (swapInstructions.length === 0) {
+      logger.warn('   [SIM] Could not build swap instructions to simulate');
+      return null;
+    }
+
+    const usdcAta = await this.getAta(tokenRegistry.mint('USDC'));
+    const balanceBefore = await this.getTokenBalance(usdcAta);
+    const simulation = await this.kaminoClient.simulateFlashLoan({
+      tokenSymbol: 'USDC',
+      amountLamports: BigInt(Math.floor(opportunity.flashAmount * 1_000_000)),
+      borrowerKeypair: this.keypair,
+      customInstructions: swapInstructions,
+      lookupTables: this.lookupTables(),
+    }, [usdcAta]);
+
+    if (!simulation) {
+      logger.warn('   [SIM] Simulation could not be run');
+      return null;
+    }
+
+    logger.info(`   [SIM] Simulation ${simulation.error ? `FAILED: ${simulation.error}` : 'succeeded'} (${simulation.unitsConsumed} CU)`);
+    for (const line of simulation.logs) {
+      logger.info(`   [SIM] ${line}`);
+    }
+    if (simulation.error) {
+      return {
+        unitsConsumed: simulation.unitsConsumed,
+        error: classifyTransactionError(simulation.error, simulation.logs, `Simulation failed: ${simulation.error}`),
+      };
+    }
+
+    // Excludes gas, tip and any base token left over by ExactOut
+    const usdcDelta = Number((simulation.postTokenBalances.get(usdcAta.toBase58()) ?? balanceBefore) - balanceBefore) / 1_000_000;
+    logger.info(`   [SIM] Simulated USDC change: $${usdcDelta.toFixed(4)} (expected $${opportunity.calculation.netProfit.toFixed(2)})`);
+    return { unitsConsumed: simulation.unitsConsumed, usdcDelta };
+  }
+
+  /**
+   * Make sure the wallet has token accounts for the given tokens (startup preflight), so the first
+   * trade isn't slowed by creating them; dry runs only report what would be created
+   * @returns Accounts missing, and the rent (lamports) spent or, in dry run, needed to create them
+   */
+  async preflightAtas(symbols: string[]): Promise<{ missing: number; rentLamports: number; created: boolean }> {
+    const owner = this.keypair.publicKey;
+    const mints = symbols
+      .map(symbol => tokenRegistry.get(symbol)?.mint)
+      .filter((m): m is PublicKey => !!m && !isWsol(m));
+    const programs = await Promise.all(mints.map(mint => getMintInfo(this.connection, mint).then(i => i.programId)));
+    const atas = mints.map((mint, i) => getAtaForProgram(mint, owner, programs[i]));
+
+    const accounts = await this.connection.getMultipleAccountsInfo(atas);
+    const createIxs = atas.flatMap((ata, i) => accounts[i]
+      ? []
+      : [createAssociatedTokenAccountIdempotentInstruction(owner, ata, owner, mints[i], programs[i])]);
+    if (createIxs.length === 0) {
+      return { missing: 0, rentLamports: 0, created: false };
+    }
+
+    const rentLamports = createIxs.length * await this.connection.getMinimumBalanceForRentExemption(ACCOUNT_SIZE);
+    if (this.dryRun) {
+      return { missing: createIxs.length, rentLamports, created: false };
+    }
+    return { missing: createIxs.length, rentLamports, created: await this.createAtas(createIxs) };
+  }
+
+  /**
+   * Create missing associated token accounts for the wallet
+   * Sent as a separate setup transaction because the flash borrow must be the
+   * first instruction of the loan transaction (wSOL is the exception: it is
+   * created and closed inside the loan transaction)
+   * @returns false if creation failed
+   */
+  private async createAtas(createIxs: TransactionInstruction[]): Promise<boolean> {
+    const owner = this.keypair.publicKey;
+    logger.info(`[Executor] Creating ${createIxs.length} missing token account(s)`);
+    try {
+      const { blockhash, lastValidBlockHeight } = await this.connection.getLatestBlockhash('confirmed');
+      const message = new TransactionMessage({
+        payerKey: owner,
+        recentBlockhash: blockhash,
+        instructions: createIxs,
+      }).compileToV0Message();
+      const tx = new VersionedTransaction(message);
+      tx.sign([this.keypair]);
+
+      const signature = await this.connection.sendTransaction(tx);
+      const confirmation = await this.connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+      if (confirmation.value.err) {
+        logger.error(`[Executor] ATA creation failed: ${JSON.stringify(confirmation.value.err)}`);
+        return false;
+      }
+      logger.info(`[Executor] Token accounts created: ${signature}`);
+      return true;
+    } catch (e) {
+      logger.error(`[Executor] ATA creation error: ${e}`);
+      return false;
+    }
+  }
+
+  /**
+   * Wallet ATA for a mint under its owning token program (classic or Token-2022)
+   */
+  private async getAta(mint: PublicKey): Promise<PublicKey> {
+    const { programId } = await getMintInfo(this.connection, mint);
+    return getAtaForProgram(mint, this.keypair.publicKey, programId);
+  }
+
+  /**
+   * Base token amount the buy leg is expected to deliver, net of any Token-2022 transfer fee
+   */
+  private async expectedTokenAmount(opportunity: ArbitrageOpportunity): Promise<number> {
+    const [baseToken] = opportunity.pair.split('/');
+    const gross = Math.floor((opportunity.flashAmount / opportunity.buyPrice) * Math.pow(10, tokenRegistry.get(baseToken)!.decimals));
+    const { transferFeeBps } = await getMintInfo(this.connection, tokenRegistry.mint(baseToken));
+    return Math.floor(gross * (1 - transferFeeBps / 10_000));
+  }
+
+  /**
+   * Cross token amount the middle leg of a triangular route is expected to deliver for
+   * `baseAmount`, net of any Token-2022 transfer fee
+   */
+  private async expectedCrossAmount(opportunity: ArbitrageOpportunity, baseAmount: number): Promise<number> {
+    const [baseToken] = opportunity.pair.split('/');
+    const { token, rate } = opportunity.crossLeg!;
+    const baseUnits = baseAmount / Math.pow(10, tokenRegistry.get(baseToken)!.decimals);
+    const gross = Math.floor(baseUnits * rate * Math.pow(10, tokenRegistry.get(token)!.decimals));
+    const { transferFeeBps } = await getMintInfo(this.connection, tokenRegistry.mint(token));
+    return Math.floor(gross * (1 - transferFeeBps / 10_000));
+  }
+
+  /**
+   * Fetch a token account's raw amount (0 if it doesn't exist)
+   */
+  private async getTokenBalance(ata: PublicKey): Promise<bigint> {
+    try {
+      const balance = await this.connection.getTokenAccountBalance(ata, 'confirmed');
+      return BigInt(balance.value.amount);
+    } catch (e) {
+      return 0n;
+    }
+  }
+
+  /**
+   * Estimate network fees from simulated compute usage
+   * fee = base signature fee + units consumed × priority fee
+   * Not cached: the priority fee changes per transaction (and with profit), and the simulation
+   * that reports the units has already run
+   */
+  private estimateGasLamports(unitsConsumed: number, priorityFeeMicroLamports: number): number {
+    // Fall back to the compute unit limit if simulation didn't report usage
+    unitsConsumed = unitsConsumed || 1_400_000;
+    const priorityLamports = Math.ceil((unitsConsumed * priorityFeeMicroLamports) / 1_000_000);
+    return BASE_SIGNATURE_FEE_LAMPORTS + priorityLamports;
+  }
+
+  private swapApi(dex: string): SwapApi {
+    const api = this.swapApis[dex];
+    if (!api) {
+      throw new Error(`No swap API for DEX: ${dex}`);
+    }
+    return api;
+  }
+
+  /**
+   * Quote every leg at the configured slippage
+   * Returns the largest price impact plus the expected and worst-case USDC out (all legs
+   * at their slippage limit); the amounts are null when a leg couldn't be quoted
+   */
+  private async quoteRoute(
+    opportunity: ArbitrageOpportunity
+  ): Promise<{ priceImpactPct: number; expectedUsdcOut: number | null; worstCaseUsdcOut: number | null }> {
+    const [baseToken] = opportunity.pair.split('/');
+    const baseMint = tokenRegistry.mint(baseToken);
+    const usdcMint = tokenRegistry.mint('USDC');
+
+    if (!baseMint) {
+      return { priceImpactPct: 0, expectedUsdcOut: null, worstCaseUsdcOut: null };
+    }
+
+    const usdcAmountIn = Math.floor(opportunity.flashAmount * 1_000_000);
+    const expectedTokenAmount = await this.expectedTokenAmount(opportunity);
+    const { crossLeg } = opportunity;
+    const crossMint = crossLeg ? tokenRegistry.mint(crossLeg.token) : null;
+    const expectedCrossAmount = crossLeg ? await this.expectedCrossAmount(opportunity, expectedTokenAmount) : 0;
+    const { slippageBps } = this.swapOptions;
+
+    const quoteLeg = async (
+      dex: string,
+      inputMint: PublicKey,
+      outputMint: PublicKey,
+      amount: number
+    ): Promise<SwapLegQuote | null> => {
+      try {
+        return await this.swapApi(dex).quote(inputMint, outputMint, amount, slippageBps);
+      } catch (e) {
+        logger.warn(`[Executor] Failed to quote ${dex}: ${e}`);
+        return null;
+      }
+    };
+
+    const [buy, cross, sell] = await Promise.all([
+      quoteLeg(opportunity.buyDex, usdcMint, baseMint, usdcAmountIn),
+      crossLeg && crossMint ? quoteLeg(crossLeg.dex, baseMint, crossMint, expectedTokenAmount) : null,
+      crossMint
+        ? quoteLeg(opportunity.sellDex, crossMint, usdcMint, expectedCrossAmount)
+        : quoteLeg(opportunity.sellDex, baseMint, usdcMint, expectedTokenAmount),
+    ]);
+
+    const priceImpactPct = Math.max(buy?.priceImpactPct ?? 0, cross?.priceImpactPct ?? 0, sell?.priceImpactPct ?? 0);
+    if (!buy || !sell || buy.outAmount <= 0 || sell.outAmount <= 0 || (crossLeg && (!cross || cross.outAmount <= 0))) {
+      return { priceImpactPct, expectedUsdcOut: null, worstCaseUsdcOut: null };
+    }
+
+    // Later legs were quoted for the scan-time amounts; scale by what the earlier legs deliver now
+    let expectedUsdcOut = sell.outAmount * (buy.outAmount / expectedTokenAmount);
+    let worstCaseUsdcOut = sell.minOutAmount * (buy.minOutAmount / expectedTokenAmount);
+    if (cross) {
+      expectedUsdcOut *= cross.outAmount / expectedCrossAmount;
+      worstCaseUsdcOut *= cross.minOutAmount / expectedCrossAmount;
+    }
+    return { priceImpactPct, expectedUsdcOut, worstCaseUsdcOut };
+  }
+
   /**
    * Build swap instructions for the arbitrage
-   * Uses Raydium/Orca DEX SDKs directly
+   * Each leg goes through the SwapApi for its DEX
+   * @returns The instructions (empty on failure) and the compute unit price they set
    */
   private async buildSwapInstructions(
     opportunity: ArbitrageOpportunity
-  ): Promise<TransactionInstruction[]> {
+  ): Promise<{ instructions: TransactionInstruction[]; priorityFeeMicroLamports: number }> {
     const [baseToken] = opportunity.pair.split('/');
-    const baseMint = TOKEN_MINTS[baseToken];
-    const usdcMint = TOKEN_MINTS['USDC'];
-    const baseDecimals = TOKEN_DECIMALS[baseToken];
+    const baseMint = tokenRegistry.mint(baseToken);
+    const usdcMint = tokenRegistry.mint('USDC');
+    const failed = { instructions: [], priorityFeeMicroLamports: 0 };
     
     if (!baseMint) {
       logger.error(`Unknown token: ${baseToken}`);
-      return [];
+      return failed;
     }
 
     const instructions: TransactionInstruction[] = [];
@@ -284,88 +1053,100 @@ export class Executor {
     try {
       // Calculate amounts
       const usdcAmountIn = Math.floor(opportunity.flashAmount * 1_000_000); // USDC has 6 decimals
-      const expectedTokenAmount = Math.floor((opportunity.flashAmount / opportunity.buyPrice) * Math.pow(10, baseDecimals));
+      const expectedTokenAmount = await this.expectedTokenAmount(opportunity);
+      const { crossLeg } = opportunity;
+      const crossMint = crossLeg ? tokenRegistry.mint(crossLeg.token) : null;
 
-      logger.info(`[Executor] Building swaps: ${opportunity.flashAmount} USDC → ${baseToken} → USDC`);
-      logger.info(`[Executor] Buy on ${opportunity.buyDex}, Sell on ${opportunity.sellDex}`);
+      const tokens = crossLeg ? `${baseToken} → ${crossLeg.token}` : baseToken;
+      logger.info(`[Executor] Building swaps: ${opportunity.flashAmount} USDC → ${tokens} → USDC`);
+      logger.info(`[Executor] Route: ${describeRoute(opportunity)}`);
+
+      // SOL legs settle through the wSOL ATA: create + sync before, unwrap after all swaps
+      const wrapSol = isWsol(baseMint) || (crossMint !== null && isWsol(crossMint));
+      const swapOptions = wrapSol ? { ...this.swapOptions, wrapUnwrapSol: false } : this.swapOptions;
+      if (wrapSol) {
+        instructions.push(...buildWrapSolInstructions(this.keypair.publicKey));
+      }
 
       // STEP 1: Buy token on buyDex (USDC → baseToken)
-      if (opportunity.buyDex === 'raydium') {
-        const buyTxBuffer = await this.raydiumClient.buildSwapTransaction(
-          usdcMint.toBase58(),
-          baseMint.toBase58(),
-          usdcAmountIn,
-          this.keypair.publicKey,
-          100 // 1% slippage
-        );
-        if (!buyTxBuffer) {
-          logger.error('[Executor] Failed to build Raydium buy transaction');
-          return [];
-        }
-        // Extract instructions from Raydium versioned transaction
-        const buyTx = VersionedTransaction.deserialize(buyTxBuffer);
-        const buyIxs = await this.extractInstructionsFromVersionedTx(buyTx);
-        instructions.push(...buyIxs);
-        logger.info(`[Executor] Raydium buy: ${buyIxs.length} instructions`);
-      } else if (opportunity.buyDex === 'orca') {
-        const buyTxBuilder = await this.orcaClient.buildSwapTransaction(
-          usdcMint,
-          baseMint,
-          new BN(usdcAmountIn),
-          this.keypair.publicKey,
-          1 // 1% slippage
-        );
-        if (!buyTxBuilder) {
-          logger.error('[Executor] Failed to build Orca buy transaction');
-          return [];
-        }
-        // Orca TransactionBuilder.compressIx(false) returns Instruction with instructions array
-        const buyIx = buyTxBuilder.compressIx(false);
-        instructions.push(...buyIx.instructions);
-        // Add cleanup instructions at the end (close WSOL accounts etc)
-        if (buyIx.cleanupInstructions?.length > 0) {
-          instructions.push(...buyIx.cleanupInstructions);
-        }
-        logger.info(`[Executor] Orca buy: ${buyIx.instructions.length} instructions`);
+      const buyIxs = await this.swapApi(opportunity.buyDex).buildSwapInstructions(
+        usdcMint,
+        baseMint,
+        usdcAmountIn,
+        this.keypair.publicKey,
+        swapOptions
+      );
+      if (!buyIxs) {
+        logger.error(`[Executor] Failed to build ${opportunity.buyDex} buy transaction`);
+        return failed;
       }
+      instructions.push(...buyIxs);
+      logger.info(`[Executor] ${opportunity.buyDex} buy: ${buyIxs.length} instructions`);
 
-      // STEP 2: Sell token on sellDex (baseToken → USDC)
-      if (opportunity.sellDex === 'raydium') {
-        const sellTxBuffer = await this.raydiumClient.buildSwapTransaction(
-          baseMint.toBase58(),
-          usdcMint.toBase58(),
-          expectedTokenAmount,
-          this.keypair.publicKey,
-          100 // 1% slippage
-        );
-        if (!sellTxBuffer) {
-          logger.error('[Executor] Failed to build Raydium sell transaction');
-          return [];
-        }
-        // Extract instructions from Raydium versioned transaction
-        const sellTx = VersionedTransaction.deserialize(sellTxBuffer);
-        const sellIxs = await this.extractInstructionsFromVersionedTx(sellTx);
-        instructions.push(...sellIxs);
-        logger.info(`[Executor] Raydium sell: ${sellIxs.length} instructions`);
-      } else if (opportunity.sellDex === 'orca') {
-        const sellTxBuilder = await this.orcaClient.buildSwapTransaction(
+      // Triangular routes swap the base token into the cross token and sell that instead
+      let crossIxs: TransactionInstruction[] = [];
+      let sellMint = baseMint;
+      let sellAmount = expectedTokenAmount;
+      if (crossLeg && crossMint) {
+        const ixs = await this.swapApi(crossLeg.dex).buildSwapInstructions(
           baseMint,
-          usdcMint,
-          new BN(expectedTokenAmount),
+          crossMint,
+          expectedTokenAmount,
           this.keypair.publicKey,
-          1 // 1% slippage
+          swapOptions
         );
-        if (!sellTxBuilder) {
-          logger.error('[Executor] Failed to build Orca sell transaction');
-          return [];
+        if (!ixs) {
+          logger.error(`[Executor] Failed to build ${crossLeg.dex} ${baseToken} → ${crossLeg.token} swap`);
+          return failed;
         }
-        // Orca TransactionBuilder.compressIx(false) returns Instruction with instructions array
-        const sellIx = sellTxBuilder.compressIx(false);
-        instructions.push(...sellIx.instructions);
-        if (sellIx.cleanupInstructions?.length > 0) {
-          instructions.push(...sellIx.cleanupInstructions);
+        crossIxs = ixs;
+        instructions.push(...crossIxs);
+        logger.info(`[Executor] ${crossLeg.dex} swap: ${crossIxs.length} instructions`);
+        sellMint = crossMint;
+        sellAmount = await this.expectedCrossAmount(opportunity, expectedTokenAmount);
+      }
+
+      // STEP 2: Sell token on sellDex (baseToken or cross token → USDC)
+      // ExactOut: receive exactly the flash repayment, keep the rest as the sold token
+      const sellApi = this.swapApi(opportunity.sellDex);
+      const exactOut = this.exactOutRepay && sellApi.supportsExactOut;
+      const repayAmount = usdcAmountIn + Number(this.kaminoClient.calculateFee(BigInt(usdcAmountIn)));
+      const sellIxs = await sellApi.buildSwapInstructions(
+        sellMint,
+        usdcMint,
+        exactOut ? repayAmount : sellAmount,
+        this.keypair.publicKey,
+        swapOptions,
+        exactOut ? 'ExactOut' : 'ExactIn'
+      );
+      if (!sellIxs) {
+        logger.error(`[Executor] Failed to build ${opportunity.sellDex} sell transaction`);
+        return failed;
+      }
+      instructions.push(...sellIxs);
+      logger.info(`[Executor] ${opportunity.sellDex} sell: ${sellIxs.length} instructions`);
+
+      if (wrapSol) {
+        instructions.push(...buildUnwrapSolInstructions(this.keypair.publicKey));
+      }
+
+      // Price compute against recent fees on the pools we write to; goes after the
+      // flash borrow (which must stay at index 0)
+      const poolAccounts = new Map<string, PublicKey>();
+      for (const ix of [...buyIxs, ...crossIxs, ...sellIxs]) {
+        for (const key of ix.keys) {
+          if (key.isWritable && !key.isSigner) {
+            poolAccounts.set(key.pubkey.toBase58(), key.pubkey);
+          }
         }
-        logger.info(`[Executor] Orca sell: ${sellIx.instructions.length} instructions`);
+      }
+      const priorityFeeMicroLamports = await this.priorityFee.resolve(
+        Array.from(poolAccounts.values()),
+        opportunity.calculation.netProfit
+      );
+      if (priorityFeeMicroLamports > 0) {
+        instructions.unshift(ComputeBudgetProgram.setComputeUnitPrice({ microLamports: priorityFeeMicroLamports }));
+        logger.info(`[Executor] Priority fee: ${priorityFeeMicroLamports} micro-lamports/CU`);
       }
 
       // STEP 3: Add Jito tip instruction
@@ -383,77 +1164,33 @@ export class Executor {
       }
 
       logger.info(`[Executor] Total instructions: ${instructions.length}`);
-      return instructions;
+      return { instructions, priorityFeeMicroLamports };
 
     } catch (e) {
       logger.error(`[Executor] Error building swap instructions: ${e}`);
-      return [];
+      return failed;
     }
   }
 
   /**
-   * Extract instructions from a VersionedTransaction
-   * Handles both static accounts and address lookup tables
+   * USDC (in dollars) currently available for flash loans
    */
-  private async extractInstructionsFromVersionedTx(tx: VersionedTransaction): Promise<TransactionInstruction[]> {
-    const message = tx.message;
-    const instructions: TransactionInstruction[] = [];
-    
-    // Get all account keys (static + from lookup tables)
-    let allAccountKeys: PublicKey[] = [...message.staticAccountKeys];
-    
-    // If there are address lookup tables, resolve them
-    if (message.addressTableLookups && message.addressTableLookups.length > 0) {
-      for (const lookup of message.addressTableLookups) {
-        try {
-          const lookupTableAccount = await this.connection.getAddressLookupTable(lookup.accountKey);
-          if (lookupTableAccount.value) {
-            // Add writable accounts
-            for (const idx of lookup.writableIndexes) {
-              allAccountKeys.push(lookupTableAccount.value.state.addresses[idx]);
-            }
-            // Add readonly accounts
-            for (const idx of lookup.readonlyIndexes) {
-              allAccountKeys.push(lookupTableAccount.value.state.addresses[idx]);
-            }
-          }
-        } catch (e) {
-          logger.warn(`[Executor] Failed to resolve lookup table: ${e}`);
-        }
-      }
-    }
-    
-    // Convert each compiled instruction to TransactionInstruction
-    for (const ix of message.compiledInstructions) {
-      const programId = allAccountKeys[ix.programIdIndex];
-      if (!programId) {
-        logger.warn(`[Executor] Missing programId at index ${ix.programIdIndex}`);
-        continue;
-      }
-      
-      const keys = ix.accountKeyIndexes.map(idx => {
-        const pubkey = allAccountKeys[idx];
-        if (!pubkey) {
-          logger.warn(`[Executor] Missing account at index ${idx}`);
-          return null;
-        }
-        return {
-          pubkey,
-          isSigner: idx < message.header.numRequiredSignatures,
-          isWritable: idx < message.header.numRequiredSignatures - message.header.numReadonlySignedAccounts ||
-                     (idx >= message.header.numRequiredSignatures && 
-                      idx < allAccountKeys.length - message.header.numReadonlyUnsignedAccounts),
-        };
-      }).filter(k => k !== null) as { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[];
-      
-      instructions.push(new TransactionInstruction({
-        programId,
-        keys,
-        data: Buffer.from(ix.data),
-      }));
-    }
-    
-    return instructions;
+  async getFlashLiquidity(): Promise<number> {
+    return this.kaminoClient.getAvailableLiquidity('USDC');
+  }
+
+  /**
+   * Kamino deposit TVL (USD) per loaded market (null for markets that failed to reload)
+   */
+  async getKaminoTvl(): Promise<Map<string, number | null>> {
+    return this.kaminoClient.getDepositTvl();
+  }
+
+  /**
+   * USDC flash loan fee rate net of any referral rebate, for profit estimates
+   */
+  getFlashLoanFee(): number {
+    return this.kaminoClient.getNetFlashLoanFee('USDC');
   }
 
   /**
@@ -5,49 +5,52 @@
  */
 
 import { 
+  AddressLookupTableAccount,
+  Commitment,
   Connection, 
   PublicKey, 
   Keypair, 
+  SendTransactionError,
   Transaction, 
+  TransactionError,
   TransactionInstruction,
   VersionedTransaction,
   TransactionMessage,
 } from '@solana/web3.js';
 import { 
   KaminoMarket, 
+  KaminoReserve,
+  Reserve,
   PROGRAM_ID as KAMINO_PROGRAM_ID,
   getFlashLoanInstructions,
+  referrerTokenStatePda,
 } from '@kamino-finance/klend-sdk';
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import Decimal from 'decimal.js';
-
-// Helper to derive ATA (compatible with all spl-token versions)
-function getATA(mint: PublicKey, owner: PublicKey): PublicKey {
-  const [ata] = PublicKey.findProgramAddressSync(
-    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
-    ASSOCIATED_TOKEN_PROGRAM_ID
-  );
-  return ata;
-}
+import { MarketRegistry } from './market-registry.js';
+import { getMintInfo, getAtaForProgram } from './utils/token-program.js';
+import { sendAndConfirm, SendStatus } from './utils/confirm.js';
+import { NonceLease } from './nonce-manager.js';
+import { tokenRegistry } from './token-registry.js';
 
 // Kamino Main Market address
-const KAMINO_MAIN_MARKET = new PublicKey('7u3HeHxYDLhnCoErrtycNokbQYbWGzLs6JSDqGAv5PfF');
-
-// Token mint addresses
-const TOKEN_MINTS: Record<string, PublicKey> = {
-  'SOL': new PublicKey('So11111111111111111111111111111111111111112'),
-  'USDC': new PublicKey('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v'),
-  'JUP': new PublicKey('JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN'),
-  'JTO': new PublicKey('jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL'),
-  'BONK': new PublicKey('DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263'),
-  'WIF': new PublicKey('EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm'),
-};
+export const KAMINO_MAIN_MARKET = new PublicKey('7u3HeHxYDLhnCoErrtycNokbQYbWGzLs6JSDqGAv5PfF');
+
+/**
+ * Available liquidity (base units) from raw Reserve account data
+ */
+export function decodeAvailableLiquidity(data: Buffer): bigint {
+  return BigInt(Reserve.decode(data).liquidity.availableAmount.toString());
+}
 
 export interface FlashLoanParams {
   tokenSymbol: string;
   amountLamports: bigint;
   borrowerKeypair: Keypair;
   customInstructions: TransactionInstruction[];
+  // Tables used to compress account keys when compiling the v0 message
+  lookupTables?: AddressLookupTableAccount[];
+  // Durable nonce used instead of a recent blockhash (advance instruction goes first)
+  nonce?: NonceLease;
 }
 
 export interface FlashLoanResult {
@@ -55,6 +58,82 @@ export interface FlashLoanResult {
   signature?: string;
   error?: string;
   flashFee?: number;
+  // How the send ended (unset when the transaction could not be built or sent)
+  status?: SendStatus;
+  // On-chain error of a landed failure
+  err?: TransactionError;
+  // Program logs from a rejected preflight
+  logs?: string[];
+  // Send/landing times (ms) and landed slot
+  submittedAt?: number;
+  confirmedAt?: number;
+  slot?: number;
+}
+
+export interface FlashLoanSimulation {
+  unitsConsumed: number;
+  logs: string[];
+  error?: string;
+  // Post-simulation amounts (base units) of the requested token accounts
+  postTokenBalances: Map<string, bigint>;
+  // Post-simulation lamports of every requested account that still exists
+  postLamports: Map<string, bigint>;
+}
+
+// SPL token account layout: amount is a u64 after mint (32) and owner (32)
+const TOKEN_ACCOUNT_AMOUNT_OFFSET = 64;
+
+/**
+ * Kamino client options
+ * With a registry, markets are discovered on-chain; otherwise the given list (default: main market)
+ */
+export interface KaminoClientOptions {
+  markets?: PublicKey[];
+  registry?: MarketRegistry;
+  // Rebroadcasts of a sent transaction while waiting for it to land
+  maxRetries?: number;
+  // Commitment for the blockhash and for counting a transaction as landed (default: confirmed)
+  commitment?: Commitment;
+  skipPreflight?: boolean;
+  // Give up waiting for a sent transaction after this long
+  confirmTimeoutMs?: number;
+  // Referrer passed to flash borrow/repay; the full fee is still repaid, and the market's referral
+  // share of it (referral fee bps) accrues to the referrer token state as a rebate to claim later
+  referrer?: PublicKey;
+}
+
+// Default flash loan fee when the reserve isn't loaded (0.001%)
+const DEFAULT_FLASH_LOAN_FEE = 0.00001;
+
+// Kamino scaled fractions are fixed point with 60 fractional bits
+const SCALED_FRACTION_ONE = 2 ** 60;
+
+/**
+ * Available liquidity (base units) of a loaded reserve, the field decodeAvailableLiquidity reads
+ */
+function reserveLiquidity(reserve: KaminoReserve): bigint {
+  return BigInt(reserve.state.liquidity.availableAmount.toString());
+}
+
+/**
+ * Drop (%) in deposit TVL summed over the markets present in both snapshots, so markets added or
+ * removed since the baseline don't count as a drop
+ * @returns null when the snapshots share no market
+ */
+export function tvlDropPercent(
+  baseline: ReadonlyMap<string, number>,
+  current: ReadonlyMap<string, number>
+): { dropPercent: number; before: number; after: number } | null {
+  let before = 0;
+  let after = 0;
+  for (const [address, tvl] of current) {
+    const base = baseline.get(address);
+    if (base === undefined) continue;
+    before += base;
+    after += tvl;
+  }
+  if (before <= 0) return null;
+  return { dropPercent: (1 - after / before) * 100, before, after };
 }
 
 /**
@@ -63,36 +142,44 @@ export interface FlashLoanResult {
  */
 export class KaminoFlashLoanClient {
   private connection: Connection;
-  private market: KaminoMarket | null = null;
+  private markets: Map<string, KaminoMarket> = new Map();
+  private configuredMarkets: PublicKey[];
+  private registry: MarketRegistry | null;
+  private referrer: PublicKey | null;
+  // Market authority and referrer token state PDAs, derived once per market/reserve
+  private derivedAccounts: Map<string, PublicKey> = new Map();
+  private maxRetries: number;
+  private commitment: Commitment;
+  private skipPreflight: boolean;
+  private confirmTimeoutMs: number;
   private initialized = false;
 
-  constructor(connection: Connection) {
+  constructor(connection: Connection, options: KaminoClientOptions = {}) {
     this.connection = connection;
+    this.configuredMarkets = options.markets?.length ? options.markets : [KAMINO_MAIN_MARKET];
+    this.registry = options.registry ?? null;
+    this.maxRetries = options.maxRetries ?? 3;
+    this.commitment = options.commitment ?? 'confirmed';
+    this.skipPreflight = options.skipPreflight ?? false;
+    this.confirmTimeoutMs = options.confirmTimeoutMs ?? 30_000;
+    this.referrer = options.referrer ?? null;
   }
 
   /**
-   * Initialize the Kamino market
+   * Initialize the Kamino markets
    */
   async initialize(): Promise<void> {
     if (this.initialized) return;
 
     try {
-      console.log('[Kamino] Loading market...');
-      this.market = await KaminoMarket.load(
-        this.connection,
-        KAMINO_MAIN_MARKET,
-        400 // Recent slot duration
-      );
-      
-      if (!this.market) {
+      await this.loadMarkets();
+      if (this.markets.size === 0) {
         throw new Error('Failed to load Kamino market');
       }
 
-      // Load reserves
-      await this.market.loadReserves();
-      
       this.initialized = true;
-      console.log('[Kamino] Market initialized with', this.market.reserves.size, 'reserves');
+      const reserveCount = Array.from(this.markets.values()).reduce((n, m) => n + m.reserves.size, 0);
+      console.log(`[Kamino] ${this.markets.size} market(s) initialized with ${reserveCount} reserves`);
     } catch (e) {
       console.error('[Kamino] Failed to initialize:', e);
       throw e;
@@ -100,67 +187,191 @@ export class KaminoFlashLoanClient {
   }
 
   /**
-   * Get the flash loan fee for a token (0.001% = 0.00001)
+   * Load any markets not loaded yet (from the registry when configured)
+   */
+  private async loadMarkets(): Promise<void> {
+    const addresses = this.registry ? await this.registry.getKaminoMarkets() : this.configuredMarkets;
+    const missing = addresses.filter(a => !this.markets.has(a.toBase58()));
+    if (missing.length === 0) return;
+
+    console.log(`[Kamino] Loading ${missing.length} market(s)...`);
+    const results = await Promise.allSettled(missing.map(async address => {
+      const market = await KaminoMarket.load(
+        this.connection,
+        address,
+        400 // Recent slot duration
+      );
+      if (!market) {
+        throw new Error(`Failed to load Kamino market ${address.toBase58()}`);
+      }
+      // Load reserves
+      await market.loadReserves();
+      this.markets.set(address.toBase58(), market);
+    }));
+
+    for (const result of results) {
+      if (result.status === 'rejected') {
+        console.error('[Kamino]', result.reason);
+      }
+    }
+  }
+
+  /**
+   * Pick up newly discovered markets once the registry TTL expires
+   */
+  private async refreshMarkets(): Promise<void> {
+    if (this.registry?.isStale()) {
+      await this.loadMarkets();
+    }
+  }
+
+  /**
+   * Market whose reserve for the mint has the most available liquidity
+   */
+  private findMarketForMint(mint: PublicKey): { address: PublicKey; market: KaminoMarket; reserve: KaminoReserve } | null {
+    let best: { address: PublicKey; market: KaminoMarket; reserve: KaminoReserve } | null = null;
+    for (const [address, market] of this.markets) {
+      const reserve = market.getReserveByMint(mint);
+      if (reserve && (!best || reserveLiquidity(reserve) > reserveLiquidity(best.reserve))) {
+        best = { address: new PublicKey(address), market, reserve };
+      }
+    }
+    return best;
+  }
+
+  /**
+   * Accounts referenced by flash loans of the given tokens, for the wallet's lookup table:
+   * each loaded market and its authority, plus the token's reserve and vaults
+   */
+  async getHotAccounts(tokenSymbols: string[]): Promise<PublicKey[]> {
+    if (!this.initialized) {
+      await this.initialize();
+    }
+    await this.refreshMarkets();
+
+    const accounts: PublicKey[] = [KAMINO_PROGRAM_ID];
+    for (const [address, market] of this.markets) {
+      accounts.push(new PublicKey(address), await this.lendingMarketAuthority(address, market));
+      for (const symbol of tokenSymbols) {
+        const mint = tokenRegistry.get(symbol)?.mint;
+        const reserve = mint && market.getReserveByMint(mint);
+        if (reserve) {
+          accounts.push(reserve.address, reserve.state.liquidity.supplyVault, reserve.state.liquidity.feeVault);
+        }
+      }
+    }
+    return accounts;
+  }
+
+  /**
+   * Lending market authority PDA (derived once per market)
+   */
+  private async lendingMarketAuthority(address: string, market: KaminoMarket): Promise<PublicKey> {
+    const key = `authority:${address}`;
+    let authority = this.derivedAccounts.get(key);
+    if (!authority) {
+      authority = await market.getLendingMarketAuthority();
+      this.derivedAccounts.set(key, authority);
+    }
+    return authority;
+  }
+
+  /**
+   * Referrer token state for a reserve (PublicKey.default without a referrer)
+   */
+  private referrerTokenState(reserve: PublicKey): PublicKey {
+    if (!this.referrer) return PublicKey.default;
+    const key = `referrer:${reserve.toBase58()}`;
+    let state = this.derivedAccounts.get(key);
+    if (!state) {
+      state = referrerTokenStatePda(this.referrer, reserve, KAMINO_PROGRAM_ID)[0];
+      this.derivedAccounts.set(key, state);
+    }
+    return state;
+  }
+
+  /**
+   * Flash loan fee rate flash_repay charges for a token, from the deepest reserve's config
+   * This is what the repayment must cover, referrer or not
    */
-  getFlashLoanFee(): number {
-    return 0.00001; // 0.001% fee
+  getFlashLoanFee(tokenSymbol = 'USDC'): number {
+    const tokenMint = tokenRegistry.get(tokenSymbol)?.mint;
+    const found = tokenMint && this.findMarketForMint(tokenMint);
+    if (!found) return DEFAULT_FLASH_LOAN_FEE;
+    return Number(found.reserve.state.config.fees.flashLoanFeeSf.toString()) / SCALED_FRACTION_ONE;
   }
 
   /**
-   * Calculate the flash loan fee in lamports
+   * Flash loan fee rate net of the referral rebate our own referrer can claim back
+   * For profit estimates only; repayments use getFlashLoanFee/calculateFee
    */
-  calculateFee(amountLamports: bigint): bigint {
-    return amountLamports / 100000n; // 0.001%
+  getNetFlashLoanFee(tokenSymbol = 'USDC'): number {
+    const tokenMint = tokenRegistry.get(tokenSymbol)?.mint;
+    const found = this.referrer && tokenMint ? this.findMarketForMint(tokenMint) : null;
+    const referralBps = found ? found.market.state.referralFeeBps : 0;
+    return this.getFlashLoanFee(tokenSymbol) * (1 - referralBps / 10_000);
+  }
+
+  /**
+   * Calculate the flash loan fee in lamports (rounded up, like the program)
+   * Always the full fee: this sizes the repayment
+  calculateFee(amountLamports: bigint, tokenSymbol = 'USDC'): bigint {
+    // Rate in parts per 10^12 keeps the multiplication exact in bigint
+    const ratePpt = BigInt(Math.round(this.getFlashLoanFee(tokenSymbol) * 1e12));
+    return (amountLamports * ratePpt + 999_999_999_999n) / 1_000_000_000_000n;
   }
 
   /**
    * Build flash loan instructions
    * Returns the borrow and repay instructions to wrap around custom logic
+   * @param borrowIxnIndex Position of the borrow in the transaction (1 after a nonce advance)
    */
   async buildFlashLoanInstructions(
     tokenSymbol: string,
     amountLamports: bigint,
-    borrowerKeypair: Keypair
+    borrowerKeypair: Keypair,
+    borrowIxnIndex = 0
   ): Promise<{
     flashBorrowIx: TransactionInstruction;
     flashRepayIx: TransactionInstruction;
     destinationAta: PublicKey;
   } | null> {
-    if (!this.market) {
+    if (!this.initialized) {
       await this.initialize();
     }
 
-    const tokenMint = TOKEN_MINTS[tokenSymbol];
+    const tokenMint = tokenRegistry.get(tokenSymbol)?.mint;
     if (!tokenMint) {
       console.error(`[Kamino] Unknown token: ${tokenSymbol}`);
       return null;
     }
 
     try {
-      // Get reserve for the token
-      const reserve = this.market!.getReserveByMint(tokenMint);
-      if (!reserve) {
+      // Get reserve for the token from the deepest market
+      const found = this.findMarketForMint(tokenMint);
+      if (!found) {
         console.error(`[Kamino] No reserve found for ${tokenSymbol}`);
         return null;
       }
+      const { address: lendingMarketAddress, market, reserve } = found;
 
-      // Get borrower's ATA for this token
-      const destinationAta = getATA(tokenMint, borrowerKeypair.publicKey);
+      // Get borrower's ATA for this token (under its classic or Token-2022 program)
+      const { programId: tokenProgram } = await getMintInfo(this.connection, tokenMint);
+      const destinationAta = getAtaForProgram(tokenMint, borrowerKeypair.publicKey, tokenProgram);
 
-      // Get lending market authority
-      const lendingMarketAuthority = await this.market!.getLendingMarketAuthority();
+      const lendingMarketAuthority = await this.lendingMarketAuthority(lendingMarketAddress.toBase58(), market);
 
       // Build flash loan instructions using SDK
       const { flashBorrowIxn, flashRepayIxn } = getFlashLoanInstructions({
-        borrowIxnIndex: 0, // Flash borrow will be first instruction
+        borrowIxnIndex, // Repay points back at the borrow by instruction index
         walletPublicKey: borrowerKeypair.publicKey,
         lendingMarketAuthority,
-        lendingMarketAddress: KAMINO_MAIN_MARKET,
+        lendingMarketAddress,
         reserve,
         amountLamports: new Decimal(amountLamports.toString()),
         destinationAta,
-        referrerAccount: PublicKey.default,
-        referrerTokenState: PublicKey.default,
+        referrerAccount: this.referrer ?? PublicKey.default,
+        referrerTokenState: this.referrerTokenState(reserve.address),
         programId: KAMINO_PROGRAM_ID,
       });
 
@@ -177,64 +388,141 @@ export class KaminoFlashLoanClient {
   }
 
   /**
-   * Execute a flash loan with custom instructions in between
-   * Flow: Flash Borrow → Custom Instructions → Flash Repay
+   * Build and sign the flash loan transaction
+   * Layout: [Advance Nonce →] Flash Borrow → Custom Instructions → Flash Repay
    */
-  async executeFlashLoan(params: FlashLoanParams): Promise<FlashLoanResult> {
-    const { tokenSymbol, amountLamports, borrowerKeypair, customInstructions } = params;
+  async buildFlashLoanTransaction(params: FlashLoanParams): Promise<{
+    transaction: VersionedTransaction;
+    blockhash: string;
+    lastValidBlockHeight: number;
+  } | null> {
+    const { tokenSymbol, amountLamports, borrowerKeypair, customInstructions, lookupTables = [], nonce } = params;
 
-    try {
-      // Build flash loan instructions
-      const flashLoanIxs = await this.buildFlashLoanInstructions(
-        tokenSymbol,
-        amountLamports,
-        borrowerKeypair
-      );
+    // Build flash loan instructions
+    const flashLoanIxs = await this.buildFlashLoanInstructions(
+      tokenSymbol,
+      amountLamports,
+      borrowerKeypair,
+      nonce ? 1 : 0
+    );
 
-      if (!flashLoanIxs) {
-        return { success: false, error: 'Failed to build flash loan instructions' };
-      }
+    if (!flashLoanIxs) {
+      return null;
+    }
 
-      const { flashBorrowIx, flashRepayIx } = flashLoanIxs;
+    const { flashBorrowIx, flashRepayIx } = flashLoanIxs;
 
-      // Combine all instructions: borrow → custom → repay
-      const allInstructions = [
-        flashBorrowIx,
-        ...customInstructions,
-        flashRepayIx,
-      ];
+    // Combine all instructions: [advance nonce →] borrow → custom → repay
+    const allInstructions = [
+      ...(nonce ? [nonce.advanceIx] : []),
+      flashBorrowIx,
+      ...customInstructions,
+      flashRepayIx,
+    ];
 
-      // Get recent blockhash
-      const { blockhash, lastValidBlockHeight } = await this.connection.getLatestBlockhash('finalized');
+    // Get recent blockhash; a durable nonce stands in for it and never expires
+    const latest = await this.connection.getLatestBlockhash(this.commitment);
+    const blockhash = nonce ? nonce.nonce : latest.blockhash;
+    const lastValidBlockHeight = latest.lastValidBlockHeight;
+
+    // Build versioned transaction
+    const messageV0 = new TransactionMessage({
+      payerKey: borrowerKeypair.publicKey,
+      recentBlockhash: blockhash,
+      instructions: allInstructions,
+    }).compileToV0Message(lookupTables);
+
+    const transaction = new VersionedTransaction(messageV0);
+    transaction.sign([borrowerKeypair]);
+
+    return { transaction, blockhash, lastValidBlockHeight };
+  }
 
-      // Build versioned transaction
-      const messageV0 = new TransactionMessage({
-        payerKey: borrowerKeypair.publicKey,
-        recentBlockhash: blockhash,
-        instructions: allInstructions,
-      }).compileToV0Message();
+  /**
+   * Simulate a flash loan without sending it
+   * Returns compute units consumed, program logs and post balances of watched accounts
+   */
+  async simulateFlashLoan(
+    params: FlashLoanParams,
+    watchAccounts: PublicKey[] = []
+  ): Promise<FlashLoanSimulation | null> {
+    try {
+      const built = await this.buildFlashLoanTransaction(params);
+      if (!built) {
+        return null;
+      }
 
-      const transaction = new VersionedTransaction(messageV0);
-      transaction.sign([borrowerKeypair]);
+      const { value } = await this.connection.simulateTransaction(built.transaction, {
+        sigVerify: false,
+        // A nonce transaction must keep the nonce as its blockhash to pass the nonce check
+        replaceRecentBlockhash: !params.nonce,
+        accounts: watchAccounts.length > 0
+          ? { encoding: 'base64', addresses: watchAccounts.map(a => a.toBase58()) }
+          : undefined,
+      });
 
-      // Send transaction
-      const signature = await this.connection.sendTransaction(transaction, {
-        skipPreflight: false,
-        preflightCommitment: 'confirmed',
+      const postTokenBalances = new Map<string, bigint>();
+      const postLamports = new Map<string, bigint>();
+      value.accounts?.forEach((account, i) => {
+        if (!account) return;
+        postLamports.set(watchAccounts[i].toBase58(), BigInt(account.lamports));
+        const data = Buffer.from(account.data[0], 'base64');
+        if (data.length >= TOKEN_ACCOUNT_AMOUNT_OFFSET + 8) {
+          postTokenBalances.set(watchAccounts[i].toBase58(), data.readBigUInt64LE(TOKEN_ACCOUNT_AMOUNT_OFFSET));
+        }
       });
 
-      // Confirm transaction
-      const confirmation = await this.connection.confirmTransaction({
-        signature,
-        blockhash,
+      return {
+        unitsConsumed: value.unitsConsumed || 0,
+        logs: value.logs || [],
+        error: value.err ? JSON.stringify(value.err) : undefined,
+        postTokenBalances,
+        postLamports,
+      };
+    } catch (e) {
+      console.error('[Kamino] Flash loan simulation error:', e);
+      return null;
+    }
+  }
+
+  /**
+   * Execute a flash loan with custom instructions in between
+   * Flow: Flash Borrow → Custom Instructions → Flash Repay
+   */
+  async executeFlashLoan(params: FlashLoanParams): Promise<FlashLoanResult> {
+    const { amountLamports } = params;
+
+    try {
+      const built = await this.buildFlashLoanTransaction(params);
+
+      if (!built) {
+        return { success: false, error: 'Failed to build flash loan instructions' };
+      }
+
+      const { transaction, lastValidBlockHeight } = built;
+
+      // Send, rebroadcast and poll until it lands, fails or its blockhash expires
+      const { signature, status, err, submittedAt, confirmedAt, slot } = await sendAndConfirm(this.connection, transaction, {
+        skipPreflight: this.skipPreflight,
+        commitment: this.commitment,
         lastValidBlockHeight,
-      }, 'confirmed');
+        nonce: params.nonce && { account: params.nonce.account, value: params.nonce.nonce },
+        timeoutMs: this.confirmTimeoutMs,
+        maxRebroadcasts: this.maxRetries,
+      });
 
-      if (confirmation.value.err) {
+      if (status !== 'confirmed') {
         return { 
           success: false, 
-          error: `Transaction failed: ${JSON.stringify(confirmation.value.err)}`,
+          error: status === 'failed'
+            ? `Transaction failed: ${JSON.stringify(err)}`
+            : `Transaction ${status === 'dropped' ? `dropped (${params.nonce ? 'nonce advanced' : 'blockhash expired'})` : 'not confirmed before timeout'}`,
           signature,
+          status,
+          err,
+          submittedAt,
+          confirmedAt,
+          slot,
         };
       }
 
@@ -244,32 +532,68 @@ export class KaminoFlashLoanClient {
         success: true,
         signature,
         flashFee,
+        status,
+        submittedAt,
+        confirmedAt,
+        slot,
       };
 
     } catch (e) {
       console.error('[Kamino] Flash loan execution error:', e);
-      return { success: false, error: String(e) };
+      return { success: false, error: String(e), logs: e instanceof SendTransactionError ? e.logs ?? undefined : undefined };
     }
   }
 
   /**
-   * Get available liquidity for a token in the Kamino reserve
+   * Address of the reserve a loan of the token would borrow from (the deepest across loaded markets)
+   */
+  async getFlashReserve(tokenSymbol: string): Promise<PublicKey | null> {
+    const tokenMint = tokenRegistry.get(tokenSymbol)?.mint;
+    if (!tokenMint) return null;
+    await this.refreshMarkets();
+    return this.findMarketForMint(tokenMint)?.reserve.address ?? null;
+  }
+
+  /**
+   * Deposit TVL (USD, at each reserve's market price) per loaded market, reloading reserves first
+   * Markets that fail to reload map to null
+   */
+  async getDepositTvl(): Promise<Map<string, number | null>> {
+    if (!this.initialized) {
+      await this.initialize();
+    }
+    await this.refreshMarkets();
+
+    const tvl: Map<string, number | null> = new Map();
+    await Promise.all(Array.from(this.markets.entries()).map(async ([address, market]) => {
+      try {
+        await market.loadReserves();
+        tvl.set(address, market.getTotalDepositTVL().toNumber());
+      } catch (e) {
+        console.error(`[Kamino] Error reloading market ${address}:`, e);
+        tvl.set(address, null);
+      }
+    }));
+    return tvl;
+  }
+
+  /**
+   * Available liquidity (whole tokens, e.g. dollars for USDC) in the deepest Kamino reserve for a token
    */
   async getAvailableLiquidity(tokenSymbol: string): Promise<number> {
-    if (!this.market) {
+    if (!this.initialized) {
       await this.initialize();
     }
 
-    const tokenMint = TOKEN_MINTS[tokenSymbol];
+    const tokenMint = tokenRegistry.get(tokenSymbol)?.mint;
     if (!tokenMint) return 0;
 
     try {
-      const reserve = this.market!.getReserveByMint(tokenMint);
-      if (!reserve) return 0;
-
-      // Get available liquidity from reserve stats
-      const availableLiquidity = (reserve as any).stats?.availableLiquidity || (reserve as any).liquidity?.availableAmount || 0;
-      return Number(availableLiquidity);
+      await this.refreshMarkets();
+      const found = this.findMarketForMint(tokenMint);
+      if (!found) return 0;
+      const decimals = Number(found.reserve.state.liquidity.mintDecimals.toString());
+      return Number(reserveLiquidity(found.reserve)) / Math.pow(10, decimals);
     } catch (e) {
       console.error(`[Kamino] Error getting liquidity for ${tokenSymbol}:`, e);
       return 0;
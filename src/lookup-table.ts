@@ -0,0 +1,192 @@
+/**
+ * Address Lookup Table Manager
+ * Keeps a wallet-owned lookup table with the accounts every flash loan transaction touches,
+ * so the borrow, both swaps and the repay fit in a single v0 transaction
+ */
+
+import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  Connection,
+  Keypair,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import fs from 'fs';
+import { logger } from './utils/logger.js';
+
+// Addresses per extend instruction (keeps each extend transaction under the size limit)
+const EXTEND_CHUNK_SIZE = 20;
+
+// A lookup table holds at most 256 addresses
+const MAX_TABLE_ADDRESSES = 256;
+
+interface LookupTableState {
+  address: string;
+}
+
+export class LookupTableManager {
+  private connection: Connection;
+  private keypair: Keypair;
+  private statePath: string;
+  private table: AddressLookupTableAccount | null = null;
+
+  constructor(connection: Connection, keypair: Keypair, statePath: string) {
+    this.connection = connection;
+    this.keypair = keypair;
+    this.statePath = statePath;
+  }
+
+  /**
+   * Load the table recorded in the state file
+   * A missing file, missing account or deactivated table leaves no table loaded
+   */
+  async load(): Promise<AddressLookupTableAccount | null> {
+    this.table = null;
+    const address = readState(this.statePath);
+    if (!address) {
+      return null;
+    }
+
+    const { value } = await this.connection.getAddressLookupTable(address);
+    if (!value) {
+      logger.warn(`[ALT] Lookup table ${address.toBase58()} not found`);
+      return null;
+    }
+    if (!value.isActive()) {
+      logger.warn(`[ALT] Lookup table ${address.toBase58()} is deactivated`);
+      return null;
+    }
+
+    this.table = value;
+    return value;
+  }
+
+  /**
+   * Make sure the table exists and contains every given address
+   * Creates a new table when none is usable, then extends it with whatever is missing
+   */
+  async ensure(addresses: PublicKey[]): Promise<void> {
+    if (!this.table) {
+      await this.load();
+    }
+    if (!this.table) {
+      await this.create();
+    }
+
+    const known = new Set(this.table!.state.addresses.map(a => a.toBase58()));
+    const missing = Array.from(new Set(addresses.map(a => a.toBase58())))
+      .filter(a => !known.has(a))
+      .map(a => new PublicKey(a));
+    if (missing.length === 0) {
+      return;
+    }
+
+    const room = MAX_TABLE_ADDRESSES - known.size;
+    if (missing.length > room) {
+      logger.warn(`[ALT] Table full, adding ${room} of ${missing.length} new addresses`);
+      missing.length = room;
+    }
+
+    for (let i = 0; i < missing.length; i += EXTEND_CHUNK_SIZE) {
+      const ix = AddressLookupTableProgram.extendLookupTable({
+        lookupTable: this.table!.key,
+        authority: this.keypair.publicKey,
+        payer: this.keypair.publicKey,
+        addresses: missing.slice(i, i + EXTEND_CHUNK_SIZE),
+      });
+      await this.send([ix]);
+    }
+    logger.info(`[ALT] Extended ${this.table!.key.toBase58()} with ${missing.length} addresses`);
+
+    // Re-read so the new entries are usable when compiling messages
+    await this.load();
+  }
+
+  /**
+   * The loaded table, or null before load()/ensure()
+   */
+  getTable(): AddressLookupTableAccount | null {
+    return this.table;
+  }
+
+  private async create(): Promise<void> {
+    const recentSlot = await this.connection.getSlot('finalized');
+    const [ix, address] = AddressLookupTableProgram.createLookupTable({
+      authority: this.keypair.publicKey,
+      payer: this.keypair.publicKey,
+      recentSlot,
+    });
+    await this.send([ix]);
+    writeState(this.statePath, { address: address.toBase58() });
+    logger.info(`[ALT] Created lookup table ${address.toBase58()}`);
+
+    const table = await this.load();
+    if (!table) {
+      throw new Error(`Lookup table ${address.toBase58()} not found after creation`);
+    }
+  }
+
+  private async send(instructions: TransactionInstruction[]): Promise<void> {
+    const { blockhash, lastValidBlockHeight } = await this.connection.getLatestBlockhash('confirmed');
+    const message = new TransactionMessage({
+      payerKey: this.keypair.publicKey,
+      recentBlockhash: blockhash,
+      instructions,
+    }).compileToV0Message();
+    const tx = new VersionedTransaction(message);
+    tx.sign([this.keypair]);
+
+    const signature = await this.connection.sendTransaction(tx);
+    const confirmation = await this.connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+    if (confirmation.value.err) {
+      throw new Error(`Lookup table transaction failed: ${JSON.stringify(confirmation.value.err)}`);
+    }
+  }
+}
+
+/**
+ * Table address recorded in the state file, or null if there is none
+ */
+function readState(path: string): PublicKey | null {
+  if (!fs.existsSync(path)) {
+    return null;
+  }
+  try {
+    const state: LookupTableState = JSON.parse(fs.readFileSync(path, 'utf8'));
+    return new PublicKey(state.address);
+  } catch (e) {
+    logger.warn(`[ALT] State file ${path} is invalid: ${e}`);
+    return null;
+  }
+}
+
+function writeState(path: string, state: LookupTableState): void {
+  fs.writeFileSync(`${path}.tmp`, JSON.stringify(state, null, 2));
+  fs.renameSync(`${path}.tmp`, path);
+}
+
+/**
+ * Print the table recorded in the state file (`alt show`)
+ */
+export async function showLookupTable(connection: Connection, statePath: string): Promise<void> {
+  const address = readState(statePath);
+  if (!address) {
+    logger.info(`No lookup table recorded in ${statePath}`);
+    return;
+  }
+
+  const { value } = await connection.getAddressLookupTable(address);
+  if (!value) {
+    logger.warn(`Lookup table ${address.toBase58()} not found on-chain`);
+    return;
+  }
+
+  logger.info(`Lookup table: ${address.toBase58()}`);
+  logger.info(`   Authority: ${value.state.authority?.toBase58() ?? 'frozen'}`);
+  logger.info(`   Active: ${value.isActive()}`);
+  logger.info(`   Addresses: ${value.state.addresses.length}/${MAX_TABLE_ADDRESSES}`);
+  value.state.addresses.forEach((a, i) => logger.info(`   [${i}] ${a.toBase58()}`));
+}
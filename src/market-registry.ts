@@ -0,0 +1,63 @@
+/**
+ * Kamino Market Registry
+ * Discovers lending markets on-chain so new markets are used without code changes
+ */
+
+import { Connection, PublicKey } from '@solana/web3.js';
+import { PROGRAM_ID as KAMINO_PROGRAM_ID } from '@kamino-finance/klend-sdk';
+import { logger } from './utils/logger.js';
+import { accountDiscriminator, discriminatorFilter } from './utils/memcmp.js';
+
+// Market list changes rarely; re-discover hourly by default
+export const DEFAULT_REGISTRY_TTL_MS = 60 * 60 * 1000;
+
+const LENDING_MARKET_DISCRIMINATOR = accountDiscriminator('LendingMarket');
+
+/**
+ * Cached list of Kamino lending markets
+ */
+export class MarketRegistry {
+  private connection: Connection;
+  private ttlMs: number;
+  private markets: PublicKey[] = [];
+  private fetchedAt = 0;
+
+  constructor(connection: Connection, ttlMs: number = DEFAULT_REGISTRY_TTL_MS) {
+    this.connection = connection;
+    this.ttlMs = ttlMs;
+  }
+
+  /**
+   * Whether the cached list is older than the TTL
+   */
+  isStale(): boolean {
+    return Date.now() - this.fetchedAt > this.ttlMs;
+  }
+
+  /**
+   * All LendingMarket accounts owned by the Kamino program
+   * Served from cache within the TTL; a failed refresh keeps the previous list
+   */
+  async getKaminoMarkets(): Promise<PublicKey[]> {
+    if (!this.isStale()) {
+      return this.markets;
+    }
+
+    try {
+      const accounts = await this.connection.getProgramAccounts(KAMINO_PROGRAM_ID, {
+        filters: [discriminatorFilter(LENDING_MARKET_DISCRIMINATOR)],
+        dataSlice: { offset: 0, length: 0 }, // addresses only
+      });
+      this.markets = accounts.map(a => a.pubkey);
+      this.fetchedAt = Date.now();
+      logger.info(`[Registry] Found ${this.markets.length} Kamino markets`);
+    } catch (e) {
+      if (this.markets.length === 0) {
+        throw e;
+      }
+      logger.warn(`[Registry] Market refresh failed, keeping ${this.markets.length} cached: ${e}`);
+    }
+
+    return this.markets;
+  }
+}
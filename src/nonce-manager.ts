@@ -0,0 +1,135 @@
+/**
+ * Durable Nonce Manager
+ * Leases wallet-owned nonce accounts to in-flight transactions, so a signed flash loan
+ * doesn't expire with its blockhash while it waits to land during congestion
+ */
+
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  NONCE_ACCOUNT_LENGTH,
+  NonceAccount,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import { logger } from './utils/logger.js';
+
+/**
+ * A nonce account reserved for one transaction
+ * The transaction must start with advanceIx and use nonce as its recent blockhash
+ */
+export interface NonceLease {
+  account: PublicKey;
+  nonce: string;
+  advanceIx: TransactionInstruction;
+}
+
+export class NonceManager {
+  private connection: Connection;
+  private authority: Keypair;
+  private accounts: PublicKey[];
+  // Nonce accounts currently leased to a transaction
+  private leased: Set<string> = new Set();
+
+  constructor(connection: Connection, authority: Keypair, accounts: PublicKey[]) {
+    this.connection = connection;
+    this.authority = authority;
+    this.accounts = accounts;
+  }
+
+  /**
+   * Reserve a free nonce account and read its current nonce
+   * @returns null when every account is leased or none could be read (use a normal blockhash)
+   */
+  async lease(): Promise<NonceLease | null> {
+    for (const account of this.accounts) {
+      const key = account.toBase58();
+      if (this.leased.has(key)) continue;
+      this.leased.add(key);
+
+      const nonce = await this.fetchNonce(account);
+      if (!nonce) {
+        this.leased.delete(key);
+        continue;
+      }
+
+      return {
+        account,
+        nonce,
+        advanceIx: SystemProgram.nonceAdvance({
+          noncePubkey: account,
+          authorizedPubkey: this.authority.publicKey,
+        }),
+      };
+    }
+    return null;
+  }
+
+  /**
+   * Return a nonce account to the pool once its transaction landed, failed or was abandoned
+   */
+  release(lease: NonceLease): void {
+    this.leased.delete(lease.account.toBase58());
+  }
+
+  /**
+   * Current nonce value stored in the account (null if missing or not an initialized nonce)
+   */
+  async fetchNonce(account: PublicKey): Promise<string | null> {
+    try {
+      const info = await this.connection.getAccountInfo(account, 'confirmed');
+      if (!info || !info.owner.equals(SystemProgram.programId) || info.data.length !== NONCE_ACCOUNT_LENGTH) {
+        logger.warn(`[Nonce] ${account.toBase58()} is not a nonce account`);
+        return null;
+      }
+      const nonceAccount = NonceAccount.fromAccountData(info.data);
+      if (!nonceAccount.authorizedPubkey.equals(this.authority.publicKey)) {
+        logger.warn(`[Nonce] ${account.toBase58()} is not owned by the wallet`);
+        return null;
+      }
+      return nonceAccount.nonce;
+    } catch (e) {
+      logger.warn(`[Nonce] Failed to read ${account.toBase58()}: ${e}`);
+      return null;
+    }
+  }
+}
+
+/**
+ * Create and fund nonce accounts with the wallet as authority (`nonce create <count>` command)
+ * @returns Addresses of the created accounts, for NONCE_ACCOUNTS
+ */
+export async function createNonceAccounts(connection: Connection, payer: Keypair, count: number): Promise<PublicKey[]> {
+  const rent = await connection.getMinimumBalanceForRentExemption(NONCE_ACCOUNT_LENGTH);
+  logger.info(`[Nonce] Creating ${count} nonce account(s) at ${(rent / LAMPORTS_PER_SOL).toFixed(6)} SOL rent each`);
+
+  const created: PublicKey[] = [];
+  for (let i = 0; i < count; i++) {
+    const nonceKeypair = Keypair.generate();
+    const instructions = SystemProgram.createNonceAccount({
+      fromPubkey: payer.publicKey,
+      noncePubkey: nonceKeypair.publicKey,
+      authorizedPubkey: payer.publicKey,
+      lamports: rent,
+    }).instructions;
+
+    const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash('confirmed');
+    const message = new TransactionMessage({ payerKey: payer.publicKey, recentBlockhash: blockhash, instructions }).compileToV0Message();
+    const tx = new VersionedTransaction(message);
+    tx.sign([payer, nonceKeypair]);
+
+    const signature = await connection.sendTransaction(tx);
+    const confirmation = await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+    if (confirmation.value.err) {
+      throw new Error(`Creating nonce account failed: ${JSON.stringify(confirmation.value.err)}`);
+    }
+
+    logger.success(`[Nonce] Created ${nonceKeypair.publicKey.toBase58()}: ${signature}`);
+    created.push(nonceKeypair.publicKey);
+  }
+  return created;
+}
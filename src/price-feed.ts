@@ -0,0 +1,196 @@
+/**
+ * WebSocket Price Feed
+ * Subscribes to Orca whirlpool accounts so pool prices update on every change
+ * instead of being fetched on each scan
+ */
+
+import { AccountInfo, Connection, PublicKey } from '@solana/web3.js';
+import { logger } from './utils/logger.js';
+import { OrcaClient } from './dex-integrations/orca.js';
+
+// No slot notification for this long means the WebSocket is dead
+const HEARTBEAT_TIMEOUT_MS = 15_000;
+
+// Reconnect backoff bounds
+const RECONNECT_BASE_DELAY_MS = 1000;
+const RECONNECT_MAX_DELAY_MS = 60_000;
+
+export interface FeedPrice {
+  price: number;
+  liquidity: number;
+  slot: number;
+  updatedAt: number;
+}
+
+/**
+ * Streams pool prices for the tracked pairs
+ * Prices are kept in a map shared with the Scanner
+ */
+export class PriceFeed {
+  private connection: Connection;
+  private orcaClient: OrcaClient;
+  private pairs: string[];
+  private prices: Map<string, FeedPrice> = new Map();
+  private subscriptionIds: number[] = [];
+  private lastHeartbeat = 0;
+  private heartbeatTimer: NodeJS.Timeout | null = null;
+  private reconnectAttempts = 0;
+  private reconnects = 0;
+  private reconnecting = false;
+  private running = false;
+  // Price move (bps) from the last reference price that fires the move listener
+  private moveThresholdBps = 0;
+  private moveListener: ((pair: string) => void) | null = null;
+  private referencePrices: Map<string, number> = new Map();
+
+  constructor(connection: Connection, orcaClient: OrcaClient, pairs: string[]) {
+    this.connection = connection;
+    this.orcaClient = orcaClient;
+    this.pairs = pairs;
+  }
+
+  /**
+   * Subscribe to all tracked pools and start the heartbeat watchdog
+   */
+  async start(): Promise<void> {
+    if (this.running) return;
+    this.running = true;
+
+    await this.subscribe();
+    this.heartbeatTimer = setInterval(() => this.checkHeartbeat(), HEARTBEAT_TIMEOUT_MS / 3);
+  }
+
+  /**
+   * Remove all subscriptions
+   */
+  async stop(): Promise<void> {
+    this.running = false;
+    if (this.heartbeatTimer) {
+      clearInterval(this.heartbeatTimer);
+      this.heartbeatTimer = null;
+    }
+    await this.unsubscribe();
+  }
+
+  /**
+   * Latest streamed price for a pair
+   * Quiet pools keep their last price; null when the socket itself is down
+   */
+  getPrice(pair: string): FeedPrice | null {
+    if (!this.isHealthy()) {
+      return null;
+    }
+    return this.prices.get(pair) ?? null;
+  }
+
+  /**
+   * Call the listener whenever a pair's pool price moves thresholdBps from the price at its last call
+   */
+  onPriceMove(thresholdBps: number, listener: (pair: string) => void): void {
+    this.moveThresholdBps = thresholdBps;
+    this.moveListener = listener;
+  }
+
+  isHealthy(): boolean {
+    return this.running && Date.now() - this.lastHeartbeat < HEARTBEAT_TIMEOUT_MS;
+  }
+
+  getStats(): { subscriptions: number; reconnects: number } {
+    return {
+      subscriptions: this.subscriptionIds.length,
+      reconnects: this.reconnects,
+    };
+  }
+
+  private async subscribe(): Promise<void> {
+    this.lastHeartbeat = Date.now();
+
+    // Slot updates double as a liveness signal for the socket
+    this.subscriptionIds.push(
+      this.connection.onSlotChange(() => {
+        this.lastHeartbeat = Date.now();
+        this.reconnectAttempts = 0;
+      })
+    );
+
+    for (const pair of this.pairs) {
+      const address = await this.orcaClient.getWhirlpoolAddress(pair);
+      if (!address) {
+        logger.warn(`[PriceFeed] No Orca pool for ${pair}, falling back to polling`);
+        continue;
+      }
+      this.subscriptionIds.push(
+        this.connection.onAccountChange(
+          address,
+          (accountInfo, context) => this.onPoolUpdate(pair, address, accountInfo, context.slot),
+          'processed'
+        )
+      );
+    }
+
+    logger.info(`[PriceFeed] Subscribed to ${this.subscriptionIds.length - 1} pools`);
+  }
+
+  private async unsubscribe(): Promise<void> {
+    const [slotId, ...accountIds] = this.subscriptionIds;
+    this.subscriptionIds = [];
+    await Promise.allSettled([
+      ...(slotId !== undefined ? [this.connection.removeSlotChangeListener(slotId)] : []),
+      ...accountIds.map(id => this.connection.removeAccountChangeListener(id)),
+    ]);
+  }
+
+  private onPoolUpdate(pair: string, address: PublicKey, accountInfo: AccountInfo<Buffer>, slot: number): void {
+    this.lastHeartbeat = Date.now();
+    try {
+      const parsed = this.orcaClient.priceFromAccount(pair, address, accountInfo);
+      if (parsed) {
+        this.prices.set(pair, { ...parsed, slot, updatedAt: Date.now() });
+        this.checkPriceMove(pair, parsed.price);
+      }
+    } catch (e) {
+      logger.debug(`[PriceFeed] Failed to parse ${pair} pool update: ${e}`);
+    }
+  }
+
+  private checkPriceMove(pair: string, price: number): void {
+    if (!this.moveListener) return;
+    const reference = this.referencePrices.get(pair);
+    if (reference === undefined) {
+      this.referencePrices.set(pair, price);
+      return;
+    }
+    if ((Math.abs(price - reference) / reference) * 10_000 >= this.moveThresholdBps) {
+      this.referencePrices.set(pair, price);
+      this.moveListener(pair);
+    }
+  }
+
+  /**
+   * Re-subscribe with exponential backoff when the socket goes quiet
+   * The attempt counter only resets once slot notifications resume
+   */
+  private async checkHeartbeat(): Promise<void> {
+    if (!this.running || this.reconnecting || this.isHealthy()) {
+      return;
+    }
+
+    const delay = Math.min(RECONNECT_BASE_DELAY_MS * Math.pow(2, this.reconnectAttempts), RECONNECT_MAX_DELAY_MS);
+    this.reconnectAttempts++;
+    this.reconnecting = true;
+    logger.warn(`[PriceFeed] WebSocket silent, reconnecting in ${delay}ms (attempt ${this.reconnectAttempts})`);
+
+    try {
+      await new Promise(r => setTimeout(r, delay));
+      if (!this.running) return;
+
+      await this.unsubscribe();
+      await this.subscribe();
+      this.reconnects++;
+    } catch (e) {
+      logger.error(`[PriceFeed] Reconnect failed: ${e}`);
+    } finally {
+      this.reconnecting = false;
+    }
+  }
+}
@@ -0,0 +1,208 @@
+/**
+ * Priority Fee Resolver
+ * Picks the compute unit price for arbitrage transactions:
+ * a fixed value, a percentile of recent fees paid on the accounts we write to,
+ * Raydium's recommended fee, or a low/medium/high estimate chosen by expected profit
+ */
+
+import { Connection, PublicKey } from '@solana/web3.js';
+import { logger } from './utils/logger.js';
+import { fetchJson } from './utils/http.js';
+import { fetchRaydiumPriorityFee } from './dex-integrations/raydium.js';
+import { PriorityLevel } from './dex-integrations/swap-options.js';
+
+// Resolved fees are reused for this long
+const PRIORITY_FEE_CACHE_MS = 3000;
+
+// getRecentPrioritizationFees accepts at most 128 accounts
+const MAX_FEE_ACCOUNTS = 128;
+
+// Percentiles of recent fees standing in for low/medium/high without Helius
+const LEVEL_PERCENTILES = { low: 25, medium: 50, high: 75 };
+
+// Expected profit (USD) at which the estimate strategy bids medium / high
+const MEDIUM_FEE_PROFIT_USD = 20;
+const HIGH_FEE_PROFIT_USD = 100;
+
+export type PriorityFeeStrategy =
+  | { type: 'static'; microLamports: number }
+  | { type: 'percentile'; percentile: number }
+  | { type: 'auto'; level: PriorityLevel }
+  | { type: 'estimate' };
+
+export interface FeeLevels {
+  low: number;
+  medium: number;
+  high: number;
+}
+
+/**
+ * Parse PRIORITY_FEE_STRATEGY: "static", "percentile:<0-100>", "auto" or "estimate"
+ * @param staticFee Fee used by the static strategy
+ * @param level Raydium fee tier used by the auto strategy
+ */
+export function parsePriorityFeeStrategy(raw: string, staticFee: number, level: PriorityLevel): PriorityFeeStrategy {
+  const [type, arg] = raw.split(':');
+  switch (type) {
+    case 'static':
+      return { type: 'static', microLamports: staticFee };
+    case 'percentile': {
+      const percentile = arg === undefined ? 75 : Number(arg);
+      if (!Number.isInteger(percentile) || percentile < 0 || percentile > 100) {
+        throw new Error(`Invalid priority fee percentile: ${arg} (must be 0-100)`);
+      }
+      return { type: 'percentile', percentile };
+    }
+    case 'auto':
+      return { type: 'auto', level };
+    case 'estimate':
+      return { type: 'estimate' };
+    default:
+      throw new Error(`Invalid PRIORITY_FEE_STRATEGY: ${raw} (expected static, percentile:<n>, auto or estimate)`);
+  }
+}
+
+/**
+ * Helius RPC endpoints support the enhanced getPriorityFeeEstimate method
+ */
+export function isHeliusRpc(url: string): boolean {
+  try {
+    return new URL(url).hostname.endsWith('helius-rpc.com');
+  } catch {
+    return false;
+  }
+}
+
+export class PriorityFeeResolver {
+  private connection: Connection;
+  private strategy: PriorityFeeStrategy;
+  // Fallback when a dynamic strategy can't fetch fees
+  private fallbackMicroLamports: number;
+  private maxMicroLamports: number;
+  // Helius RPC URL for getPriorityFeeEstimate (null = standard RPC method)
+  private heliusRpcUrl: string | null;
+  private cache: Map<string, { fee: number | FeeLevels | null; resolvedAt: number }> = new Map();
+
+  constructor(
+    connection: Connection,
+    strategy: PriorityFeeStrategy,
+    fallbackMicroLamports: number,
+    maxMicroLamports: number,
+    heliusRpcUrl: string | null = null
+  ) {
+    this.connection = connection;
+    this.strategy = strategy;
+    this.fallbackMicroLamports = fallbackMicroLamports;
+    this.maxMicroLamports = maxMicroLamports;
+    this.heliusRpcUrl = heliusRpcUrl;
+  }
+
+  /**
+   * Compute unit price (micro-lamports) for a transaction writing to the given accounts
+   * Capped at maxMicroLamports; fetched fees are cached for PRIORITY_FEE_CACHE_MS per account set
+   * @param expectedProfitUsd Picks the level for the estimate strategy (bigger profit, higher bid)
+   */
+  async resolve(writableAccounts: PublicKey[], expectedProfitUsd = 0): Promise<number> {
+    const strategy = this.strategy;
+    if (strategy.type === 'static') {
+      return strategy.microLamports;
+    }
+
+    const accounts = writableAccounts.slice(0, MAX_FEE_ACCOUNTS);
+    const key = strategy.type === 'auto' ? 'auto' : accounts.map(a => a.toBase58()).sort().join(',');
+    let cached = this.cache.get(key);
+    if (!cached || Date.now() - cached.resolvedAt >= PRIORITY_FEE_CACHE_MS) {
+      const fee = strategy.type === 'percentile' ? await this.recentFeePercentile(accounts, strategy.percentile)
+        : strategy.type === 'auto' ? await fetchRaydiumPriorityFee(strategy.level)
+        : await this.estimateLevels(accounts);
+      cached = { fee, resolvedAt: Date.now() };
+      this.cache.set(key, cached);
+    }
+
+    let fee = cached.fee;
+    if (fee !== null && typeof fee === 'object') {
+      fee = expectedProfitUsd >= HIGH_FEE_PROFIT_USD ? fee.high
+        : expectedProfitUsd >= MEDIUM_FEE_PROFIT_USD ? fee.medium
+        : fee.low;
+    }
+    return Math.min(fee ?? this.fallbackMicroLamports, this.maxMicroLamports);
+  }
+
+  /**
+   * Low/medium/high fee levels for these accounts, from Helius when available
+   */
+  private async estimateLevels(accounts: PublicKey[]): Promise<FeeLevels | null> {
+    if (this.heliusRpcUrl) {
+      const levels = await this.heliusEstimate(accounts);
+      if (levels) {
+        return levels;
+      }
+    }
+
+    const fees = await this.recentFees(accounts);
+    return fees && {
+      low: percentileOf(fees, LEVEL_PERCENTILES.low),
+      medium: percentileOf(fees, LEVEL_PERCENTILES.medium),
+      high: percentileOf(fees, LEVEL_PERCENTILES.high),
+    };
+  }
+
+  private async heliusEstimate(accounts: PublicKey[]): Promise<FeeLevels | null> {
+    try {
+      const data = await fetchJson(this.heliusRpcUrl!, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({
+          jsonrpc: '2.0',
+          id: 1,
+          method: 'getPriorityFeeEstimate',
+          params: [{
+            accountKeys: accounts.map(a => a.toBase58()),
+            options: { includeAllPriorityFeeLevels: true },
+          }],
+        }),
+      }, { maxRetries: 0 });
+
+      const levels = data.result?.priorityFeeLevels;
+      if (!levels) {
+        return null;
+      }
+      return {
+        low: Math.ceil(levels.low ?? 0),
+        medium: Math.ceil(levels.medium ?? 0),
+        high: Math.ceil(levels.high ?? 0),
+      };
+    } catch (e) {
+      logger.warn(`[PriorityFee] Helius getPriorityFeeEstimate failed: ${e}`);
+      return null;
+    }
+  }
+
+  /**
+   * Nth percentile of the fees paid in recent slots by transactions locking these accounts
+   */
+  private async recentFeePercentile(accounts: PublicKey[], percentile: number): Promise<number | null> {
+    const fees = await this.recentFees(accounts);
+    return fees && percentileOf(fees, percentile);
+  }
+
+  /**
+   * Recent per-slot prioritization fees, sorted ascending (null if unavailable)
+   */
+  private async recentFees(accounts: PublicKey[]): Promise<number[] | null> {
+    try {
+      const fees = await this.connection.getRecentPrioritizationFees({ lockedWritableAccounts: accounts });
+      if (fees.length === 0) {
+        return null;
+      }
+      return fees.map(f => f.prioritizationFee).sort((a, b) => a - b);
+    } catch (e) {
+      logger.warn(`[PriorityFee] getRecentPrioritizationFees failed: ${e}`);
+      return null;
+    }
+  }
+}
+
+function percentileOf(sorted: number[], percentile: number): number {
+  return sorted[Math.min(sorted.length - 1, Math.floor((percentile / 100) * sorted.length))];
+}
@@ -10,9 +10,16 @@ export const DEX_FEES: Record<string, number> = {
 };
 
 /**
- * Kamino flash loan fee
+ * Kamino flash loan fee (0.001% until the reserve's configured fee is known)
  */
-export const FLASH_LOAN_FEE = 0.00001; // 0.001%
+export let FLASH_LOAN_FEE = 0.00001;
+
+/**
+ * Use the flash loan fee read from the reserve (net of any referrer share) in profit estimates
+ */
+export function setFlashLoanFee(rate: number): void {
+  FLASH_LOAN_FEE = rate;
+}
 
 /**
  * Jito tip configuration
@@ -79,11 +86,26 @@ export interface ProfitCalculation {
   buyFee: number;
   sellFee: number;
   slippageCost: number;
+  // Fee of the middle leg (triangular routes only)
+  crossFee?: number;
   netProfit: number;
   profitPercent: number;
   isProfitable: boolean;
 }
 
+/**
+ * Middle leg of a triangular route: the bought base token is swapped into another token,
+ * which the sell leg then sells for the quote token
+ */
+export interface CrossLeg {
+  dex: string;
+  // Token received (e.g. JUP for SOL → JUP)
+  token: string;
+  // Tokens received per base token
+  rate: number;
+  liquidity: number;
+}
+
 export interface ArbitrageOpportunity {
   pair: string;
   buyDex: string;
@@ -97,6 +119,83 @@ export interface ArbitrageOpportunity {
   buyLiquidity: number;
   sellLiquidity: number;
   timestamp: number;
+  // Triangular route (quote → base → crossLeg.token → quote); sellPrice is then the quote
+  // tokens per base token the last two legs return
+  crossLeg?: CrossLeg;
+}
+
+/**
+ * Identity of an opportunity's route, e.g. "SOL/USDC:raydium:orca" or "SOL/USDC:raydium:orca>JUP:raydium"
+ */
+export function routeKey(opp: ArbitrageOpportunity): string {
+  const cross = opp.crossLeg ? `${opp.crossLeg.dex}>${opp.crossLeg.token}:` : '';
+  return `${opp.pair}:${opp.buyDex}:${cross}${opp.sellDex}`;
+}
+
+/**
+ * Route for logs and notifications, e.g. "raydium → orca" or "raydium → orca (JUP) → raydium"
+ */
+export function describeRoute(opp: ArbitrageOpportunity): string {
+  const cross = opp.crossLeg ? `${opp.crossLeg.dex} (${opp.crossLeg.token}) → ` : '';
+  return `${opp.buyDex} → ${cross}${opp.sellDex}`;
+}
+
+/**
+ * Check an opportunity for inconsistent fields
+ * @returns Reason the opportunity is invalid, or null if it's consistent
+ */
+export function validateOpportunity(opp: ArbitrageOpportunity): string | null {
+  const [base, quote] = opp.pair.split('/');
+  if (!base || !quote || base === quote) {
+    return `invalid pair ${opp.pair}`;
+  }
+  if (opp.crossLeg) {
+    // Triangular legs use three different pools, so DEXes may repeat
+    if (opp.crossLeg.token === base || opp.crossLeg.token === quote) {
+      return `cross leg token ${opp.crossLeg.token} already in ${opp.pair}`;
+    }
+    if (!(opp.crossLeg.rate > 0)) {
+      return `invalid cross leg rate ${opp.crossLeg.rate}`;
+    }
+  } else if (opp.buyDex === opp.sellDex) {
+    return `buy and sell on the same DEX (${opp.buyDex})`;
+  }
+  if (!(opp.buyPrice > 0) || !(opp.sellPrice > opp.buyPrice)) {
+    return `sell price ${opp.sellPrice} not above buy price ${opp.buyPrice}`;
+  }
+  if (!isFinite(opp.flashAmount) || opp.flashAmount <= 0) {
+    return `invalid flash amount ${opp.flashAmount}`;
+  }
+  if (!isFinite(opp.calculation.netProfit) || opp.calculation.netProfit <= 0) {
+    return `non-positive net profit ${opp.calculation.netProfit}`;
+  }
+  return null;
+}
+
+/**
+ * Flash loan fee plus every swap fee, as a fraction of the traded amount
+ * @param crossDex DEX of the middle leg, for triangular routes
+ */
+export function routeFeesPercent(buyDex: string, sellDex: string, crossDex?: string): number {
+  const crossFee = crossDex ? DEX_FEES[crossDex] || 0.0025 : 0;
+  return FLASH_LOAN_FEE + (DEX_FEES[buyDex] || 0.0025) + crossFee + (DEX_FEES[sellDex] || 0.0025);
+}
+
+/**
+ * Smallest flash amount (USD) whose spread, net of percentage fees, covers the transaction's gas:
+ * amount × (spread − fees) > gas
+ * @returns Infinity when fees eat the whole spread (no size is profitable)
+ */
+export function computeMinProfitableAmount(
+  spreadPercent: number,
+  totalFeesPercent: number,
+  gasLamports: number,
+  solPriceUsd: number
+): number {
+  const edge = spreadPercent - totalFeesPercent;
+  if (edge <= 0) return Infinity;
+  const gasUsd = (gasLamports / 1_000_000_000) * solPriceUsd;
+  return gasUsd / edge;
 }
 
 /**
@@ -173,7 +272,12 @@ export function findBestOpportunity(
   pair: string,
   prices: Map<string, number>,
   liquidities: Map<string, number>,
-  calculateOptimalAmount: (pair: string, minLiquidity: number, spreadPercent: number) => number
+  calculateOptimalAmount: (
+    pair: string,
+    minLiquidity: number,
+    spreadPercent: number,
+    totalFeesPercent: number
+  ) => number
 ): ArbitrageOpportunity | null {
   const dexes = Array.from(prices.keys());
   let bestOpportunity: ArbitrageOpportunity | null = null;
@@ -203,7 +307,9 @@ export function findBestOpportunity(
       const minLiquidity = Math.min(buyLiquidity, sellLiquidity);
       
       // Calculate optimal flash amount
-      const flashAmount = calculateOptimalAmount(pair, minLiquidity, spreadPercent);
+      const totalFeesPercent = routeFeesPercent(buyDex, sellDex);
+      const flashAmount = calculateOptimalAmount(pair, minLiquidity, spreadPercent, totalFeesPercent);
+      if (flashAmount <= 0) continue;
       
       // Estimate slippage based on amount vs liquidity
       const slippageEstimate = estimateSlippage(flashAmount, minLiquidity);
@@ -241,6 +347,136 @@ export function findBestOpportunity(
   return bestOpportunity;
 }
 
+/**
+ * Pool prices (quote per base) and liquidity per DEX
+ */
+export type DexQuotes = ReadonlyMap<string, { price: number; liquidity: number }>;
+
+/**
+ * Profit of a triangular route: calculateProfit on the effective sell price, minus the middle leg's fee
+ * on the value passing through it
+ */
+export function calculateTriangularProfit(
+  flashAmountUsd: number,
+  buyPrice: number,
+  effectiveSellPrice: number,
+  buyDex: string,
+  crossDex: string,
+  sellDex: string,
+  estimatedSlippage: number = 0.001
+): ProfitCalculation {
+  const calculation = calculateProfit(flashAmountUsd, buyPrice, effectiveSellPrice, buyDex, sellDex, estimatedSlippage);
+  const crossFee = (flashAmountUsd / buyPrice) * effectiveSellPrice * (DEX_FEES[crossDex] || 0.0025);
+  const netProfit = calculation.netProfit - crossFee;
+  return {
+    ...calculation,
+    crossFee,
+    netProfit,
+    profitPercent: netProfit / flashAmountUsd,
+    isProfitable: netProfit > 0,
+  };
+}
+
+/**
+ * Best triangular route quote → base → crossToken → quote over every buy, cross and sell DEX
+ * (brute force over DEX triples; each leg is a different pool even when a DEX repeats)
+ * @param buyQuotes Base token prices (quote per base)
+ * @param crossQuotes Cross rates (crossToken per base)
+ * @param sellQuotes Cross token prices (quote per crossToken)
+ */
+export function findBestTriangularOpportunity(
+  pair: string,
+  crossToken: string,
+  buyQuotes: DexQuotes,
+  crossQuotes: DexQuotes,
+  sellQuotes: DexQuotes,
+  calculateOptimalAmount: (
+    pair: string,
+    minLiquidity: number,
+    spreadPercent: number,
+    totalFeesPercent: number
+  ) => number
+): ArbitrageOpportunity | null {
+  let bestOpportunity: ArbitrageOpportunity | null = null;
+  let bestProfit = 0;
+
+  for (const [buyDex, buy] of buyQuotes) {
+    for (const [crossDex, cross] of crossQuotes) {
+      for (const [sellDex, sell] of sellQuotes) {
+        const sellPrice = cross.price * sell.price;
+        if (!(buy.price > 0) || buy.price >= sellPrice) continue;
+
+        const { spread, spreadPercent } = calculateSpread(buy.price, sellPrice);
+        const totalFeesPercent = routeFeesPercent(buyDex, sellDex, crossDex);
+        if (spreadPercent <= totalFeesPercent) continue;
+
+        const minLiquidity = Math.min(buy.liquidity, cross.liquidity, sell.liquidity);
+        const flashAmount = calculateOptimalAmount(pair, minLiquidity, spreadPercent, totalFeesPercent);
+        if (flashAmount <= 0) continue;
+
+        const calculation = calculateTriangularProfit(
+          flashAmount,
+          buy.price,
+          sellPrice,
+          buyDex,
+          crossDex,
+          sellDex,
+          estimateSlippage(flashAmount, minLiquidity)
+        );
+
+        if (calculation.netProfit > bestProfit) {
+          bestProfit = calculation.netProfit;
+          bestOpportunity = {
+            pair,
+            buyDex,
+            sellDex,
+            buyPrice: buy.price,
+            sellPrice,
+            spread,
+            spreadPercent,
+            flashAmount,
+            calculation,
+            buyLiquidity: buy.liquidity,
+            sellLiquidity: sell.liquidity,
+            timestamp: Date.now(),
+            crossLeg: { dex: crossDex, token: crossToken, rate: cross.price, liquidity: cross.liquidity },
+          };
+        }
+      }
+    }
+  }
+
+  return bestOpportunity;
+}
+
+/**
+ * Rough chance (0-1) that an opportunity still fills when executed
+ * Falls as its quotes age toward maxAgeMs and as the loan takes a bigger share of the shallower pool
+ * (a large share means any competing trade or price tick moves the pool past our minimum out)
+ */
+export function executionProbability(opp: ArbitrageOpportunity, now: number, maxAgeMs: number): number {
+  const freshness = Math.max(0, 1 - (now - opp.timestamp) / maxAgeMs);
+  const minLiquidity = Math.min(opp.buyLiquidity, opp.sellLiquidity, opp.crossLeg?.liquidity ?? Infinity);
+  const depth = minLiquidity > 0 ? Math.max(0, 1 - opp.flashAmount / minLiquidity) : 0.5;
+  return freshness * depth;
+}
+
+/**
+ * Queue priority: profit^profitWeight × probability^probabilityWeight
+ * Weights of 1 and 0 rank by profit alone
+ */
+export function opportunityScore(
+  opp: ArbitrageOpportunity,
+  now: number,
+  maxAgeMs: number,
+  profitWeight: number,
+  probabilityWeight: number
+): number {
+  const profit = Math.max(opp.calculation.netProfit, 0);
+  if (probabilityWeight === 0) return Math.pow(profit, profitWeight);
+  return Math.pow(profit, profitWeight) * Math.pow(executionProbability(opp, now, maxAgeMs), probabilityWeight);
+}
+
 /**
  * Estimate slippage based on trade size vs pool liquidity
  */
@@ -0,0 +1,112 @@
+/**
+ * Profit Tracker
+ * Measures realized profit as the change in everything the wallet holds:
+ * native SOL plus every token account under the classic and Token-2022 programs
+ */
+
+import { Connection, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { WSOL_MINT } from './utils/wsol.js';
+
+/**
+ * Wallet holdings at one point in time, token amounts summed per mint
+ */
+export interface BalanceSnapshot {
+  lamports: bigint;
+  tokens: Map<string, { amount: bigint; decimals: number }>;
+}
+
+/**
+ * Change in one asset (wSOL is folded into native SOL)
+ */
+export interface TokenDelta {
+  mint: string;
+  amount: number;
+  // null when no price was given for the mint
+  usd: number | null;
+}
+
+export interface ProfitReport {
+  deltas: TokenDelta[];
+  netUsd: number;
+  netLamports: number;
+}
+
+export class ProfitTracker {
+  private connection: Connection;
+  private owner: PublicKey;
+
+  constructor(connection: Connection, owner: PublicKey) {
+    this.connection = connection;
+    this.owner = owner;
+  }
+
+  /**
+   * Fetch SOL and all token balances
+   */
+  async snapshot(): Promise<BalanceSnapshot> {
+    const [lamports, ...programAccounts] = await Promise.all([
+      this.connection.getBalance(this.owner, 'confirmed'),
+      ...[TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID].map(programId =>
+        this.connection.getParsedTokenAccountsByOwner(this.owner, { programId }, 'confirmed')
+      ),
+    ]);
+
+    const tokens = new Map<string, { amount: bigint; decimals: number }>();
+    for (const { value } of programAccounts) {
+      for (const { account } of value) {
+        const info = account.data.parsed.info;
+        const held = tokens.get(info.mint);
+        tokens.set(info.mint, {
+          amount: (held?.amount ?? 0n) + BigInt(info.tokenAmount.amount),
+          decimals: info.tokenAmount.decimals,
+        });
+      }
+    }
+    return { lamports: BigInt(lamports), tokens };
+  }
+
+  /**
+   * Value the change between two snapshots
+   * Mints only present in one snapshot (ATAs created or closed by the trade) count from zero
+   * @param pricesUsd USD price per mint; SOL is valued at solPriceUsd
+   */
+  diff(
+    before: BalanceSnapshot,
+    after: BalanceSnapshot,
+    pricesUsd: Map<string, number>,
+    solPriceUsd: number
+  ): ProfitReport {
+    const wsol = WSOL_MINT.toBase58();
+    const deltas: TokenDelta[] = [];
+
+    // Native SOL and wSOL are one asset; fees and tips come out of lamports
+    const solDelta = Number(after.lamports - before.lamports + tokenAmount(after, wsol) - tokenAmount(before, wsol));
+    if (solDelta !== 0) {
+      deltas.push({ mint: wsol, amount: solDelta / LAMPORTS_PER_SOL, usd: (solDelta / LAMPORTS_PER_SOL) * solPriceUsd });
+    }
+
+    const mints = new Set([...before.tokens.keys(), ...after.tokens.keys()]);
+    mints.delete(wsol);
+    for (const mint of mints) {
+      const raw = tokenAmount(after, mint) - tokenAmount(before, mint);
+      if (raw === 0n) continue;
+
+      const decimals = (after.tokens.get(mint) ?? before.tokens.get(mint))!.decimals;
+      const amount = Number(raw) / Math.pow(10, decimals);
+      const price = pricesUsd.get(mint);
+      deltas.push({ mint, amount, usd: price !== undefined ? amount * price : null });
+    }
+
+    const netUsd = deltas.reduce((sum, d) => sum + (d.usd ?? 0), 0);
+    return {
+      deltas,
+      netUsd,
+      netLamports: solPriceUsd > 0 ? Math.round((netUsd / solPriceUsd) * LAMPORTS_PER_SOL) : 0,
+    };
+  }
+}
+
+function tokenAmount(snapshot: BalanceSnapshot, mint: string): bigint {
+  return snapshot.tokens.get(mint)?.amount ?? 0n;
+}
@@ -0,0 +1,73 @@
+/**
+ * Program ID Check
+ * Verifies at startup that the hardcoded program and account addresses the bot trades through
+ * still point at live programs and accounts of the expected type
+ */
+
+import { Connection, PublicKey } from '@solana/web3.js';
+import { PROGRAM_ID as KAMINO_PROGRAM_ID } from '@kamino-finance/klend-sdk';
+import { ORCA_WHIRLPOOL_PROGRAM_ID } from '@orca-so/whirlpools-sdk';
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { logger } from './utils/logger.js';
+import { accountDiscriminator, DISCRIMINATOR_LENGTH } from './utils/memcmp.js';
+import { WHIRLPOOLS_CONFIG } from './dex-integrations/orca.js';
+
+const PROGRAMS: { name: string; id: PublicKey }[] = [
+  { name: 'Kamino Lending', id: KAMINO_PROGRAM_ID },
+  { name: 'Orca Whirlpool', id: ORCA_WHIRLPOOL_PROGRAM_ID },
+  { name: 'SPL Token', id: TOKEN_PROGRAM_ID },
+  { name: 'Token-2022', id: TOKEN_2022_PROGRAM_ID },
+  { name: 'Associated Token', id: ASSOCIATED_TOKEN_PROGRAM_ID },
+];
+
+/**
+ * Anchor accounts the bot depends on: expected owner and account type
+ */
+function expectedAccounts(kaminoMarkets: PublicKey[]): { name: string; address: PublicKey; owner: PublicKey; type: string }[] {
+  return [
+    ...kaminoMarkets.map(address => ({
+      name: `Kamino market ${address.toBase58().substring(0, 8)}`,
+      address,
+      owner: KAMINO_PROGRAM_ID,
+      type: 'LendingMarket',
+    })),
+    { name: 'Orca config', address: WHIRLPOOLS_CONFIG, owner: ORCA_WHIRLPOOL_PROGRAM_ID, type: 'WhirlpoolsConfig' },
+  ];
+}
+
+/**
+ * Check every program is executable and every account has the expected owner and discriminator
+ * @returns Problems found (empty when everything checks out); each is also logged as an error
+ */
+export async function verifyProgramIds(connection: Connection, kaminoMarkets: PublicKey[]): Promise<string[]> {
+  const accounts = expectedAccounts(kaminoMarkets);
+  const infos = await connection.getMultipleAccountsInfo([
+    ...PROGRAMS.map(p => p.id),
+    ...accounts.map(a => a.address),
+  ]);
+
+  const problems: string[] = [];
+  PROGRAMS.forEach((program, i) => {
+    const info = infos[i];
+    if (!info) {
+      problems.push(`${program.name} program ${program.id.toBase58()} not found`);
+    } else if (!info.executable) {
+      problems.push(`${program.name} program ${program.id.toBase58()} is not executable`);
+    }
+  });
+  accounts.forEach((account, i) => {
+    const info = infos[PROGRAMS.length + i];
+    if (!info) {
+      problems.push(`${account.name} ${account.address.toBase58()} not found`);
+    } else if (!info.owner.equals(account.owner)) {
+      problems.push(`${account.name} is owned by ${info.owner.toBase58()}, expected ${account.owner.toBase58()}`);
+    } else if (!info.data.subarray(0, DISCRIMINATOR_LENGTH).equals(accountDiscriminator(account.type))) {
+      problems.push(`${account.name} is not a ${account.type} account`);
+    }
+  });
+
+  for (const problem of problems) {
+    logger.error(`[Programs] ${problem} (program upgraded, moved or compromised?)`);
+  }
+  return problems;
+}
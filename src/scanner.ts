@@ -1,15 +1,31 @@
 /**
  * Multi-DEX Price Scanner
  * Uses native DEX SDKs for accurate price fetching
- * Supports: Raydium, Orca
+ * Supports: Raydium, Orca (any PriceSource can be plugged in)
+ * Finds two-leg cross-DEX routes per pair and, optionally, triangular routes across two pairs
  */
 
-import { Connection } from '@solana/web3.js';
+import { Connection, PublicKey } from '@solana/web3.js';
 import { logger } from './utils/logger.js';
-import { findBestOpportunity, ArbitrageOpportunity } from './profit-calculator.js';
-import { calculateOptimalAmount } from './dynamic-sizer.js';
+import { withSpan } from './utils/tracing.js';
+import {
+  findBestOpportunity,
+  findBestTriangularOpportunity,
+  validateOpportunity,
+  computeMinProfitableAmount,
+  routeFeesPercent,
+  describeRoute,
+  ArbitrageOpportunity,
+  FLASH_LOAN_FEE,
+} from './profit-calculator.js';
+import { findOptimalAmount, MAX_AMOUNT } from './dynamic-sizer.js';
 import { RaydiumClient } from './dex-integrations/raydium.js';
 import { OrcaClient } from './dex-integrations/orca.js';
+import { JupiterClient, JupiterClientOptions } from './dex-integrations/jupiter.js';
+import { PriceSource, PoolPrice } from './dex-integrations/price-source.js';
+import { SwapApi } from './dex-integrations/swap-api.js';
+import { PriceFeed } from './price-feed.js';
+import { getMintInfo, isToken2022 } from './utils/token-program.js';
 
 /**
  * Trading pairs to monitor
@@ -21,6 +37,25 @@ export const TRADING_PAIRS = [
   'WIF/USDC',
 ];
 
+/**
+ * Per-pair arbitrage configuration
+ * Unset thresholds fall back to the global config
+ */
+export interface ArbitragePairConfig {
+  pair: string;
+  minProfitUsd?: number;
+  maxFlashAmount?: number;
+  enabled: boolean;
+}
+
+/**
+ * Default pair configuration - every trading pair enabled with global thresholds
+ */
+export const DEFAULT_PAIR_CONFIGS: ArbitragePairConfig[] = TRADING_PAIRS.map(pair => ({
+  pair,
+  enabled: true,
+}));
+
 /**
  * DEX identifiers - 2 DEXes with direct SDK access
  */
@@ -38,6 +73,70 @@ export interface PriceQuote {
   timestamp: number;
 }
 
+/**
+ * Why a pair did or didn't produce an opportunity in a scan
+ */
+export type PairScanOutcome =
+  | 'opportunity'
+  // Jupiter price hadn't moved, pair not quoted
+  | 'prefiltered'
+  // Fewer than two DEXes returned a price
+  | 'missing_quotes'
+  // No route profitable after fees and slippage
+  | 'no_spread'
+  // Profitable, but too small to cover gas
+  | 'below_min_size'
+  // Profitable at pool prices, but not at real swap quotes (false positive)
+  | 'unconfirmed'
+  | 'invalid'
+  | 'error';
+
+/**
+ * Diagnostics for one full scan
+ */
+export interface ScanReport {
+  opportunities: ArbitrageOpportunity[];
+  // Pairs per outcome
+  outcomes: Partial<Record<PairScanOutcome, number>>;
+  // Prices returned and missing (no pool, failed request) per DEX
+  perDex: Partial<Record<DexName, { quotes: number; misses: number }>>;
+  durationMs: number;
+  errors: string[];
+}
+
+/**
+ * Scanner options
+ */
+export interface ScannerOptions {
+  // Min divergence between Jupiter and last pool prices to re-quote a pair (0 = always quote)
+  divergenceThreshold?: number;
+  pairs?: ArbitragePairConfig[];
+  jupiter?: JupiterClientOptions;
+  // Stream Orca pool prices over WebSocket instead of polling each scan
+  priceFeed?: boolean;
+  // Drop pairs with a Token-2022 mint
+  skipToken2022?: boolean;
+  // Streamed prices older than this are re-polled (default: no limit)
+  maxPriceAgeMs?: number;
+  // Per-DEX price sources (defaults to the Raydium/Orca clients)
+  priceSources?: Partial<Record<DexName, PriceSource>>;
+  // Expected transaction cost; opportunities too small to cover it are dropped (0 = no gas filter)
+  gasEstimateLamports?: number;
+  // Re-quote both legs of each candidate with the DEX swap APIs and drop it unless it still repays the loan
+  confirmWithQuotes?: boolean;
+  // Also look for triangular routes (USDC → A → B → USDC) between every two scanned pairs
+  triangularRoutes?: boolean;
+}
+
+// Re-quote every pair at least this often, even if Jupiter shows no movement
+const PREFILTER_MAX_AGE_MS = 30_000;
+
+// SOL price for gas costs until SOL/USDC has been quoted
+const DEFAULT_SOL_PRICE_USD = 100;
+
+// Confirmation quotes only read the expected output, so the slippage bound doesn't matter
+const CONFIRM_SLIPPAGE_BPS = 50;
+
 /**
  * Multi-DEX Price Scanner
  * Fetches prices from multiple DEXes using their native SDKs
@@ -46,14 +145,60 @@ export class Scanner {
   private connection: Connection;
   private raydiumClient: RaydiumClient;
   private orcaClient: OrcaClient;
+  private jupiterClient: JupiterClient;
+  private priceSources: Partial<Record<DexName, PriceSource>>;
+  private priceFeed: PriceFeed | null = null;
+  private skipToken2022: boolean;
+  private maxPriceAgeMs: number;
+  private divergenceThreshold: number;
+  private pairConfigs: ArbitragePairConfig[];
+  private lastPairPrices: Map<string, { prices: Map<DexName, number>; timestamp: number }> = new Map();
+  private lastPairQuotes: Map<string, Map<DexName, PriceQuote>> = new Map();
   private scanCount = 0;
   private opportunitiesFound = 0;
+  private pairsPrefiltered = 0;
   private initialized = false;
+  private maxFlashAmount = MAX_AMOUNT;
+  private gasEstimateLamports: number;
+  private opportunitiesBelowMinSize = 0;
+  private confirmWithQuotes: boolean;
+  private triangularRoutes: boolean;
 
-  constructor(connection: Connection) {
+  constructor(connection: Connection, options: ScannerOptions = {}) {
     this.connection = connection;
     this.raydiumClient = new RaydiumClient(connection);
     this.orcaClient = new OrcaClient(connection);
+    this.jupiterClient = new JupiterClient(options.jupiter);
+    this.divergenceThreshold = options.divergenceThreshold ?? 0;
+    this.skipToken2022 = options.skipToken2022 ?? false;
+    this.maxPriceAgeMs = options.maxPriceAgeMs ?? Infinity;
+    this.gasEstimateLamports = options.gasEstimateLamports ?? 0;
+    this.confirmWithQuotes = options.confirmWithQuotes ?? false;
+    this.triangularRoutes = options.triangularRoutes ?? false;
+    this.pairConfigs = (options.pairs ?? DEFAULT_PAIR_CONFIGS).filter(p => p.enabled);
+    if (options.priceFeed) {
+      this.priceFeed = new PriceFeed(connection, this.orcaClient, this.pairConfigs.map(p => p.pair));
+    }
+    this.priceSources = options.priceSources ?? {
+      raydium: this.raydiumClient,
+      orca: this.withPriceFeed(this.orcaClient),
+    };
+  }
+
+  /**
+   * Orca source that answers from the price feed while it's live and fresh
+   */
+  private withPriceFeed(source: PriceSource): PriceSource {
+    return {
+      initialize: () => source.initialize(),
+      getPrice: async (pair: string): Promise<PoolPrice | null> => {
+        const feedPrice = this.priceFeed?.getPrice(pair) ?? null;
+        if (feedPrice && Date.now() - feedPrice.updatedAt <= this.maxPriceAgeMs) {
+          return feedPrice;
+        }
+        return source.getPrice(pair);
+      },
+    };
   }
 
   /**
@@ -65,20 +210,41 @@ export class Scanner {
     logger.info('Initializing DEX clients...');
     
     try {
-      // Initialize all DEX clients in parallel
-      const results = await Promise.allSettled([
-        this.raydiumClient.initialize(),
-        this.orcaClient.initialize(),
-      ]);
+      // Initialize all price sources in parallel
+      const sources = Object.entries(this.priceSources);
+      const results = await Promise.allSettled(sources.map(([, source]) => source.initialize()));
 
       // Log any initialization failures
-      const dexNames = ['Raydium', 'Orca'];
       results.forEach((result, index) => {
         if (result.status === 'rejected') {
-          logger.warn(`${dexNames[index]} init failed: ${result.reason}`);
+          logger.warn(`${sources[index][0]} init failed: ${result.reason}`);
         }
       });
 
+      if (this.divergenceThreshold > 0) {
+        try {
+          const latency = await this.jupiterClient.checkEndpoint();
+          logger.info(`Jupiter endpoint OK (${latency}ms)`);
+        } catch (e) {
+          logger.warn(`Jupiter endpoint check failed, pre-filter will fall back to full quoting: ${e}`);
+        }
+      }
+
+      if (this.skipToken2022) {
+        await this.dropToken2022Pairs();
+      }
+
+      // Look up every Orca pool in one batched call instead of per pair on first quote
+      await this.orcaClient.resolveWhirlpools(this.pairConfigs.map(p => p.pair));
+
+      if (this.priceFeed) {
+        try {
+          await this.priceFeed.start();
+        } catch (e) {
+          logger.warn(`Price feed failed to start, polling Orca instead: ${e}`);
+        }
+      }
+
       this.initialized = true;
       logger.info('DEX clients initialization complete');
     } catch (e) {
@@ -86,6 +252,43 @@ export class Scanner {
     }
   }
 
+  /**
+   * Remove pairs where either token is a Token-2022 mint
+   */
+  private async dropToken2022Pairs(): Promise<void> {
+    const kept: ArbitragePairConfig[] = [];
+    for (const pairConfig of this.pairConfigs) {
+      const mints = pairConfig.pair.split('/')
+        .map(symbol => this.raydiumClient.getTokenInfo(symbol)?.mint)
+        .filter((m): m is string => !!m);
+      try {
+        const infos = await Promise.all(mints.map(m => getMintInfo(this.connection, new PublicKey(m))));
+        if (infos.some(isToken2022)) {
+          logger.info(`${pairConfig.pair}: Token-2022 mint, skipping pair`);
+          continue;
+        }
+      } catch (e) {
+        logger.warn(`${pairConfig.pair}: Could not check token program: ${e}`);
+      }
+      kept.push(pairConfig);
+    }
+    this.pairConfigs = kept;
+  }
+
+  /**
+   * Stop background subscriptions
+   */
+  async stop(): Promise<void> {
+    await this.priceFeed?.stop();
+  }
+
+  /**
+   * Set the upper bound for flash loan sizing (config cap or reserve liquidity)
+   */
+  setMaxFlashAmount(amount: number): void {
+    this.maxFlashAmount = amount;
+  }
+
   /**
    * Fetch prices for a trading pair from all DEXes
    * Returns price in USDC per 1 token
@@ -94,32 +297,21 @@ export class Scanner {
     const quotes = new Map<DexName, PriceQuote>();
 
     // Fetch from each DEX in parallel
-    const [raydiumQuote, orcaQuote] = await Promise.all([
-      this.raydiumClient.getPrice(pair).catch(() => null),
-      this.orcaClient.getPrice(pair).catch(() => null),
-    ]);
-
-    // Add Raydium quote
-    if (raydiumQuote && raydiumQuote.price > 0) {
-      quotes.set('raydium', {
-        dex: 'raydium',
-        pair,
-        price: raydiumQuote.price,
-        liquidity: raydiumQuote.liquidity,
-        timestamp: Date.now(),
-      });
-    }
+    const sources = Object.entries(this.priceSources) as [DexName, PriceSource][];
+    const results = await Promise.all(sources.map(([, source]) => source.getPrice(pair).catch(() => null)));
 
-    // Add Orca quote
-    if (orcaQuote && orcaQuote.price > 0) {
-      quotes.set('orca', {
-        dex: 'orca',
-        pair,
-        price: orcaQuote.price,
-        liquidity: orcaQuote.liquidity,
-        timestamp: Date.now(),
-      });
-    }
+    sources.forEach(([dex], i) => {
+      const result = results[i];
+      if (result && result.price > 0) {
+        quotes.set(dex, {
+          dex,
+          pair,
+          price: result.price,
+          liquidity: result.liquidity,
+          timestamp: Date.now(),
+        });
+      }
+    });
 
     if (quotes.size > 0) {
       this.logQuoteSummary(pair, quotes);
@@ -153,74 +345,376 @@ export class Scanner {
     );
   }
 
+  /**
+   * Fetch Jupiter-implied prices (quote token per base token) for all pairs
+   * Failures are non-fatal: an empty map means every pair gets quoted
+   */
+  private async fetchJupiterPairPrices(): Promise<Map<string, number>> {
+    const pairPrices = new Map<string, number>();
+    if (this.divergenceThreshold <= 0) {
+      return pairPrices;
+    }
+
+    const mintOf = (symbol: string) => this.raydiumClient.getTokenInfo(symbol)?.mint;
+    const mints = new Set<string>();
+    for (const { pair } of this.pairConfigs) {
+      for (const symbol of pair.split('/')) {
+        const mint = mintOf(symbol);
+        if (mint) mints.add(mint);
+      }
+    }
+
+    try {
+      const usdPrices = await this.jupiterClient.getPrices(Array.from(mints));
+      for (const { pair } of this.pairConfigs) {
+        const [base, quote] = pair.split('/');
+        const basePrice = usdPrices.get(mintOf(base) || '');
+        const quotePrice = usdPrices.get(mintOf(quote) || '');
+        if (basePrice && quotePrice) {
+          pairPrices.set(pair, basePrice / quotePrice);
+        }
+      }
+    } catch (e) {
+      logger.warn(`[Jupiter] Price fetch failed, quoting all pairs: ${e}`);
+    }
+
+    return pairPrices;
+  }
+
+  /**
+   * Whether a pair can be skipped because Jupiter shows no movement since the last pool quotes
+   */
+  private isUnchanged(pair: string, jupiterPrice: number | undefined): boolean {
+    const last = this.lastPairPrices.get(pair);
+    if (!jupiterPrice || !last || Date.now() - last.timestamp > PREFILTER_MAX_AGE_MS) {
+      return false;
+    }
+
+    for (const price of last.prices.values()) {
+      if (Math.abs(jupiterPrice - price) / price >= this.divergenceThreshold) {
+        return false;
+      }
+    }
+    return true;
+  }
+
   /**
    * Scan all pairs for arbitrage opportunities
+   * @param onOpportunity Called as soon as each pair yields an opportunity, before later pairs are quoted
    */
-  async scanAllPairs(): Promise<ArbitrageOpportunity[]> {
+  async scanAllPairs(
+    onOpportunity?: (opportunity: ArbitrageOpportunity) => Promise<void> | void
+  ): Promise<ScanReport> {
     if (!this.initialized) {
       await this.initialize();
     }
 
     this.scanCount++;
-    const opportunities: ArbitrageOpportunity[] = [];
+    const start = Date.now();
+    const report: ScanReport = { opportunities: [], outcomes: {}, perDex: {}, durationMs: 0, errors: [] };
+    const count = (outcome: PairScanOutcome) => {
+      report.outcomes[outcome] = (report.outcomes[outcome] ?? 0) + 1;
+    };
     
-    logger.scan(`Scanning ${TRADING_PAIRS.length} pairs across ${DEX_LIST.length} DEXes...`);
+    logger.scan(`Scanning ${this.pairConfigs.length} pairs across ${Object.keys(this.priceSources).length} DEXes...`);
 
-    for (const pair of TRADING_PAIRS) {
+    const jupiterPrices = await this.fetchJupiterPairPrices();
+
+    for (const { pair } of this.pairConfigs) {
       try {
-        const quotes = await this.fetchPairPrices(pair);
-        
-        if (quotes.size < 2) {
-          continue; // Need at least 2 DEXes to arbitrage
+        if (this.isUnchanged(pair, jupiterPrices.get(pair))) {
+          this.pairsPrefiltered++;
+          count('prefiltered');
+          logger.debug(`${pair}: no divergence from Jupiter, skipping quotes`);
+          continue;
         }
 
-        // Log prices for debugging
-        for (const [dex, quote] of quotes) {
-          logger.debug(`${pair} ${dex}: $${quote.price.toFixed(6)}`);
+        const { opportunity, outcome } = await withSpan('scan.pair', { pair }, async span => {
+          const evaluated = await this.evaluatePair(pair, report.perDex);
+          span.setAttribute('scan.outcome', evaluated.outcome);
+          return evaluated;
+        });
+        count(outcome);
+        if (opportunity) {
+          report.opportunities.push(opportunity);
+          await onOpportunity?.(opportunity);
         }
 
-        // Convert to price and liquidity maps
-        const prices = new Map<string, number>();
-        const liquidities = new Map<string, number>();
-        
-        for (const [dex, quote] of quotes) {
-          prices.set(dex, quote.price);
-          liquidities.set(dex, quote.liquidity);
+      } catch (e) {
+        count('error');
+        report.errors.push(`${pair}: ${e}`);
+        logger.error(`Error scanning ${pair}: ${e}`);
+      }
+    }
+
+    if (this.triangularRoutes) {
+      for (const opportunity of await this.findTriangularOpportunities(start)) {
+        report.opportunities.push(opportunity);
+        await onOpportunity?.(opportunity);
+      }
+    }
+
+    report.durationMs = Date.now() - start;
+    return report;
+  }
+
+  /**
+   * Quote one pair on every DEX and return its best valid profitable opportunity
+   * Used by full scans and by price-triggered rescans (no Jupiter pre-filter)
+   */
+  async scanPair(pair: string): Promise<ArbitrageOpportunity | null> {
+    return (await this.evaluatePair(pair)).opportunity;
+  }
+
+  /**
+   * scanPair plus the outcome and the pool prices it saw (`watch` command)
+   */
+  async inspectPair(pair: string): Promise<{
+    opportunity: ArbitrageOpportunity | null;
+    outcome: PairScanOutcome;
+    prices: Partial<Record<DexName, number>>;
+  }> {
+    const { opportunity, outcome } = await this.evaluatePair(pair);
+    return { opportunity, outcome, prices: Object.fromEntries(this.lastPairPrices.get(pair)?.prices ?? []) };
+  }
+
+  /**
+   * scanPair plus why the pair produced no opportunity
+   * @param perDex Per-DEX quote counters to update (scan report)
+   */
+  private async evaluatePair(
+    pair: string,
+    perDex?: ScanReport['perDex']
+  ): Promise<{ opportunity: ArbitrageOpportunity | null; outcome: PairScanOutcome }> {
+    const none = (outcome: PairScanOutcome) => ({ opportunity: null, outcome });
+    const pairConfig = this.pairConfigs.find(p => p.pair === pair);
+    if (!pairConfig) {
+      return none('invalid');
+    }
+    const maxFlashAmount = Math.min(this.maxFlashAmount, pairConfig.maxFlashAmount ?? Infinity);
+
+    const quotes = await this.fetchPairPrices(pair);
+    this.lastPairQuotes.set(pair, quotes);
+    this.lastPairPrices.set(pair, {
+      prices: new Map(Array.from(quotes.entries()).map(([dex, q]) => [dex, q.price])),
+      timestamp: Date.now(),
+    });
+    if (perDex) {
+      for (const dex of Object.keys(this.priceSources) as DexName[]) {
+        const dexStats = perDex[dex] ?? (perDex[dex] = { quotes: 0, misses: 0 });
+        if (quotes.has(dex)) {
+          dexStats.quotes++;
+        } else {
+          dexStats.misses++;
         }
+      }
+    }
+    
+    if (quotes.size < 2) {
+      return none('missing_quotes'); // Need at least 2 DEXes to arbitrage
+    }
 
-        // Find best opportunity
-        const opportunity = findBestOpportunity(
-          pair,
-          prices,
-          liquidities,
-          (p, liq, spread) => calculateOptimalAmount(p, liq, spread).amount
-        );
-
-        if (opportunity && opportunity.calculation.isProfitable) {
-          this.opportunitiesFound++;
-          opportunities.push(opportunity);
-          
-          logger.opportunity(`${pair} ${(opportunity.spreadPercent * 100).toFixed(2)}% spread`);
-          logger.info(`   Buy: ${opportunity.buyDex} ($${opportunity.buyPrice.toFixed(4)})`);
-          logger.info(`   Sell: ${opportunity.sellDex} ($${opportunity.sellPrice.toFixed(4)})`);
-          logger.info(`   Expected profit: $${opportunity.calculation.netProfit.toFixed(2)}`);
+    // Log prices for debugging
+    for (const [dex, quote] of quotes) {
+      logger.debug(`${pair} ${dex}: $${quote.price.toFixed(6)}`);
+    }
+
+    // Convert to price and liquidity maps
+    const prices = new Map<string, number>();
+    const liquidities = new Map<string, number>();
+    
+    for (const [dex, quote] of quotes) {
+      prices.set(dex, quote.price);
+      liquidities.set(dex, quote.liquidity);
+    }
+
+    // Find best opportunity
+    const opportunity = findBestOpportunity(
+      pair,
+      prices,
+      liquidities,
+      (_pair, liq, spread, fees) => findOptimalAmount(liq, spread, fees, maxFlashAmount).amount
+    );
+
+    if (!opportunity || !opportunity.calculation.isProfitable) {
+      return none('no_spread');
+    }
+
+    const outcome = await this.screenOpportunity(opportunity);
+    return { opportunity: outcome === 'opportunity' ? opportunity : null, outcome };
+  }
+
+  /**
+   * Checks a profitable candidate must pass before it's reported: big enough to cover gas,
+   * consistent, and (with confirmWithQuotes) still profitable at real swap quotes
+   */
+  private async screenOpportunity(opportunity: ArbitrageOpportunity): Promise<PairScanOutcome> {
+    const { pair } = opportunity;
+
+    // Too small to pay for its own transaction; MIN_PROFIT_USD is still checked before execution
+    const minAmount = computeMinProfitableAmount(
+      opportunity.spreadPercent,
+      routeFeesPercent(opportunity.buyDex, opportunity.sellDex, opportunity.crossLeg?.dex),
+      this.gasEstimateLamports,
+      this.getSolPrice()
+    );
+    if (opportunity.flashAmount < minAmount) {
+      this.opportunitiesBelowMinSize++;
+      logger.debug(`${pair}: $${opportunity.flashAmount.toFixed(0)} below min profitable size $${minAmount.toFixed(0)}`);
+      return 'below_min_size';
+    }
+
+    const invalidReason = validateOpportunity(opportunity);
+    if (invalidReason) {
+      logger.warn(`${pair}: Dropping invalid opportunity (${describeRoute(opportunity)}): ${invalidReason}`);
+      return 'invalid';
+    }
+
+    if (this.confirmWithQuotes) {
+      const quotedProfit = await this.quotedProfit(opportunity);
+      if (quotedProfit === null || quotedProfit <= 0) {
+        logger.debug(`${pair}: Not confirmed by swap quotes (${quotedProfit === null ? 'no quote' : `$${quotedProfit.toFixed(2)}`})`);
+        return 'unconfirmed';
+      }
+    }
+
+    this.opportunitiesFound++;
+    
+    logger.opportunity(`${pair} ${(opportunity.spreadPercent * 100).toFixed(2)}% spread`);
+    logger.info(`   Buy: ${opportunity.buyDex} ($${opportunity.buyPrice.toFixed(4)})`);
+    if (opportunity.crossLeg) {
+      logger.info(`   Swap: ${opportunity.crossLeg.dex} (${opportunity.crossLeg.rate.toFixed(6)} ${opportunity.crossLeg.token} each)`);
+    }
+    logger.info(`   Sell: ${opportunity.sellDex} ($${opportunity.sellPrice.toFixed(4)})`);
+    logger.info(`   Expected profit: $${opportunity.calculation.netProfit.toFixed(2)}`);
+
+    return 'opportunity';
+  }
+
+  /**
+   * Triangular routes between every two pairs quoted in this scan with the same quote token:
+   * buy A, swap A → B, sell B (and the reverse), over every DEX triple
+   * Costs one price request per DEX for each cross pair
+   * @param since Start of this scan; pairs without fresh quotes (prefiltered) are left out
+   */
+  private async findTriangularOpportunities(since: number): Promise<ArbitrageOpportunity[]> {
+    const quoted = this.pairConfigs.filter(({ pair }) =>
+      Array.from(this.lastPairQuotes.get(pair)?.values() ?? []).some(q => q.timestamp >= since)
+    );
+    const found: ArbitrageOpportunity[] = [];
+
+    for (let i = 0; i < quoted.length; i++) {
+      for (let j = i + 1; j < quoted.length; j++) {
+        const [baseA, quoteA] = quoted[i].pair.split('/');
+        const [baseB, quoteB] = quoted[j].pair.split('/');
+        if (quoteA !== quoteB) continue;
+
+        try {
+          // Price of A in B; the B → A direction uses its inverse
+          const crossQuotes = await this.fetchPairPrices(`${baseA}/${baseB}`);
+          if (crossQuotes.size === 0) continue;
+
+          const directions: [ArbitragePairConfig, ArbitragePairConfig, boolean][] = [
+            [quoted[i], quoted[j], false],
+            [quoted[j], quoted[i], true],
+          ];
+          for (const [from, to, inverse] of directions) {
+            const rates = new Map(Array.from(crossQuotes, ([dex, q]) => [dex, {
+              price: inverse ? 1 / q.price : q.price,
+              liquidity: q.liquidity,
+            }]));
+            const maxFlashAmount = Math.min(this.maxFlashAmount, from.maxFlashAmount ?? Infinity);
+            const opportunity = findBestTriangularOpportunity(
+              from.pair,
+              to.pair.split('/')[0],
+              this.lastPairQuotes.get(from.pair)!,
+              rates,
+              this.lastPairQuotes.get(to.pair)!,
+              (_pair, liq, spread, fees) => findOptimalAmount(liq, spread, fees, maxFlashAmount).amount
+            );
+            if (opportunity && await this.screenOpportunity(opportunity) === 'opportunity') {
+              found.push(opportunity);
+            }
+          }
+        } catch (e) {
+          logger.error(`Error scanning ${baseA}/${baseB} triangular routes: ${e}`);
         }
+      }
+    }
 
-      } catch (e) {
-        logger.error(`Error scanning ${pair}: ${e}`);
+    return found;
+  }
+
+  /**
+   * USD profit of the candidate at current swap quotes: buy leg, then swap exactly what each leg
+   * returns (through the cross leg, if any), minus the flash loan repayment (DEX fees and price
+   * impact are in the quotes)
+   * @returns null when any leg can't be quoted
+   */
+  private async quotedProfit(opp: ArbitrageOpportunity): Promise<number | null> {
+    const swapApis: Record<DexName, SwapApi> = { raydium: this.raydiumClient, orca: this.orcaClient };
+    const [base, quote] = opp.pair.split('/');
+    const baseMint = this.raydiumClient.getTokenInfo(base)?.mint;
+    const quoteMint = this.raydiumClient.getTokenInfo(quote)?.mint;
+    const crossMint = opp.crossLeg ? this.raydiumClient.getTokenInfo(opp.crossLeg.token)?.mint : undefined;
+    const buyApi = swapApis[opp.buyDex as DexName];
+    const sellApi = swapApis[opp.sellDex as DexName];
+    const crossApi = opp.crossLeg ? swapApis[opp.crossLeg.dex as DexName] : undefined;
+    if (!baseMint || !quoteMint || !buyApi || !sellApi) return null;
+    if (opp.crossLeg && (!crossMint || !crossApi)) return null;
+
+    try {
+      const amountIn = Math.floor(opp.flashAmount * 1_000_000);
+      const buy = await buyApi.quote(new PublicKey(quoteMint), new PublicKey(baseMint), amountIn, CONFIRM_SLIPPAGE_BPS);
+      if (!buy || buy.outAmount <= 0) return null;
+      let sellMint = baseMint;
+      let sellAmount = buy.outAmount;
+      if (crossApi && crossMint) {
+        const cross = await crossApi.quote(new PublicKey(baseMint), new PublicKey(crossMint), buy.outAmount, CONFIRM_SLIPPAGE_BPS);
+        if (!cross || cross.outAmount <= 0) return null;
+        sellMint = crossMint;
+        sellAmount = cross.outAmount;
       }
+      const sell = await sellApi.quote(new PublicKey(sellMint), new PublicKey(quoteMint), sellAmount, CONFIRM_SLIPPAGE_BPS);
+      if (!sell) return null;
+      return (sell.outAmount - amountIn * (1 + FLASH_LOAN_FEE)) / 1_000_000;
+    } catch (e) {
+      logger.debug(`${opp.pair}: Confirmation quote failed: ${e}`);
+      return null;
     }
+  }
+
+  /**
+   * Average SOL/USDC price across DEXes from the last quote (default until quoted)
+   */
+  getSolPrice(): number {
+    const prices = Array.from(this.lastPairPrices.get('SOL/USDC')?.prices.values() ?? []);
+    return prices.length > 0
+      ? prices.reduce((sum, p) => sum + p, 0) / prices.length
+      : DEFAULT_SOL_PRICE_USD;
+  }
 
-    return opportunities;
+  /**
+   * Rescan a pair as soon as its streamed pool price moves thresholdBps (needs the price feed)
+   * @returns false when there is no price feed to trigger on
+   */
+  onPriceMove(thresholdBps: number, listener: (pair: string) => void): boolean {
+    if (!this.priceFeed) {
+      return false;
+    }
+    this.priceFeed.onPriceMove(thresholdBps, listener);
+    return true;
   }
 
   /**
    * Get scanner statistics
    */
-  getStats(): { scanCount: number; opportunitiesFound: number } {
+  getStats(): { scanCount: number; opportunitiesFound: number; pairsPrefiltered: number; opportunitiesBelowMinSize: number } {
     return {
       scanCount: this.scanCount,
       opportunitiesFound: this.opportunitiesFound,
+      pairsPrefiltered: this.pairsPrefiltered,
+      opportunitiesBelowMinSize: this.opportunitiesBelowMinSize,
     };
   }
 }
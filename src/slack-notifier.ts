@@ -0,0 +1,87 @@
+/**
+ * Slack Notifier
+ * Posts a Block Kit summary of each landed trade (and pause/resume notices) to a Slack incoming webhook
+ */
+
+import { logger } from './utils/logger.js';
+import { ArbitrageOpportunity, describeRoute } from './profit-calculator.js';
+import { ExecutionResult } from './executor.js';
+
+const WEBHOOK_TIMEOUT_MS = 5000;
+
+/**
+ * Block Kit message (JSON string) for a landed trade: route, size, expected vs realized profit,
+ * fee paid and a Solscan link
+ */
+export function toSlackMessage(opp: ArbitrageOpportunity, result: ExecutionResult): string {
+  const profit = result.actualProfit ?? 0;
+  const fields = [
+    `*Route*\n${describeRoute(opp)}`,
+    `*Flash amount*\n$${opp.flashAmount.toLocaleString()}`,
+    `*Spread*\n${(opp.spreadPercent * 100).toFixed(3)}%`,
+    `*Expected profit*\n$${opp.calculation.netProfit.toFixed(2)}`,
+    `*Realized profit*\n$${profit.toFixed(2)}`,
+    `*Fee*\n${result.feeLamports !== undefined ? `${result.feeLamports} lamports` : 'unknown'}`,
+  ];
+
+  return JSON.stringify({
+    text: `${opp.pair} arbitrage landed: $${profit.toFixed(2)}`,
+    blocks: [
+      {
+        type: 'header',
+        text: { type: 'plain_text', text: `⚡ ${opp.pair} arbitrage landed` },
+      },
+      {
+        type: 'section',
+        fields: fields.map(text => ({ type: 'mrkdwn', text })),
+      },
+      {
+        type: 'context',
+        elements: [{
+          type: 'mrkdwn',
+          text: result.txSignature
+            ? `<https://solscan.io/tx/${result.txSignature}|View on Solscan>`
+            : 'No signature',
+        }],
+      },
+    ],
+  });
+}
+
+export class SlackNotifier {
+  private webhookUrl: string;
+
+  constructor(webhookUrl: string) {
+    this.webhookUrl = webhookUrl;
+  }
+
+  /**
+   * Post a trade summary; failures are logged, never thrown
+   */
+  async notifyTrade(opp: ArbitrageOpportunity, result: ExecutionResult): Promise<void> {
+    await this.post(toSlackMessage(opp, result));
+  }
+
+  /**
+   * Post a plain status message (pause/resume and similar)
+   */
+  async notifyText(text: string): Promise<void> {
+    await this.post(JSON.stringify({ text }));
+  }
+
+  private async post(body: string): Promise<void> {
+    try {
+      const response = await fetch(this.webhookUrl, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body,
+        signal: AbortSignal.timeout(WEBHOOK_TIMEOUT_MS),
+      });
+      if (!response.ok) {
+        logger.warn(`[Slack] Webhook returned ${response.status}: ${await response.text()}`);
+      }
+    } catch (e) {
+      logger.warn(`[Slack] Webhook failed: ${e}`);
+    }
+  }
+}
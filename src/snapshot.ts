@@ -0,0 +1,162 @@
+/**
+ * Price Snapshots
+ * Records the pool prices a scan sees to disk and replays them through the scanner offline,
+ * so threshold and sizing changes can be checked against the same market state every run
+ */
+
+import { Connection } from '@solana/web3.js';
+import fs from 'fs';
+import path from 'path';
+import { logger } from './utils/logger.js';
+import { Scanner, ScannerOptions, DexName } from './scanner.js';
+import { PriceSource, PoolPrice } from './dex-integrations/price-source.js';
+import { ArbitrageOpportunity } from './profit-calculator.js';
+
+const SNAPSHOT_FILE = 'prices.json';
+
+/**
+ * Pool prices per pair and DEX at one slot
+ */
+export interface PriceSnapshot {
+  slot: number;
+  takenAt: number;
+  pairs: Record<string, Partial<Record<DexName, PoolPrice>>>;
+}
+
+/**
+ * One replayed opportunity (no timestamps, so reports of the same snapshot are identical)
+ */
+export interface ReplayEntry {
+  pair: string;
+  buyDex: string;
+  sellDex: string;
+  buyPrice: number;
+  sellPrice: number;
+  spreadPercent: number;
+  flashAmount: number;
+  netProfit: number;
+  minProfit: number;
+  wouldExecute: boolean;
+}
+
+export interface ReplayReport {
+  slot: number;
+  opportunities: ReplayEntry[];
+  totalExpectedProfit: number;
+}
+
+/**
+ * Serves prices for one DEX from a snapshot
+ */
+export class SnapshotPriceSource implements PriceSource {
+  private snapshot: PriceSnapshot;
+  private dex: DexName;
+
+  constructor(snapshot: PriceSnapshot, dex: DexName) {
+    this.snapshot = snapshot;
+    this.dex = dex;
+  }
+
+  async initialize(): Promise<void> {}
+
+  async getPrice(pair: string): Promise<PoolPrice | null> {
+    return this.snapshot.pairs[pair]?.[this.dex] ?? null;
+  }
+}
+
+/**
+ * Quote every configured pair once with a live scanner and record the results
+ */
+export async function recordSnapshot(connection: Connection, scanner: Scanner, pairs: string[]): Promise<PriceSnapshot> {
+  await scanner.initialize();
+  const slot = await connection.getSlot('confirmed');
+
+  const snapshot: PriceSnapshot = { slot, takenAt: Date.now(), pairs: {} };
+  for (const pair of pairs) {
+    const quotes = await scanner.fetchPairPrices(pair);
+    snapshot.pairs[pair] = Object.fromEntries(
+      Array.from(quotes.entries()).map(([dex, q]) => [dex, { price: q.price, liquidity: q.liquidity }])
+    );
+  }
+  return snapshot;
+}
+
+export function saveSnapshot(dir: string, snapshot: PriceSnapshot): string {
+  fs.mkdirSync(dir, { recursive: true });
+  const file = path.join(dir, SNAPSHOT_FILE);
+  fs.writeFileSync(file, JSON.stringify(snapshot, null, 2));
+  return file;
+}
+
+export function loadSnapshot(dir: string): PriceSnapshot {
+  const snapshot = JSON.parse(fs.readFileSync(path.join(dir, SNAPSHOT_FILE), 'utf-8')) as PriceSnapshot;
+  if (typeof snapshot.slot !== 'number' || typeof snapshot.pairs !== 'object' || snapshot.pairs === null) {
+    throw new Error(`${dir}: not a price snapshot`);
+  }
+  return snapshot;
+}
+
+/**
+ * Run the scanner over a snapshot instead of RPC; nothing is executed
+ * @param options Scanner options; price sources, the price feed, Token-2022 checks and quote confirmation are overridden
+ * @param minProfitFor Execution threshold per opportunity
+ */
+export async function replaySnapshot(
+  connection: Connection,
+  snapshot: PriceSnapshot,
+  options: ScannerOptions,
+  maxFlashAmount: number,
+  minProfitFor: (opp: ArbitrageOpportunity) => number
+): Promise<ReplayReport> {
+  const dexes = new Set<DexName>();
+  for (const prices of Object.values(snapshot.pairs)) {
+    for (const dex of Object.keys(prices)) dexes.add(dex as DexName);
+  }
+
+  const scanner = new Scanner(connection, {
+    ...options,
+    divergenceThreshold: 0,
+    priceFeed: false,
+    skipToken2022: false,
+    confirmWithQuotes: false,
+    priceSources: Object.fromEntries(Array.from(dexes).map(dex => [dex, new SnapshotPriceSource(snapshot, dex)])),
+  });
+  await scanner.initialize();
+  scanner.setMaxFlashAmount(maxFlashAmount);
+
+  const opportunities: ReplayEntry[] = [];
+  for (const pair of Object.keys(snapshot.pairs)) {
+    const opp = await scanner.scanPair(pair);
+    if (!opp) continue;
+    const minProfit = minProfitFor(opp);
+    opportunities.push({
+      pair: opp.pair,
+      buyDex: opp.buyDex,
+      sellDex: opp.sellDex,
+      buyPrice: opp.buyPrice,
+      sellPrice: opp.sellPrice,
+      spreadPercent: opp.spreadPercent,
+      flashAmount: opp.flashAmount,
+      netProfit: opp.calculation.netProfit,
+      minProfit,
+      wouldExecute: opp.calculation.netProfit >= minProfit,
+    });
+  }
+
+  return {
+    slot: snapshot.slot,
+    opportunities,
+    totalExpectedProfit: opportunities.filter(o => o.wouldExecute).reduce((sum, o) => sum + o.netProfit, 0),
+  };
+}
+
+export function logReplayReport(report: ReplayReport): void {
+  logger.info(`[Replay] Slot ${report.slot}: ${report.opportunities.length} opportunities`);
+  for (const o of report.opportunities) {
+    logger.info(
+      `[Replay]   ${o.pair} ${o.buyDex} → ${o.sellDex} | ${(o.spreadPercent * 100).toFixed(3)}% | ` +
+      `$${o.flashAmount.toFixed(0)} | $${o.netProfit.toFixed(2)} (min $${o.minProfit}) ${o.wouldExecute ? 'EXECUTE' : 'skip'}`
+    );
+  }
+  logger.info(`[Replay] Expected profit of executable trades: $${report.totalExpectedProfit.toFixed(2)}`);
+}
@@ -0,0 +1,272 @@
+/**
+ * Profit Sweeper
+ * Swaps token balances left in the wallet (ExactOut leftovers, airdrops, dust) into one
+ * target mint via Raydium, optionally closing emptied token accounts to reclaim rent
+ */
+
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import { createCloseAccountInstruction, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { logger } from './utils/logger.js';
+import { isWsol } from './utils/wsol.js';
+import { formatTokenAmount } from './token-registry.js';
+import { RaydiumClient } from './dex-integrations/raydium.js';
+import { JupiterClient } from './dex-integrations/jupiter.js';
+import { SwapOptions, DEFAULT_SWAP_OPTIONS } from './dex-integrations/swap-options.js';
+
+// Close instructions per transaction
+const CLOSE_BATCH_SIZE = 10;
+
+// Worst-case fee of one sweep transaction (base fee plus a capped Raydium priority fee)
+const MAX_SWEEP_TX_FEE_LAMPORTS = 500_000;
+
+export interface SweepOptions {
+  targetMint: PublicKey;
+  // Balances worth less than this (USD) are left alone
+  dustUsd: number;
+  closeEmptyAccounts: boolean;
+  // Mints whose accounts are never closed (the flash loan and pair tokens)
+  keepMints: Set<string>;
+  // Only log what would be swapped
+  dryRun: boolean;
+  // Stop sending once the wallet's SOL minus one transaction's worst-case fee would fall below this
+  minSolReserveLamports?: number;
+  swapOptions?: SwapOptions;
+}
+
+// Settings a single sweep can override (e.g. from the `sweep` command line)
+export type SweepOverrides = Partial<Pick<SweepOptions, 'dustUsd' | 'closeEmptyAccounts'>>;
+
+export interface SweepResult {
+  swapped: number;
+  skipped: number;
+  closed: number;
+}
+
+interface HeldAccount {
+  address: PublicKey;
+  mint: string;
+  programId: PublicKey;
+  amount: bigint;
+  uiAmount: number;
+}
+
+export class Sweeper {
+  private connection: Connection;
+  private keypair: Keypair;
+  private raydium: RaydiumClient;
+  private jupiter: JupiterClient;
+  private options: SweepOptions;
+  // Mints a trade is currently using; never swept or closed
+  private isBusy: (mint: string) => boolean;
+  // Sweep in progress, if any
+  private current: Promise<SweepResult> | null = null;
+
+  constructor(
+    connection: Connection,
+    keypair: Keypair,
+    raydium: RaydiumClient,
+    jupiter: JupiterClient,
+    options: SweepOptions,
+    isBusy: (mint: string) => boolean = () => false
+  ) {
+    this.connection = connection;
+    this.keypair = keypair;
+    this.raydium = raydium;
+    this.jupiter = jupiter;
+    this.options = options;
+    this.isBusy = isBusy;
+  }
+
+  isRunning(): boolean {
+    return this.current !== null;
+  }
+
+  /**
+   * Resolves once no sweep is in progress (for shutdown)
+   */
+  async idle(): Promise<void> {
+    await this.current?.catch(() => {});
+  }
+
+  /**
+   * Run one sweep; a sweep already in progress makes this a no-op
+   */
+  async sweep(overrides: SweepOverrides = {}): Promise<SweepResult> {
+    if (this.current) return { swapped: 0, skipped: 0, closed: 0 };
+    this.current = this.runSweep({ ...this.options, ...overrides });
+    try {
+      return await this.current;
+    } finally {
+      this.current = null;
+    }
+  }
+
+  private async runSweep(options: SweepOptions): Promise<SweepResult> {
+    const result: SweepResult = { swapped: 0, skipped: 0, closed: 0 };
+    const target = this.options.targetMint.toBase58();
+    const accounts = (await this.getTokenAccounts())
+      .filter(a => a.mint !== target && !isWsol(new PublicKey(a.mint)) && !this.isBusy(a.mint));
+
+    const funded = accounts.filter(a => a.amount > 0n);
+    const prices = funded.length > 0
+      ? await this.jupiter.getPrices(Array.from(new Set(funded.map(a => a.mint))))
+      : new Map<string, number>();
+
+    const emptied: HeldAccount[] = accounts.filter(a => a.amount === 0n);
+    for (const account of funded) {
+      const price = prices.get(account.mint);
+      const valueUsd = price !== undefined ? account.uiAmount * price : 0;
+      if (valueUsd < options.dustUsd) {
+        result.skipped++;
+        logger.debug(`[Sweep] Skipping ${account.mint}: ${account.uiAmount} ($${valueUsd.toFixed(2)}) below dust threshold`);
+        continue;
+      }
+
+      if (this.options.dryRun) {
+        logger.info(`[Sweep] [DRY RUN] Would swap ${account.uiAmount} ${account.mint} (~$${valueUsd.toFixed(2)}) → ${target}`);
+        continue;
+      }
+
+      // Re-check right before sending: a trade may have started on this mint meanwhile
+      if (this.isBusy(account.mint)) {
+        result.skipped++;
+        continue;
+      }
+      if (!await this.canPayFee()) {
+        result.skipped++;
+        continue;
+      }
+      if (await this.swapAll(account)) {
+        result.swapped++;
+        emptied.push(account);
+      }
+    }
+
+    if (options.closeEmptyAccounts && !this.options.dryRun && await this.canPayFee()) {
+      result.closed = await this.closeAccounts(
+        emptied.filter(a => !this.options.keepMints.has(a.mint) && !this.isBusy(a.mint))
+      );
+    }
+
+    logger.info(`[Sweep] ${result.swapped} swapped, ${result.skipped} skipped, ${result.closed} accounts closed`);
+    return result;
+  }
+
+  /**
+   * Whether one more transaction keeps the wallet above the SOL reserve
+   */
+  private async canPayFee(): Promise<boolean> {
+    const reserve = this.options.minSolReserveLamports ?? 0;
+    if (reserve === 0) return true;
+    const balance = await this.connection.getBalance(this.keypair.publicKey, 'confirmed');
+    if (balance - MAX_SWEEP_TX_FEE_LAMPORTS >= reserve) return true;
+    logger.warn(`[Sweep] SOL balance ${balance} lamports too close to the ${reserve} lamport reserve, not sending`);
+    return false;
+  }
+
+  private async getTokenAccounts(): Promise<HeldAccount[]> {
+    const owner = this.keypair.publicKey;
+    const held: HeldAccount[] = [];
+    for (const programId of [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]) {
+      const { value } = await this.connection.getParsedTokenAccountsByOwner(owner, { programId }, 'confirmed');
+      for (const { pubkey, account } of value) {
+        const info = account.data.parsed.info;
+        held.push({
+          address: pubkey,
+          mint: info.mint,
+          programId,
+          amount: BigInt(info.tokenAmount.amount),
+          uiAmount: info.tokenAmount.uiAmount ?? 0,
+        });
+      }
+    }
+    return held;
+  }
+
+  /**
+   * Swap an account's whole balance into the target mint
+   */
+  private async swapAll(account: HeldAccount): Promise<boolean> {
+    const mint = new PublicKey(account.mint);
+    const swapOptions = this.options.swapOptions ?? DEFAULT_SWAP_OPTIONS;
+    try {
+      const quote = await this.raydium.quote(mint, this.options.targetMint, Number(account.amount), swapOptions.slippageBps);
+      if (!quote) {
+        logger.warn(`[Sweep] No Raydium route for ${account.mint}`);
+        return false;
+      }
+
+      const txBuffer = await this.raydium.buildSwapTransaction(
+        account.mint,
+        this.options.targetMint.toBase58(),
+        Number(account.amount),
+        this.keypair.publicKey,
+        swapOptions
+      );
+      if (!txBuffer) {
+        logger.warn(`[Sweep] Failed to build swap for ${account.mint}`);
+        return false;
+      }
+
+      // Raydium sets the blockhash; confirm against the current block height window
+      const tx = VersionedTransaction.deserialize(txBuffer);
+      tx.sign([this.keypair]);
+      const { lastValidBlockHeight } = await this.connection.getLatestBlockhash('confirmed');
+      const signature = await this.connection.sendTransaction(tx);
+      const confirmation = await this.connection.confirmTransaction(
+        { signature, blockhash: tx.message.recentBlockhash, lastValidBlockHeight },
+        'confirmed'
+      );
+      if (confirmation.value.err) {
+        logger.warn(`[Sweep] Swap of ${account.mint} failed: ${JSON.stringify(confirmation.value.err)}`);
+        return false;
+      }
+
+      logger.success(`[Sweep] Swapped ${formatTokenAmount(account.amount, account.mint)} for ~${formatTokenAmount(quote.outAmount, this.options.targetMint)}: ${signature}`);
+      return true;
+    } catch (e) {
+      logger.warn(`[Sweep] Swap of ${account.mint} failed: ${e}`);
+      return false;
+    }
+  }
+
+  /**
+   * Close empty token accounts, returning their rent to the wallet
+   */
+  private async closeAccounts(accounts: HeldAccount[]): Promise<number> {
+    const owner = this.keypair.publicKey;
+    let closed = 0;
+
+    for (let i = 0; i < accounts.length; i += CLOSE_BATCH_SIZE) {
+      const batch = accounts.slice(i, i + CLOSE_BATCH_SIZE);
+      const instructions: TransactionInstruction[] = batch.map(a =>
+        createCloseAccountInstruction(a.address, owner, owner, [], a.programId)
+      );
+
+      try {
+        const { blockhash, lastValidBlockHeight } = await this.connection.getLatestBlockhash('confirmed');
+        const message = new TransactionMessage({ payerKey: owner, recentBlockhash: blockhash, instructions }).compileToV0Message();
+        const tx = new VersionedTransaction(message);
+        tx.sign([this.keypair]);
+
+        const signature = await this.connection.sendTransaction(tx);
+        const confirmation = await this.connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+        if (confirmation.value.err) {
+          logger.warn(`[Sweep] Closing accounts failed: ${JSON.stringify(confirmation.value.err)}`);
+          continue;
+        }
+        closed += batch.length;
+      } catch (e) {
+        logger.warn(`[Sweep] Closing accounts failed: ${e}`);
+      }
+    }
+    return closed;
+  }
+}
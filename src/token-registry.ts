@@ -0,0 +1,145 @@
+/**
+ * Token Registry
+ * Symbol ↔ mint and decimals for every token the bot trades, shared by config, the DEX clients
+ * and the executor. Built-in tokens are always known; other pair symbols are resolved from
+ * Jupiter's verified token list at startup, with a cache file as the offline fallback
+ */
+
+import { PublicKey } from '@solana/web3.js';
+import fs from 'fs';
+import { logger } from './utils/logger.js';
+import { JupiterClient } from './dex-integrations/jupiter.js';
+
+export interface TokenInfo {
+  symbol: string;
+  mint: PublicKey;
+  decimals: number;
+}
+
+// Always available, even without network or a cache file
+const BUILTIN_TOKENS: [string, string, number][] = [
+  ['SOL', 'So11111111111111111111111111111111111111112', 9],
+  ['USDC', 'EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v', 6],
+  ['JUP', 'JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN', 6],
+  ['JTO', 'jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL', 9],
+  ['BONK', 'DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263', 5],
+  ['WIF', 'EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm', 6],
+];
+
+export class TokenRegistry {
+  private bySymbol: Map<string, TokenInfo> = new Map();
+  private byMint: Map<string, TokenInfo> = new Map();
+
+  constructor() {
+    for (const [symbol, mint, decimals] of BUILTIN_TOKENS) {
+      this.add({ symbol, mint: new PublicKey(mint), decimals });
+    }
+  }
+
+  get(symbol: string): TokenInfo | undefined {
+    return this.bySymbol.get(symbol);
+  }
+
+  /**
+   * Mint for a symbol
+   * @throws Error for symbols that were never registered or resolved
+   */
+  mint(symbol: string): PublicKey {
+    const info = this.bySymbol.get(symbol);
+    if (!info) {
+      throw new Error(`Unknown token symbol: ${symbol}`);
+    }
+    return info.mint;
+  }
+
+  forMint(mint: PublicKey | string): TokenInfo | undefined {
+    return this.byMint.get(typeof mint === 'string' ? mint : mint.toBase58());
+  }
+
+  all(): TokenInfo[] {
+    return Array.from(this.bySymbol.values());
+  }
+
+  /**
+   * Register symbols missing from the built-ins: cache file first, then Jupiter's verified list
+   * Newly fetched tokens are written back to the cache file
+   * @returns Symbols that are still unknown
+   */
+  async resolve(symbols: string[], jupiter: JupiterClient, cacheFile?: string): Promise<string[]> {
+    let missing = symbols.filter(s => !this.bySymbol.has(s));
+    if (missing.length === 0) return [];
+
+    const cached = cacheFile ? readCache(cacheFile) : [];
+    for (const token of cached) {
+      if (missing.includes(token.symbol)) this.add(token);
+    }
+    missing = missing.filter(s => !this.bySymbol.has(s));
+    if (missing.length === 0) return [];
+
+    try {
+      const list = await jupiter.getTokenList();
+      for (const symbol of missing) {
+        // The list is ordered by Jupiter's ranking, so the first match is the canonical token
+        const entry = list.find(t => t.symbol === symbol);
+        if (entry) {
+          this.add({ symbol, mint: new PublicKey(entry.address), decimals: entry.decimals });
+          logger.info(`[Tokens] Resolved ${symbol} to ${entry.address} (${entry.decimals} decimals)`);
+        }
+      }
+    } catch (e) {
+      logger.warn(`[Tokens] Jupiter token list unavailable: ${e}`);
+    }
+
+    if (cacheFile) {
+      const builtin = new Set(BUILTIN_TOKENS.map(([symbol]) => symbol));
+      writeCache(cacheFile, this.all().filter(t => !builtin.has(t.symbol)));
+    }
+    return missing.filter(s => !this.bySymbol.has(s));
+  }
+
+  private add(token: TokenInfo): void {
+    this.bySymbol.set(token.symbol, token);
+    this.byMint.set(token.mint.toBase58(), token);
+  }
+}
+
+function readCache(file: string): TokenInfo[] {
+  if (!fs.existsSync(file)) return [];
+  try {
+    const entries = JSON.parse(fs.readFileSync(file, 'utf-8')) as { symbol: string; mint: string; decimals: number }[];
+    return entries.map(e => ({ symbol: e.symbol, mint: new PublicKey(e.mint), decimals: e.decimals }));
+  } catch (e) {
+    logger.warn(`[Tokens] Ignoring unreadable token cache ${file}: ${e}`);
+    return [];
+  }
+}
+
+function writeCache(file: string, tokens: TokenInfo[]): void {
+  try {
+    fs.writeFileSync(file, JSON.stringify(
+      tokens.map(t => ({ symbol: t.symbol, mint: t.mint.toBase58(), decimals: t.decimals })),
+      null,
+      2
+    ));
+  } catch (e) {
+    logger.warn(`[Tokens] Failed to write token cache ${file}: ${e}`);
+  }
+}
+
+/**
+ * Process-wide registry
+ */
+export const tokenRegistry = new TokenRegistry();
+
+/**
+ * Human-readable amount from base units, e.g. "1,234.56 USDC"
+ * Falls back to raw base units for unregistered mints
+ */
+export function formatTokenAmount(baseUnits: bigint | number, token: string | PublicKey): string {
+  const info = tokenRegistry.get(token.toString()) ?? tokenRegistry.forMint(token);
+  if (!info) {
+    return `${baseUnits.toString()} base units of ${token.toString().substring(0, 8)}`;
+  }
+  const amount = Number(baseUnits) / Math.pow(10, info.decimals);
+  return `${amount.toLocaleString('en-US', { maximumFractionDigits: Math.min(info.decimals, 6) })} ${info.symbol}`;
+}
@@ -0,0 +1,88 @@
+/**
+ * Bounded async queue
+ * Producers wait when the queue is full (backpressure); consumers wait when it's empty
+ * With a comparator, pop() returns the highest-priority item instead of the oldest
+ */
+
+export class BoundedQueue<T> {
+  private items: T[] = [];
+  private capacity: number;
+  private compare: ((a: T, b: T) => number) | null;
+  private closed = false;
+  private waitingConsumers: ((item: T | null) => void)[] = [];
+  private waitingProducers: (() => void)[] = [];
+
+  /**
+   * @param compare Negative when `a` should be popped before `b`; equal items stay FIFO
+   */
+  constructor(capacity: number, compare?: (a: T, b: T) => number) {
+    this.capacity = capacity;
+    this.compare = compare ?? null;
+  }
+
+  get size(): number {
+    return this.items.length;
+  }
+
+  /**
+   * Add an item, waiting for space if the queue is full
+   * Items pushed after close() are dropped
+   */
+  async push(item: T): Promise<void> {
+    while (!this.closed && this.items.length >= this.capacity) {
+      await new Promise<void>(resolve => this.waitingProducers.push(resolve));
+    }
+    if (this.closed) return;
+
+    const consumer = this.waitingConsumers.shift();
+    if (consumer) {
+      consumer(item);
+    } else if (this.compare) {
+      const index = this.items.findIndex(queued => this.compare!(item, queued) < 0);
+      this.items.splice(index === -1 ? this.items.length : index, 0, item);
+    } else {
+      this.items.push(item);
+    }
+  }
+
+  /**
+   * Take the next item, waiting if empty
+   * @returns null once the queue is closed and drained
+   */
+  async pop(): Promise<T | null> {
+    const item = this.items.shift();
+    if (item !== undefined) {
+      this.waitingProducers.shift()?.();
+      return item;
+    }
+    if (this.closed) {
+      return null;
+    }
+    return new Promise<T | null>(resolve => this.waitingConsumers.push(resolve));
+  }
+
+  /**
+   * Drop everything queued (waiting producers are released)
+   * @returns How many items were dropped
+   */
+  clear(): number {
+    const dropped = this.items.splice(0).length;
+    for (const producer of this.waitingProducers.splice(0)) {
+      producer();
+    }
+    return dropped;
+  }
+
+  /**
+   * Stop accepting items; consumers drain what's left, then get null
+   */
+  close(): void {
+    this.closed = true;
+    for (const consumer of this.waitingConsumers.splice(0)) {
+      consumer(null);
+    }
+    for (const producer of this.waitingProducers.splice(0)) {
+      producer();
+    }
+  }
+}
@@ -0,0 +1,120 @@
+/**
+ * Transaction send + confirmation loop
+ * Sends with RPC retries disabled, rebroadcasts on our own schedule and polls signature
+ * status, so a dropped transaction is detected as soon as its blockhash (or durable nonce) is used up
+ */
+
+import { Commitment, Connection, NonceAccount, PublicKey, TransactionError, VersionedTransaction } from '@solana/web3.js';
+
+const POLL_INTERVAL_MS = 400;
+const REBROADCAST_INTERVAL_MS = 2000;
+
+export interface SendOptions {
+  skipPreflight: boolean;
+  // Status that counts as landed (processed, confirmed or finalized)
+  commitment: Commitment;
+  // Block height after which the transaction's blockhash is expired
+  lastValidBlockHeight: number;
+  // Durable nonce the transaction uses; expiry is the nonce advancing instead of the block height
+  nonce?: { account: PublicKey; value: string };
+  timeoutMs: number;
+  // Extra sends while waiting (0 = send once)
+  maxRebroadcasts: number;
+}
+
+/**
+ * confirmed: landed without error at the requested commitment
+ * failed: landed with an error
+ * dropped: blockhash expired (or nonce advanced) without the transaction landing
+ * timeout: still unknown when timeoutMs elapsed
+ */
+export type SendStatus = 'confirmed' | 'failed' | 'dropped' | 'timeout';
+
+export interface SendResult {
+  signature: string;
+  status: SendStatus;
+  err?: TransactionError;
+  // When the first send was accepted by the RPC
+  submittedAt: number;
+  // When the landed status was observed, and the slot it landed in
+  confirmedAt?: number;
+  slot?: number;
+}
+
+const COMMITMENT_RANK: Record<string, number> = { processed: 0, confirmed: 1, finalized: 2 };
+
+function sleep(ms: number): Promise<void> {
+  return new Promise(resolve => setTimeout(resolve, ms));
+}
+
+/**
+ * True once the nonce account holds a different nonce than the transaction was signed with
+ */
+async function nonceAdvanced(
+  connection: Connection,
+  nonce: { account: PublicKey; value: string },
+  commitment: Commitment
+): Promise<boolean> {
+  const info = await connection.getAccountInfo(nonce.account, commitment);
+  return !info || NonceAccount.fromAccountData(info.data).nonce !== nonce.value;
+}
+
+/**
+ * Send a signed transaction and wait until it lands, fails, expires or times out
+ */
+export async function sendAndConfirm(
+  connection: Connection,
+  transaction: VersionedTransaction,
+  options: SendOptions
+): Promise<SendResult> {
+  const raw = transaction.serialize();
+  const signature = await connection.sendRawTransaction(raw, {
+    skipPreflight: options.skipPreflight,
+    preflightCommitment: options.commitment,
+    maxRetries: 0,
+  });
+  const submittedAt = Date.now();
+
+  const required = COMMITMENT_RANK[options.commitment] ?? COMMITMENT_RANK.confirmed;
+  const deadline = Date.now() + options.timeoutMs;
+  let lastBroadcast = Date.now();
+  let rebroadcasts = 0;
+
+  while (Date.now() < deadline) {
+    await sleep(POLL_INTERVAL_MS);
+
+    const { value: [status] } = await connection.getSignatureStatuses([signature]);
+    if (status) {
+      if (status.err) {
+        return { signature, status: 'failed', err: status.err, submittedAt, confirmedAt: Date.now(), slot: status.slot };
+      }
+      if (COMMITMENT_RANK[status.confirmationStatus ?? 'processed'] >= required) {
+        return { signature, status: 'confirmed', submittedAt, confirmedAt: Date.now(), slot: status.slot };
+      }
+      // Landed but not at the requested commitment yet: no need to rebroadcast
+      continue;
+    }
+
+    // Not seen by the cluster: expired blockhash means it can never land
+    if (options.nonce) {
+      // Our own transaction advances the nonce too, so re-check the signature before giving up
+      if (await nonceAdvanced(connection, options.nonce, options.commitment)) {
+        const { value: [landed] } = await connection.getSignatureStatuses([signature]);
+        if (!landed) {
+          return { signature, status: 'dropped', submittedAt };
+        }
+        continue;
+      }
+    } else if (await connection.getBlockHeight(options.commitment) > options.lastValidBlockHeight) {
+      return { signature, status: 'dropped', submittedAt };
+    }
+
+    if (rebroadcasts < options.maxRebroadcasts && Date.now() - lastBroadcast >= REBROADCAST_INTERVAL_MS) {
+      await connection.sendRawTransaction(raw, { skipPreflight: true, maxRetries: 0 }).catch(() => {});
+      lastBroadcast = Date.now();
+      rebroadcasts++;
+    }
+  }
+
+  return { signature, status: 'timeout', submittedAt };
+}
@@ -0,0 +1,133 @@
+/**
+ * HTTP helper for DEX/aggregator APIs
+ * Adds request timeouts, retry with backoff, Retry-After handling and typed errors
+ */
+
+export type ApiErrorKind = 'timeout' | 'rate_limited' | 'server' | 'no_route' | 'client' | 'network';
+
+/**
+ * Typed API error so callers can branch on "no route" vs "rate limited"
+ */
+export class ApiError extends Error {
+  readonly kind: ApiErrorKind;
+  readonly status?: number;
+  readonly retryAfterMs?: number;
+
+  constructor(kind: ApiErrorKind, message: string, status?: number, retryAfterMs?: number) {
+    super(message);
+    this.name = 'ApiError';
+    this.kind = kind;
+    this.status = status;
+    this.retryAfterMs = retryAfterMs;
+  }
+
+  get retryable(): boolean {
+    return this.kind === 'timeout' || this.kind === 'rate_limited' || this.kind === 'server' || this.kind === 'network';
+  }
+}
+
+export interface HttpOptions {
+  timeoutMs: number;
+  maxRetries: number;
+  baseDelayMs: number;
+}
+
+let defaults: HttpOptions = {
+  timeoutMs: 5000,
+  maxRetries: 3,
+  baseDelayMs: 250,
+};
+
+/**
+ * Override default timeout/retry settings for all requests
+ */
+export function configureHttp(options: Partial<HttpOptions>): void {
+  defaults = { ...defaults, ...options };
+}
+
+function sleep(ms: number): Promise<void> {
+  return new Promise(resolve => setTimeout(resolve, ms));
+}
+
+/**
+ * Parse a Retry-After header (seconds or HTTP date) into milliseconds
+ */
+export function parseRetryAfter(header: string | null, now = Date.now()): number | undefined {
+  if (!header) return undefined;
+  const seconds = Number(header);
+  if (!isNaN(seconds)) return seconds * 1000;
+  const date = Date.parse(header);
+  return isNaN(date) ? undefined : Math.max(0, date - now);
+}
+
+/**
+ * Classify a non-OK response
+ */
+export async function toApiError(response: Response): Promise<ApiError> {
+  const body = await response.text().catch(() => '');
+
+  if (response.status === 429) {
+    return new ApiError('rate_limited', `HTTP 429: ${body}`, 429, parseRetryAfter(response.headers.get('retry-after')));
+  }
+  if (response.status >= 500) {
+    return new ApiError('server', `HTTP ${response.status}: ${body}`, response.status);
+  }
+  if (/route/i.test(body)) {
+    return new ApiError('no_route', `No route: ${body}`, response.status);
+  }
+  return new ApiError('client', `HTTP ${response.status}: ${body}`, response.status);
+}
+
+/**
+ * Single request with a timeout
+ */
+async function fetchOnce(url: string, init: RequestInit, timeoutMs: number): Promise<Response> {
+  const controller = new AbortController();
+  const timer = setTimeout(() => controller.abort(), timeoutMs);
+
+  try {
+    const response = await fetch(url, { ...init, signal: controller.signal });
+    if (!response.ok) {
+      throw await toApiError(response);
+    }
+    return response;
+  } catch (e) {
+    if (e instanceof ApiError) throw e;
+    if ((e as Error).name === 'AbortError') {
+      throw new ApiError('timeout', `Request timed out after ${timeoutMs}ms`);
+    }
+    throw new ApiError('network', String(e));
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+/**
+ * Fetch JSON with timeout and retry
+ * Retries timeouts, 5xx and 429 (honoring Retry-After) with exponential backoff;
+ * other errors are thrown immediately. No wait is longer than timeoutMs: a 429 asking for
+ * more is thrown as rate_limited so the caller can move on
+ */
+export async function fetchJson<T = any>(
+  url: string,
+  init: RequestInit = {},
+  options: Partial<HttpOptions> = {}
+): Promise<T> {
+  const { timeoutMs, maxRetries, baseDelayMs } = { ...defaults, ...options };
+
+  for (let attempt = 0; ; attempt++) {
+    try {
+      const response = await fetchOnce(url, init, timeoutMs);
+      return await response.json() as T;
+    } catch (e) {
+      const error = e instanceof ApiError ? e : new ApiError('network', String(e));
+      if (!error.retryable || attempt >= maxRetries) {
+        throw error;
+      }
+      if (error.retryAfterMs !== undefined && error.retryAfterMs > timeoutMs) {
+        throw error;
+      }
+      await sleep(Math.min(error.retryAfterMs ?? baseDelayMs * Math.pow(2, attempt), timeoutMs));
+    }
+  }
+}
@@ -0,0 +1,71 @@
+/**
+ * Buffered JSON-lines writer
+ * Entries are kept in memory and appended to the file in batches
+ */
+
+import fs from 'fs';
+import { logger } from './logger.js';
+
+const DEFAULT_FLUSH_EVERY = 100;
+
+export class LogWriter {
+  private path: string;
+  private flushEvery: number;
+  private buffer: string[] = [];
+
+  constructor(path: string, flushEvery = DEFAULT_FLUSH_EVERY) {
+    this.path = path;
+    this.flushEvery = flushEvery;
+  }
+
+  /**
+   * Queue one entry, flushing once the batch is full
+   */
+  write(entry: object): void {
+    this.buffer.push(JSON.stringify(entry));
+    if (this.buffer.length >= this.flushEvery) {
+      this.flush();
+    }
+  }
+
+  /**
+   * Last `limit` entries, newest first (file plus anything still buffered)
+   */
+  tail(limit: number): unknown[] {
+    let lines: string[] = [];
+    try {
+      if (fs.existsSync(this.path)) {
+        lines = fs.readFileSync(this.path, 'utf-8').split('\n').filter(line => line.length > 0);
+      }
+    } catch (e) {
+      logger.warn(`[LogWriter] Failed to read ${this.path}: ${e}`);
+    }
+    return [...lines, ...this.buffer]
+      .slice(-limit)
+      .reverse()
+      .map(line => {
+        try {
+          return JSON.parse(line);
+        } catch {
+          return null;
+        }
+      })
+      .filter(entry => entry !== null);
+  }
+
+  /**
+   * Append buffered entries to the file
+   * Synchronous so it can run from shutdown handlers
+   */
+  flush(): void {
+    if (this.buffer.length === 0) return;
+
+    const lines = this.buffer.join('\n') + '\n';
+    this.buffer = [];
+    try {
+      fs.appendFileSync(this.path, lines);
+    } catch (e) {
+      logger.warn(`[LogWriter] Failed to write ${this.path}: ${e}`);
+    }
+  }
+}
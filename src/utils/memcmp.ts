@@ -0,0 +1,42 @@
+/**
+ * getProgramAccounts filters
+ * Builds memcmp filters from Anchor layout positions instead of hand-written offsets
+ */
+
+import { GetProgramAccountsFilter } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import bs58 from 'bs58';
+
+// Anchor accounts start with an 8-byte discriminator
+export const DISCRIMINATOR_LENGTH = 8;
+
+/**
+ * Anchor account discriminator: first 8 bytes of sha256("account:<Name>")
+ */
+export function accountDiscriminator(accountName: string): Buffer {
+  return createHash('sha256').update(`account:${accountName}`).digest().subarray(0, DISCRIMINATOR_LENGTH);
+}
+
+/**
+ * Match bytes at an absolute offset
+ */
+export function offsetFilter(offset: number, bytes: Uint8Array): GetProgramAccountsFilter {
+  return { memcmp: { offset, bytes: bs58.encode(bytes) } };
+}
+
+/**
+ * Match accounts of one Anchor type (offset 0)
+ */
+export function discriminatorFilter(discriminator: Uint8Array): GetProgramAccountsFilter {
+  if (discriminator.length !== DISCRIMINATOR_LENGTH) {
+    throw new Error(`Discriminator must be ${DISCRIMINATOR_LENGTH} bytes, got ${discriminator.length}`);
+  }
+  return offsetFilter(0, discriminator);
+}
+
+/**
+ * Match the first field after the discriminator (e.g. an account's parent market)
+ */
+export function afterDiscriminatorFilter(bytes: Uint8Array): GetProgramAccountsFilter {
+  return offsetFilter(DISCRIMINATOR_LENGTH, bytes);
+}
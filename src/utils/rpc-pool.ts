@@ -0,0 +1,122 @@
+/**
+ * RPC endpoint pool with failover
+ * Plugged into Connection as its fetch implementation: requests go to the active
+ * endpoint and rotate to the next one on 429, 5xx, timeouts and network errors
+ */
+
+import { logger } from './logger.js';
+
+// Error rates are measured over this window
+const ERROR_WINDOW_MS = 60_000;
+
+// Endpoints failing more than this share of recent requests are tried last
+const MAX_ERROR_RATE = 0.5;
+
+// Need a few samples before an error rate means anything
+const MIN_SAMPLES = 5;
+
+interface EndpointStats {
+  url: string;
+  results: { at: number; ok: boolean }[];
+}
+
+export class RpcPool {
+  private endpoints: EndpointStats[];
+  private activeIndex = 0;
+  private timeoutMs: number;
+  private failovers = 0;
+
+  constructor(urls: string[], timeoutMs: number = 30_000) {
+    if (urls.length === 0) {
+      throw new Error('RpcPool needs at least one endpoint');
+    }
+    this.endpoints = urls.map(url => ({ url, results: [] }));
+    this.timeoutMs = timeoutMs;
+  }
+
+  /**
+   * Endpoint requests currently go to
+   */
+  activeEndpoint(): string {
+    return this.endpoints[this.activeIndex].url;
+  }
+
+  getStats(): { active: string; failovers: number; errorRates: Record<string, number> } {
+    const errorRates: Record<string, number> = {};
+    for (const endpoint of this.endpoints) {
+      errorRates[endpoint.url] = this.errorRate(endpoint);
+    }
+    return { active: this.activeEndpoint(), failovers: this.failovers, errorRates };
+  }
+
+  /**
+   * Drop-in fetch for ConnectionConfig.fetch; the url argument is replaced by the pool's endpoint
+   */
+  fetch = async (_input: any, init?: any): Promise<Response> => {
+    let lastError: unknown;
+
+    for (const index of this.attemptOrder()) {
+      const endpoint = this.endpoints[index];
+      const controller = new AbortController();
+      const timer = setTimeout(() => controller.abort(), this.timeoutMs);
+
+      try {
+        const response = await fetch(endpoint.url, { ...init, signal: controller.signal });
+        if (response.status === 429 || response.status >= 500) {
+          lastError = new Error(`HTTP ${response.status} from ${endpoint.url}`);
+          this.record(endpoint, false);
+          this.rotateFrom(index);
+          continue;
+        }
+        this.record(endpoint, true);
+        return response;
+      } catch (e) {
+        lastError = e;
+        this.record(endpoint, false);
+        this.rotateFrom(index);
+      } finally {
+        clearTimeout(timer);
+      }
+    }
+
+    throw lastError;
+  };
+
+  /**
+   * Active endpoint first, then the rest round-robin; unhealthy endpoints go last
+   */
+  private attemptOrder(): number[] {
+    const n = this.endpoints.length;
+    const order = Array.from({ length: n }, (_, i) => (this.activeIndex + i) % n);
+    const healthy = order.filter(i => this.errorRate(this.endpoints[i]) <= MAX_ERROR_RATE);
+    const unhealthy = order.filter(i => this.errorRate(this.endpoints[i]) > MAX_ERROR_RATE);
+    return [...healthy, ...unhealthy];
+  }
+
+  private rotateFrom(failedIndex: number): void {
+    if (this.endpoints.length < 2 || failedIndex !== this.activeIndex) {
+      return;
+    }
+    const next = this.attemptOrder().find(i => i !== failedIndex) ?? failedIndex;
+    this.activeIndex = next;
+    this.failovers++;
+    logger.warn(`[RPC] Failing over to ${this.endpoints[next].url.substring(0, 50)}`);
+  }
+
+  private record(endpoint: EndpointStats, ok: boolean): void {
+    const now = Date.now();
+    endpoint.results.push({ at: now, ok });
+    while (endpoint.results.length > 0 && now - endpoint.results[0].at > ERROR_WINDOW_MS) {
+      endpoint.results.shift();
+    }
+  }
+
+  private errorRate(endpoint: EndpointStats): number {
+    const now = Date.now();
+    const recent = endpoint.results.filter(r => now - r.at <= ERROR_WINDOW_MS);
+    if (recent.length < MIN_SAMPLES) {
+      return 0;
+    }
+    return recent.filter(r => !r.ok).length / recent.length;
+  }
+}
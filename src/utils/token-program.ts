@@ -0,0 +1,83 @@
+/**
+ * Token program detection
+ * Mints can be owned by the classic SPL Token program or Token-2022; ATAs, account
+ * creation and transfer-fee handling depend on which one
+ */
+
+import { Connection, PublicKey } from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getMint,
+  getTransferFeeConfig,
+} from '@solana/spl-token';
+
+export interface MintInfo {
+  programId: PublicKey;
+  decimals: number;
+  // Token-2022 transfer fee (0 for classic mints)
+  transferFeeBps: number;
+}
+
+// Mint owners never change, so entries live for the process lifetime
+const mintCache: Map<string, Promise<MintInfo>> = new Map();
+
+/**
+ * Owning token program, decimals and transfer fee for a mint (cached)
+ */
+export function getMintInfo(connection: Connection, mint: PublicKey): Promise<MintInfo> {
+  const key = mint.toBase58();
+  let cached = mintCache.get(key);
+  if (!cached) {
+    cached = loadMintInfo(connection, mint);
+    // Don't cache failures
+    cached.catch(() => mintCache.delete(key));
+    mintCache.set(key, cached);
+  }
+  return cached;
+}
+
+async function loadMintInfo(connection: Connection, mint: PublicKey): Promise<MintInfo> {
+  const account = await connection.getAccountInfo(mint);
+  if (!account) {
+    throw new Error(`Mint not found: ${mint.toBase58()}`);
+  }
+
+  const programId = account.owner;
+  const parsed = await getMint(connection, mint, 'confirmed', programId);
+  const transferFee = programId.equals(TOKEN_2022_PROGRAM_ID) ? getTransferFeeConfig(parsed) : null;
+
+  return {
+    programId,
+    decimals: parsed.decimals,
+    transferFeeBps: transferFee ? transferFee.newerTransferFee.transferFeeBasisPoints : 0,
+  };
+}
+
+export function isToken2022(info: MintInfo): boolean {
+  return info.programId.equals(TOKEN_2022_PROGRAM_ID);
+}
+
+// Derived ATAs, keyed by owner/program/mint; findProgramAddressSync hashes up to 255 bumps
+const ataCache: Map<string, PublicKey> = new Map();
+
+/**
+ * Associated token account for the given token program (cached)
+ */
+export function getAtaForProgram(
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = TOKEN_PROGRAM_ID
+): PublicKey {
+  const key = `${owner.toBase58()}:${programId.toBase58()}:${mint.toBase58()}`;
+  let ata = ataCache.get(key);
+  if (!ata) {
+    [ata] = PublicKey.findProgramAddressSync(
+      [owner.toBuffer(), programId.toBuffer(), mint.toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    ataCache.set(key, ata);
+  }
+  return ata;
+}
@@ -0,0 +1,53 @@
+/**
+ * Tracing
+ * OpenTelemetry spans around scans and executions; exported over OTLP/HTTP (Jaeger, Grafana Tempo)
+ * when an endpoint is configured, otherwise the API's no-op tracer makes spans free
+ */
+
+import { trace, Span, SpanStatusCode, Attributes } from '@opentelemetry/api';
+import { NodeTracerProvider, BatchSpanProcessor } from '@opentelemetry/sdk-trace-node';
+import { OTLPTraceExporter } from '@opentelemetry/exporter-trace-otlp-http';
+import { Resource } from '@opentelemetry/resources';
+import { logger } from './logger.js';
+
+const SERVICE_NAME = 'flash-loan-arbitrage-bot';
+
+let provider: NodeTracerProvider | null = null;
+
+/**
+ * Export spans to an OTLP/HTTP collector, e.g. http://localhost:4318/v1/traces
+ */
+export function initTracing(otlpEndpoint: string): void {
+  if (provider) return;
+  provider = new NodeTracerProvider({
+    resource: new Resource({ 'service.name': SERVICE_NAME }),
+  });
+  provider.addSpanProcessor(new BatchSpanProcessor(new OTLPTraceExporter({ url: otlpEndpoint })));
+  provider.register();
+  logger.info(`[Tracing] Exporting spans to ${otlpEndpoint}`);
+}
+
+/**
+ * Flush pending spans (shutdown)
+ */
+export async function shutdownTracing(): Promise<void> {
+  await provider?.shutdown().catch(e => logger.warn(`[Tracing] Flush failed: ${e}`));
+  provider = null;
+}
+
+/**
+ * Run fn inside an active span; thrown errors mark the span failed and are rethrown
+ */
+export async function withSpan<T>(name: string, attributes: Attributes, fn: (span: Span) => Promise<T>): Promise<T> {
+  return trace.getTracer(SERVICE_NAME).startActiveSpan(name, { attributes }, async span => {
+    try {
+      return await fn(span);
+    } catch (e) {
+      span.recordException(e as Error);
+      span.setStatus({ code: SpanStatusCode.ERROR, message: String(e) });
+      throw e;
+    } finally {
+      span.end();
+    }
+  });
+}
@@ -0,0 +1,47 @@
+/**
+ * Watchdog
+ * Exits the process when the main loop stops heartbeating (e.g. an await that never resolves),
+ * so a supervisor like PM2 restarts the bot instead of leaving it silently stuck
+ */
+
+import { logger } from './logger.js';
+
+const CHECK_INTERVAL_MS = 30_000;
+
+export class Watchdog {
+  private stallMs: number;
+  private lastHeartbeat = Date.now();
+  private timer: NodeJS.Timeout | null = null;
+
+  /**
+   * @param stallSeconds Seconds without a heartbeat before exiting (0 = disabled)
+   */
+  constructor(stallSeconds: number) {
+    this.stallMs = stallSeconds * 1000;
+  }
+
+  heartbeat(): void {
+    this.lastHeartbeat = Date.now();
+  }
+
+  start(): void {
+    if (this.stallMs === 0 || this.timer) return;
+    this.heartbeat();
+    this.timer = setInterval(() => {
+      const silentMs = Date.now() - this.lastHeartbeat;
+      if (silentMs > this.stallMs) {
+        logger.error(`[Watchdog] Main loop stalled for ${Math.round(silentMs / 1000)}s, exiting for restart`);
+        process.exit(1);
+      }
+    }, CHECK_INTERVAL_MS);
+    // Never keep the process alive on its own
+    this.timer.unref();
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = null;
+    }
+  }
+}
@@ -0,0 +1,48 @@
+/**
+ * Wrapped SOL helpers
+ * Swaps touching SOL run through the wSOL ATA: create + sync before, close (unwrap) after
+ */
+
+import { PublicKey, SystemProgram, TransactionInstruction } from '@solana/web3.js';
+import {
+  NATIVE_MINT,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createSyncNativeInstruction,
+  createCloseAccountInstruction,
+} from '@solana/spl-token';
+import { getAtaForProgram } from './token-program.js';
+
+export const WSOL_MINT = NATIVE_MINT;
+
+export function isWsol(mint: PublicKey): boolean {
+  return mint.equals(WSOL_MINT);
+}
+
+/**
+ * wSOL associated token account for an owner
+ */
+export function getWsolAta(owner: PublicKey): PublicKey {
+  return getAtaForProgram(WSOL_MINT, owner);
+}
+
+/**
+ * Create the wSOL ATA if needed, optionally fund it with native SOL, then sync
+ */
+export function buildWrapSolInstructions(owner: PublicKey, lamports: number = 0): TransactionInstruction[] {
+  const ata = getWsolAta(owner);
+  const instructions = [
+    createAssociatedTokenAccountIdempotentInstruction(owner, ata, owner, WSOL_MINT),
+  ];
+  if (lamports > 0) {
+    instructions.push(SystemProgram.transfer({ fromPubkey: owner, toPubkey: ata, lamports }));
+  }
+  instructions.push(createSyncNativeInstruction(ata));
+  return instructions;
+}
+
+/**
+ * Close the wSOL ATA, returning its balance and rent to the owner as native SOL
+ */
+export function buildUnwrapSolInstructions(owner: PublicKey): TransactionInstruction[] {
+  return [createCloseAccountInstruction(getWsolAta(owner), owner, owner)];
+}
@@ -0,0 +1,63 @@
+/**
+ * Wallet Inventory
+ * SOL and every token account the wallet owns, with symbols from the token registry and the rent
+ * each account holds (`balance` command)
+ */
+
+import { Connection, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { logger } from './utils/logger.js';
+import { tokenRegistry } from './token-registry.js';
+
+export interface TokenHolding {
+  symbol: string | null;
+  mint: string;
+  account: string;
+  uiAmount: number;
+  rentLamports: number;
+  // Empty: closing the account returns its rent
+  closable: boolean;
+}
+
+export interface WalletInventory {
+  owner: string;
+  solLamports: number;
+  tokens: TokenHolding[];
+}
+
+export async function fetchWalletInventory(connection: Connection, owner: PublicKey): Promise<WalletInventory> {
+  const solLamports = await connection.getBalance(owner, 'confirmed');
+  const tokens: TokenHolding[] = [];
+  for (const programId of [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]) {
+    const { value } = await connection.getParsedTokenAccountsByOwner(owner, { programId }, 'confirmed');
+    for (const { pubkey, account } of value) {
+      const info = account.data.parsed.info;
+      tokens.push({
+        symbol: tokenRegistry.forMint(info.mint)?.symbol ?? null,
+        mint: info.mint,
+        account: pubkey.toBase58(),
+        uiAmount: info.tokenAmount.uiAmount ?? 0,
+        rentLamports: account.lamports,
+        closable: info.tokenAmount.amount === '0',
+      });
+    }
+  }
+  // Known tokens first, then by balance
+  tokens.sort((a, b) => Number(b.symbol !== null) - Number(a.symbol !== null) || b.uiAmount - a.uiAmount);
+  return { owner: owner.toBase58(), solLamports, tokens };
+}
+
+export function logWalletInventory(inventory: WalletInventory): void {
+  logger.info(`Wallet ${inventory.owner}: ${(inventory.solLamports / LAMPORTS_PER_SOL).toFixed(6)} SOL`);
+  for (const t of inventory.tokens) {
+    logger.info(
+      `   ${(t.symbol ?? t.mint.substring(0, 8)).padEnd(8)} ${t.uiAmount.toLocaleString('en-US', { maximumFractionDigits: 6 }).padStart(20)}` +
+      `  mint ${t.mint}  account ${t.account}  rent ${(t.rentLamports / LAMPORTS_PER_SOL).toFixed(6)} SOL${t.closable ? '  (empty, closable)' : ''}`
+    );
+  }
+  const closable = inventory.tokens.filter(t => t.closable);
+  if (closable.length > 0) {
+    const rent = closable.reduce((sum, t) => sum + t.rentLamports, 0);
+    logger.info(`${closable.length} empty token account(s) could be closed for ${(rent / LAMPORTS_PER_SOL).toFixed(6)} SOL (npm run sweep -- --close)`);
+  }
+}
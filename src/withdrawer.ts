@@ -0,0 +1,153 @@
+/**
+ * Profit Withdrawer
+ * Moves SOL and USDC above the hot wallet's target balance to a cold wallet,
+ * so the bot's VPS never holds more than it needs to trade
+ */
+
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import {
+  createAssociatedTokenAccountIdempotentInstruction,
+  createTransferCheckedInstruction,
+  getAssociatedTokenAddressSync,
+} from '@solana/spl-token';
+import { logger } from './utils/logger.js';
+import { tokenRegistry } from './token-registry.js';
+
+const USDC_MINT = tokenRegistry.mint('USDC');
+const USDC_DECIMALS = tokenRegistry.get('USDC')!.decimals;
+
+export interface WithdrawOptions {
+  destination: PublicKey;
+  // SOL kept in the hot wallet for fees, tips and rent
+  targetSol: number;
+  // Withdraw only once the balance exceeds the target by this much
+  marginSol: number;
+  // USDC kept in the hot wallet (null = never withdraw USDC)
+  targetUsdc: number | null;
+  marginUsdc: number;
+  // Only log what would be withdrawn
+  dryRun: boolean;
+}
+
+export interface WithdrawResult {
+  sol: number;
+  usdc: number;
+  signature?: string;
+}
+
+export class ProfitWithdrawer {
+  private connection: Connection;
+  private keypair: Keypair;
+  private options: WithdrawOptions;
+  // True while a trade or sweep is moving the wallet's balances
+  private isBusy: () => boolean;
+  // Withdrawal in progress, if any
+  private current: Promise<WithdrawResult> | null = null;
+
+  constructor(connection: Connection, keypair: Keypair, options: WithdrawOptions, isBusy: () => boolean = () => false) {
+    if (options.destination.equals(keypair.publicKey)) {
+      throw new Error('Profit withdraw address must differ from the hot wallet');
+    }
+    this.connection = connection;
+    this.keypair = keypair;
+    this.options = options;
+    this.isBusy = isBusy;
+  }
+
+  /**
+   * Resolves once no withdrawal is in progress (for shutdown)
+   */
+  async idle(): Promise<void> {
+    await this.current?.catch(() => {});
+  }
+
+  /**
+   * Withdraw any excess above the targets in a single transaction
+   * @returns Amounts withdrawn (zero when nothing exceeded its margin or the wallet was busy)
+   */
+  async withdrawExcess(): Promise<WithdrawResult> {
+    if (this.current) return { sol: 0, usdc: 0 };
+    this.current = this.withdraw();
+    try {
+      return await this.current;
+    } finally {
+      this.current = null;
+    }
+  }
+
+  private async withdraw(): Promise<WithdrawResult> {
+    const none: WithdrawResult = { sol: 0, usdc: 0 };
+    if (this.isBusy()) {
+      logger.debug('[Withdraw] Trade or sweep in flight, skipping');
+      return none;
+    }
+
+    const owner = this.keypair.publicKey;
+    const instructions: TransactionInstruction[] = [];
+
+    // USDC first: creating the destination ATA costs rent from the SOL we keep
+    let usdc = 0;
+    if (this.options.targetUsdc !== null) {
+      const sourceAta = getAssociatedTokenAddressSync(USDC_MINT, owner);
+      const balance = await this.connection.getTokenAccountBalance(sourceAta).catch(() => null);
+      const held = balance?.value.uiAmount ?? 0;
+      if (held > this.options.targetUsdc + this.options.marginUsdc) {
+        usdc = held - this.options.targetUsdc;
+        const destinationAta = getAssociatedTokenAddressSync(USDC_MINT, this.options.destination, true);
+        instructions.push(
+          createAssociatedTokenAccountIdempotentInstruction(owner, destinationAta, this.options.destination, USDC_MINT),
+          createTransferCheckedInstruction(
+            sourceAta,
+            USDC_MINT,
+            destinationAta,
+            owner,
+            BigInt(Math.floor(usdc * Math.pow(10, USDC_DECIMALS))),
+            USDC_DECIMALS
+          )
+        );
+      }
+    }
+
+    let sol = 0;
+    const lamports = await this.connection.getBalance(owner, 'confirmed');
+    const targetLamports = Math.floor(this.options.targetSol * LAMPORTS_PER_SOL);
+    if (lamports > targetLamports + this.options.marginSol * LAMPORTS_PER_SOL) {
+      const excess = lamports - targetLamports;
+      sol = excess / LAMPORTS_PER_SOL;
+      instructions.push(SystemProgram.transfer({ fromPubkey: owner, toPubkey: this.options.destination, lamports: excess }));
+    }
+
+    if (instructions.length === 0) {
+      return none;
+    }
+
+    const summary = `${sol.toFixed(4)} SOL, ${usdc.toFixed(2)} USDC → ${this.options.destination.toBase58()}`;
+    if (this.options.dryRun) {
+      logger.info(`[Withdraw] [DRY RUN] Would withdraw ${summary}`);
+      return none;
+    }
+
+    const { blockhash, lastValidBlockHeight } = await this.connection.getLatestBlockhash('confirmed');
+    const message = new TransactionMessage({ payerKey: owner, recentBlockhash: blockhash, instructions }).compileToV0Message();
+    const tx = new VersionedTransaction(message);
+    tx.sign([this.keypair]);
+
+    const signature = await this.connection.sendTransaction(tx);
+    const confirmation = await this.connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+    if (confirmation.value.err) {
+      throw new Error(`Withdrawal failed: ${JSON.stringify(confirmation.value.err)}`);
+    }
+
+    logger.success(`[Withdraw] Withdrew ${summary}: ${signature}`);
+    return { sol, usdc, signature };
+  }
+}
@@ -0,0 +1,139 @@
+/**
+ * Bounded queue tests
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { BoundedQueue } from '../src/utils/bounded-queue.js';
+import { ArbitrageOpportunity, opportunityScore } from '../src/profit-calculator.js';
+import { makeOpportunity } from './support/fixtures.js';
+
+/**
+ * Whether a promise has settled once pending callbacks have run
+ */
+async function isSettled(promise: Promise<unknown>): Promise<boolean> {
+  let settled = false;
+  promise.then(() => { settled = true; }, () => { settled = true; });
+  await new Promise(resolve => setImmediate(resolve));
+  return settled;
+}
+
+describe('BoundedQueue', () => {
+  it('pops in FIFO order without a comparator', async () => {
+    const queue = new BoundedQueue<number>(5);
+    for (const n of [3, 1, 2]) {
+      await queue.push(n);
+    }
+
+    assert.equal(queue.size, 3);
+    assert.deepEqual([await queue.pop(), await queue.pop(), await queue.pop()], [3, 1, 2]);
+  });
+
+  it('makes producers wait while full and releases one per pop', async () => {
+    const queue = new BoundedQueue<string>(2);
+    await queue.push('a');
+    await queue.push('b');
+
+    const third = queue.push('c');
+    const fourth = queue.push('d');
+    assert.equal(await isSettled(third), false);
+    assert.equal(queue.size, 2);
+
+    assert.equal(await queue.pop(), 'a');
+    assert.equal(await isSettled(third), true);
+    assert.equal(await isSettled(fourth), false);
+    assert.equal(queue.size, 2);
+
+    assert.deepEqual([await queue.pop(), await queue.pop()], ['b', 'c']);
+    await fourth;
+    assert.equal(await queue.pop(), 'd');
+  });
+
+  it('hands a pushed item straight to a waiting consumer', async () => {
+    const queue = new BoundedQueue<number>(1);
+    const popped = queue.pop();
+    assert.equal(await isSettled(popped), false);
+
+    await queue.push(7);
+
+    assert.equal(await popped, 7);
+    assert.equal(queue.size, 0);
+  });
+
+  it('drains after close, then returns null and drops new items', async () => {
+    const queue = new BoundedQueue<number>(3);
+    await queue.push(1);
+
+    queue.close();
+    await queue.push(2);
+
+    assert.equal(await queue.pop(), 1);
+    assert.equal(await queue.pop(), null);
+  });
+
+  it('releases waiting consumers and producers on close', async () => {
+    const empty = new BoundedQueue<number>(1);
+    const consumer = empty.pop();
+    empty.close();
+    assert.equal(await consumer, null);
+
+    const full = new BoundedQueue<number>(1);
+    await full.push(1);
+    const producer = full.push(2);
+    full.close();
+    await producer;
+    assert.equal(full.size, 1);
+  });
+
+  it('clear drops queued items and releases waiting producers', async () => {
+    const queue = new BoundedQueue<number>(1);
+    await queue.push(1);
+    const producer = queue.push(2);
+
+    assert.equal(queue.clear(), 1);
+    await producer;
+    assert.equal(await queue.pop(), 2);
+  });
+});
+
+describe('BoundedQueue with a comparator', () => {
+  it('pops the highest-priority item first, FIFO among equals', async () => {
+    const queue = new BoundedQueue<{ id: string; priority: number }>(10, (a, b) => b.priority - a.priority);
+    for (const [id, priority] of [['a', 1], ['b', 5], ['c', 3], ['d', 5], ['e', 1]] as const) {
+      await queue.push({ id, priority });
+    }
+
+    const order: string[] = [];
+    while (queue.size > 0) {
+      order.push((await queue.pop())!.id);
+    }
+    assert.deepEqual(order, ['b', 'd', 'c', 'a', 'e']);
+  });
+
+  it('ranks opportunities by profit score like the execution queue', async () => {
+    const now = Date.now();
+    const score = (opp: ArbitrageOpportunity) => opportunityScore(opp, now, 10_000, 1, 0);
+    const queue = new BoundedQueue<ArbitrageOpportunity>(10, (a, b) => score(b) - score(a));
+    const withProfit = (pair: string, netProfit: number) => {
+      const opp = makeOpportunity({ pair });
+      return { ...opp, calculation: { ...opp.calculation, netProfit } };
+    };
+
+    await queue.push(withProfit('JUP/USDC', 12));
+    await queue.push(withProfit('SOL/USDC', 80));
+    await queue.push(withProfit('WIF/USDC', 35));
+
+    assert.deepEqual([(await queue.pop())!.pair, (await queue.pop())!.pair, (await queue.pop())!.pair], ['SOL/USDC', 'WIF/USDC', 'JUP/USDC']);
+  });
+
+  it('still applies backpressure by count, whatever the priority', async () => {
+    const queue = new BoundedQueue<number>(1, (a, b) => b - a);
+    await queue.push(1);
+
+    const urgent = queue.push(100);
+    assert.equal(await isSettled(urgent), false);
+    assert.equal(await queue.pop(), 1);
+    await urgent;
+    assert.equal(await queue.pop(), 100);
+  });
+});
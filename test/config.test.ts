@@ -0,0 +1,128 @@
+/**
+ * Config tests
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { Keypair } from '@solana/web3.js';
+import bs58 from 'bs58';
+import { applyConfigFile, configBuilder, flashLoanLimit, loadConfig, minProfitForOpportunity, validateConfig } from '../src/config.js';
+import { makeOpportunity } from './support/fixtures.js';
+
+const WALLET = bs58.encode(Keypair.generate().secretKey);
+
+describe('minProfitForOpportunity', () => {
+  const config = loadConfig({
+    MIN_PROFIT_USD: '10',
+    RAYDIUM_ORCA_MIN_PROFIT_USD: '15',
+    ARB_PAIRS: 'SOL/USDC,JUP/USDC:4',
+  }, []);
+
+  it('uses the route threshold over the global one', () => {
+    assert.equal(minProfitForOpportunity(config, makeOpportunity({ buyDex: 'raydium', sellDex: 'orca' })), 15);
+    assert.equal(minProfitForOpportunity(config, makeOpportunity({ buyDex: 'orca', sellDex: 'raydium' })), 10);
+  });
+
+  it('uses the pair threshold over the route one', () => {
+    assert.equal(minProfitForOpportunity(config, makeOpportunity({ pair: 'JUP/USDC', buyDex: 'raydium', sellDex: 'orca' })), 4);
+  });
+
+  it('filters the same profit differently per route', () => {
+    const profitable = (opp: ReturnType<typeof makeOpportunity>) => opp.calculation.netProfit >= minProfitForOpportunity(config, opp);
+    const raydiumToOrca = makeOpportunity({ buyDex: 'raydium', sellDex: 'orca' });
+    const orcaToRaydium = makeOpportunity({ buyDex: 'orca', sellDex: 'raydium' });
+    raydiumToOrca.calculation = { ...raydiumToOrca.calculation, netProfit: 12 };
+    orcaToRaydium.calculation = { ...orcaToRaydium.calculation, netProfit: 12 };
+
+    assert.equal(profitable(raydiumToOrca), false);
+    assert.equal(profitable(orcaToRaydium), true);
+  });
+});
+
+describe('flashLoanLimit', () => {
+  const config = loadConfig({ MAX_FLASH_LOAN_USD: '250000' }, []);
+
+  it('is bounded by the reserve liquidity', () => {
+    assert.equal(flashLoanLimit(config, 80_000.5), 80_000.5);
+    assert.equal(flashLoanLimit(config, 3_000_000), 250_000);
+  });
+
+  it('falls back to the configured max when the reserve is unknown', () => {
+    assert.equal(flashLoanLimit(config, 0), 250_000);
+  });
+});
+
+describe('validateConfig', () => {
+  it('accepts the defaults', () => {
+    assert.doesNotThrow(() => validateConfig(loadConfig({}, [])));
+  });
+
+  it('rejects route and pair thresholds over the sanity cap', () => {
+    assert.throws(() => validateConfig(loadConfig({ ORCA_RAYDIUM_MIN_PROFIT_USD: '150000' }, [])), /min profit for orca-raydium/);
+    assert.throws(() => validateConfig(loadConfig({ ARB_PAIRS: 'SOL/USDC:-1' }, [])), /min profit for SOL\/USDC/);
+  });
+
+  it('rejects a malformed RPC_URL or wallet key', () => {
+    assert.throws(() => validateConfig(loadConfig({ RPC_URL: 'localhost:8899' }, [])), /RPC_URL/);
+    assert.throws(() => validateConfig(loadConfig({ WALLET_PRIVATE_KEY: 'not-a-key' }, [])), /WALLET_PRIVATE_KEY/);
+  });
+});
+
+describe('configBuilder', () => {
+  it('builds a validated config from the defaults', () => {
+    const config = configBuilder()
+      .walletPrivateKey(WALLET)
+      .dryRun(true)
+      .pairs(['SOL/USDC', { pair: 'JUP/USDC', minProfitUsd: 3, enabled: true }])
+      .set('MIN_PROFIT_USD', 5)
+      .build();
+
+    assert.equal(config.DRY_RUN, true);
+    assert.equal(config.MIN_PROFIT_USD, 5);
+    assert.deepEqual(config.ARB_PAIRS.map(p => p.pair), ['SOL/USDC', 'JUP/USDC']);
+    assert.equal(minProfitForOpportunity(config, makeOpportunity({ pair: 'JUP/USDC' })), 3);
+  });
+
+  it('requires a wallet and runs validation', () => {
+    assert.throws(() => configBuilder().build(), /WALLET_PRIVATE_KEY is required/);
+    assert.throws(() => configBuilder().walletPrivateKey(WALLET).set('MIN_PROFIT_USD', 20_000).build(), /min profit/);
+  });
+});
+
+describe('applyConfigFile', () => {
+  function writeConfigFile(contents: string): string {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'arb-config-'));
+    const file = path.join(dir, 'bot.toml');
+    fs.writeFileSync(file, contents);
+    return file;
+  }
+
+  it('loads values from the file, with env > file > default', () => {
+    const file = writeConfigFile([
+      '# Bot settings',
+      'MIN_PROFIT_USD = 15',
+      'SCAN_INTERVAL_MS = 8000',
+      'DRY_RUN = true',
+      'ARB_PAIRS = ["SOL/USDC", "JUP/USDC:4"]',
+    ].join('\n'));
+    const env: Record<string, string | undefined> = { SCAN_INTERVAL_MS: '2000' };
+    applyConfigFile(file, env);
+    const config = loadConfig(env, []);
+
+    assert.equal(config.MIN_PROFIT_USD, 15);
+    assert.equal(config.SCAN_INTERVAL_MS, 2000);
+    assert.equal(env.DRY_RUN, 'true');
+    assert.deepEqual(config.ARB_PAIRS.map(p => [p.pair, p.minProfitUsd]), [['SOL/USDC', undefined], ['JUP/USDC', 4]]);
+    assert.equal(config.MAX_RETRIES, loadConfig({}, []).MAX_RETRIES);
+  });
+
+  it('rejects unreadable and unparseable files', () => {
+    assert.throws(() => applyConfigFile(path.join(os.tmpdir(), 'missing-arb-config.toml'), {}), /ENOENT/);
+    assert.throws(() => applyConfigFile(writeConfigFile('MIN_PROFIT_USD = '), {}));
+    assert.throws(() => applyConfigFile(writeConfigFile('{"MIN_PROFIT_USD": 15}'), {}));
+    assert.throws(() => applyConfigFile(writeConfigFile('[scanner]\nSCAN_INTERVAL_MS = 8000'), {}), /not a table/);
+  });
+});
@@ -0,0 +1,129 @@
+/**
+ * Send + confirmation loop tests
+ * A scripted connection answers each status poll in turn; polls run on the real 400ms interval
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { Connection, Keypair, SignatureStatus, VersionedTransaction } from '@solana/web3.js';
+import { sendAndConfirm, SendOptions } from '../src/utils/confirm.js';
+
+interface ScriptedChain {
+  // Signature status per getSignatureStatuses call (the last one repeats)
+  statuses: (Partial<SignatureStatus> | null)[];
+  blockHeight?: number;
+  // Nonce account missing = advanced
+  nonceAccountExists?: boolean;
+}
+
+function scriptedConnection(chain: ScriptedChain) {
+  const calls = { sends: [] as { skipPreflight?: boolean; maxRetries?: number }[], statusPolls: 0 };
+  const connection = {
+    async sendRawTransaction(_raw: Uint8Array, options: { skipPreflight?: boolean; maxRetries?: number }) {
+      calls.sends.push(options);
+      return 'sig1';
+    },
+    async getSignatureStatuses() {
+      const status = chain.statuses[Math.min(calls.statusPolls++, chain.statuses.length - 1)];
+      return { value: [status ? { slot: 42, confirmations: null, err: null, ...status } : null] };
+    },
+    async getBlockHeight() {
+      return chain.blockHeight ?? 100;
+    },
+    async getAccountInfo() {
+      return chain.nonceAccountExists ? { data: Buffer.alloc(80) } : null;
+    },
+  } as unknown as Connection;
+  return { connection, calls };
+}
+
+const transaction = { serialize: () => new Uint8Array([1]) } as unknown as VersionedTransaction;
+
+const options: SendOptions = {
+  skipPreflight: false,
+  commitment: 'confirmed',
+  lastValidBlockHeight: 150,
+  timeoutMs: 5_000,
+  maxRebroadcasts: 0,
+};
+
+describe('sendAndConfirm', () => {
+  it('sends with RPC retries off and waits for the requested commitment', async () => {
+    const { connection, calls } = scriptedConnection({
+      statuses: [null, { confirmationStatus: 'processed' }, { confirmationStatus: 'confirmed' }],
+    });
+
+    const result = await sendAndConfirm(connection, transaction, options);
+
+    assert.equal(result.status, 'confirmed');
+    assert.equal(result.signature, 'sig1');
+    assert.equal(result.slot, 42);
+    assert.ok(result.confirmedAt! >= result.submittedAt);
+    assert.equal(calls.statusPolls, 3);
+    assert.deepEqual(calls.sends, [{ skipPreflight: false, preflightCommitment: 'confirmed', maxRetries: 0 }]);
+  });
+
+  it('accepts processed when that is the requested commitment', async () => {
+    const { connection } = scriptedConnection({ statuses: [{ confirmationStatus: 'processed' }] });
+
+    const result = await sendAndConfirm(connection, transaction, { ...options, commitment: 'processed' });
+
+    assert.equal(result.status, 'confirmed');
+  });
+
+  it('reports a landed error as failed', async () => {
+    const err = { InstructionError: [2, { Custom: 6001 }] };
+    const { connection } = scriptedConnection({ statuses: [{ confirmationStatus: 'processed', err }] });
+
+    const result = await sendAndConfirm(connection, transaction, options);
+
+    assert.equal(result.status, 'failed');
+    assert.deepEqual(result.err, err);
+  });
+
+  it('reports dropped once the blockhash expires unseen', async () => {
+    const { connection } = scriptedConnection({ statuses: [null], blockHeight: 151 });
+
+    const result = await sendAndConfirm(connection, transaction, options);
+
+    assert.equal(result.status, 'dropped');
+    assert.equal(result.confirmedAt, undefined);
+  });
+
+  it('reports dropped once the durable nonce advances unseen', async () => {
+    const { connection } = scriptedConnection({ statuses: [null], blockHeight: 1_000 });
+
+    const result = await sendAndConfirm(connection, transaction, {
+      ...options,
+      nonce: { account: Keypair.generate().publicKey, value: 'nonce' },
+    });
+
+    assert.equal(result.status, 'dropped');
+  });
+
+  it('keeps waiting when the nonce advanced because the transaction itself landed', async () => {
+    const { connection, calls } = scriptedConnection({
+      statuses: [null, { confirmationStatus: 'processed' }, { confirmationStatus: 'confirmed' }],
+    });
+
+    const result = await sendAndConfirm(connection, transaction, {
+      ...options,
+      nonce: { account: Keypair.generate().publicKey, value: 'nonce' },
+    });
+
+    assert.equal(result.status, 'confirmed');
+    assert.equal(calls.statusPolls, 3);
+  });
+
+  it('times out while the blockhash is still valid, rebroadcasting at most maxRebroadcasts times', async () => {
+    const { connection, calls } = scriptedConnection({ statuses: [null] });
+    const started = Date.now();
+
+    const result = await sendAndConfirm(connection, transaction, { ...options, timeoutMs: 2_500, maxRebroadcasts: 1 });
+
+    assert.equal(result.status, 'timeout');
+    assert.ok(Date.now() - started >= 2_500);
+    assert.equal(calls.sends.length, 2);
+    assert.deepEqual(calls.sends[1], { skipPreflight: true, maxRetries: 0 });
+  });
+});
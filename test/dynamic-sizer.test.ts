@@ -0,0 +1,40 @@
+/**
+ * Dynamic sizer tests
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { estimateNetProfit, findOptimalAmount } from '../src/dynamic-sizer.js';
+
+describe('findOptimalAmount', () => {
+  const liquidity = 10_000_000;
+  const fees = 0.008;
+
+  it('lands within 1% of the analytic optimum L * sqrt((spread - fees) / 6)', () => {
+    for (const spread of [0.009, 0.012, 0.02, 0.05]) {
+      const optimum = liquidity * Math.sqrt((spread - fees) / 6);
+      const result = findOptimalAmount(liquidity, spread, fees);
+
+      assert.ok(Math.abs(result.amount - optimum) / optimum < 0.01, `spread ${spread}: ${result.amount} vs ${optimum}`);
+      assert.ok(Math.abs(result.expectedProfit - estimateNetProfit(optimum, spread, liquidity, fees)) / result.expectedProfit < 0.001);
+      assert.equal(result.slippagePercent, Math.pow(result.amount / liquidity, 2) * 2);
+    }
+  });
+
+  it('stops at maxAmount when the optimum is above it', () => {
+    const result = findOptimalAmount(liquidity, 0.02, fees, 100_000);
+
+    assert.ok(result.amount <= 100_000 && result.amount > 99_000, `${result.amount}`);
+  });
+
+  it('returns nothing when the spread does not cover fees', () => {
+    assert.deepEqual(findOptimalAmount(liquidity, fees, fees), { amount: 0, expectedProfit: 0, slippagePercent: 0 });
+    assert.deepEqual(findOptimalAmount(liquidity, 0.005, fees), { amount: 0, expectedProfit: 0, slippagePercent: 0 });
+  });
+
+  it('returns nothing when the upper bound is below MIN_AMOUNT', () => {
+    assert.deepEqual(findOptimalAmount(liquidity, 0.02, fees, 50), { amount: 0, expectedProfit: 0, slippagePercent: 0 });
+    assert.deepEqual(findOptimalAmount(500, 0.02, fees), { amount: 0, expectedProfit: 0, slippagePercent: 0 });
+    assert.deepEqual(findOptimalAmount(0, 0.02, fees), { amount: 0, expectedProfit: 0, slippagePercent: 0 });
+  });
+});
@@ -0,0 +1,67 @@
+/**
+ * Execution error classification tests
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { ExecutionError, classifyTransactionError } from '../src/execution-error.js';
+
+const custom = (code: number) => ({ InstructionError: [3, { Custom: code }] });
+
+describe('classifyTransactionError', () => {
+  it('maps Anchor error names in the logs to their category, keeping the custom code', () => {
+    const error = classifyTransactionError(custom(6036), [
+      'Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]',
+      'Program log: AnchorError occurred. Error Code: AmountOutBelowMinimum. Error Number: 6036.',
+    ]);
+    assert.equal(error.kind, 'slippage_exceeded');
+    assert.equal(error.code, 6036);
+    assert.match(error.message, /AmountOutBelowMinimum/);
+
+    assert.equal(classifyTransactionError(custom(6016), ['Program log: Error Code: InsufficientLiquidity']).kind, 'insufficient_liquidity');
+    assert.equal(classifyTransactionError(custom(6009), ['Program log: Error Code: ReserveStale']).kind, 'stale');
+    assert.equal(classifyTransactionError(custom(6022), ['Program log: Error Code: ExceededSlippage']).kind, 'slippage_exceeded');
+  });
+
+  it('maps runtime and token program log fragments', () => {
+    assert.equal(classifyTransactionError(custom(1), ['Transfer: insufficient lamports 100, need 2039280']).kind, 'insufficient_funds');
+    assert.equal(classifyTransactionError('BlockhashNotFound', ['Blockhash not found']).kind, 'blockhash_expired');
+  });
+
+  it('maps transaction-level errors without logs', () => {
+    assert.equal(classifyTransactionError('BlockhashNotFound').kind, 'blockhash_expired');
+    assert.equal(classifyTransactionError('InsufficientFundsForFee').kind, 'insufficient_funds');
+    assert.equal(classifyTransactionError({ InsufficientFundsForRent: { account_index: 0 } }).kind, 'insufficient_funds');
+  });
+
+  it('treats token insufficient funds (custom 1) as slippage between legs', () => {
+    const error = classifyTransactionError(JSON.stringify(custom(1)));
+    assert.equal(error.kind, 'slippage_exceeded');
+    assert.equal(error.code, 1);
+  });
+
+  it('falls back to simulation_failed with the custom code and message', () => {
+    const error = classifyTransactionError(custom(6000), [], 'Simulation failed');
+    assert.equal(error.kind, 'simulation_failed');
+    assert.equal(error.code, 6000);
+    assert.equal(error.message, 'Simulation failed');
+    assert.equal(classifyTransactionError(null).kind, 'simulation_failed');
+  });
+});
+
+describe('ExecutionError', () => {
+  it('retries only expired blockhashes and RPC failures', () => {
+    assert.equal(new ExecutionError('blockhash_expired', '').retryable, true);
+    assert.equal(new ExecutionError('rpc', '').retryable, true);
+    assert.equal(new ExecutionError('slippage_exceeded', '').retryable, false);
+    assert.equal(new ExecutionError('stale', '').retryable, false);
+  });
+
+  it('serializes kind, message and code', () => {
+    assert.deepEqual(JSON.parse(JSON.stringify(new ExecutionError('simulation_failed', 'boom', 6001))), {
+      kind: 'simulation_failed',
+      message: 'boom',
+      code: 6001,
+    });
+  });
+});
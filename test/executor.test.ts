@@ -0,0 +1,146 @@
+/**
+ * Executor tests
+ * Dry-run executions against mock swap APIs and mints
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { Keypair } from '@solana/web3.js';
+import { Executor, shrinkOpportunity } from '../src/executor.js';
+import { DEFAULT_SWAP_OPTIONS } from '../src/dex-integrations/swap-options.js';
+import { tokenRegistry } from '../src/token-registry.js';
+import { MockSwapApi, MockQuoteOptions, mockMintConnection } from './support/mock-swap-api.js';
+import { calculateProfit, calculateTriangularProfit } from '../src/profit-calculator.js';
+import { makeOpportunity } from './support/fixtures.js';
+
+/**
+ * Dry-run executor buying on raydium at `buyPrice` and selling on orca at `sellPrice`
+ */
+function makeExecutor(buyPrice: number, sellPrice: number, quoteOptions: MockQuoteOptions = {}, maxPriceImpactPct = 2) {
+  const raydium = new MockSwapApi(buyPrice, quoteOptions);
+  const orca = new MockSwapApi(sellPrice, quoteOptions);
+  const executor = new Executor(mockMintConnection(), Keypair.generate(), {
+    dryRun: true,
+    maxPriceImpactPct,
+    swapOptions: { ...DEFAULT_SWAP_OPTIONS, slippageBps: 30 },
+    swapApis: { raydium, orca },
+  });
+  return { executor, raydium, orca };
+}
+
+describe('Executor.execute (dry run)', () => {
+  it('re-quotes both legs and accepts a spread that still repays the loan', async () => {
+    const { executor, raydium, orca } = makeExecutor(100, 101.5);
+    const opp = makeOpportunity();
+
+    const result = await executor.execute(opp);
+
+    assert.equal(result.success, true);
+    assert.equal(result.actualProfit, opp.calculation.netProfit);
+    assert.equal(raydium.quotes.length, 1);
+    assert.ok(raydium.quotes[0].inputMint.equals(tokenRegistry.mint('USDC')));
+    assert.equal(raydium.quotes[0].amount, 10_000 * 1_000_000);
+    assert.equal(raydium.quotes[0].slippageBps, 30);
+    assert.equal(orca.quotes.length, 1);
+    assert.ok(orca.quotes[0].inputMint.equals(tokenRegistry.mint('SOL')));
+    assert.equal(orca.quotes[0].amount, 100 * 1e9);
+  });
+
+  it('skips as stale when current quotes no longer repay the loan', async () => {
+    const { executor } = makeExecutor(100, 99.5);
+
+    const result = await executor.execute(makeOpportunity());
+
+    assert.equal(result.success, false);
+    assert.equal(result.skipReason, 'stale');
+    assert.equal(result.error?.kind, 'stale');
+    assert.equal(executor.getStats().skippedStale, 1);
+  });
+
+  it('skips opportunities older than maxOpportunityAgeMs without quoting', async () => {
+    const raydium = new MockSwapApi(100);
+    const orca = new MockSwapApi(101.5);
+    const executor = new Executor(mockMintConnection(), Keypair.generate(), {
+      dryRun: true,
+      swapApis: { raydium, orca },
+      maxOpportunityAgeMs: 5_000,
+    });
+
+    const result = await executor.execute(makeOpportunity({ timestamp: Date.now() - 10_000 }));
+
+    assert.equal(result.skipReason, 'stale');
+    assert.equal(raydium.quotes.length, 0);
+  });
+
+  it('rejects a worst case below the repayment at the configured slippage', async () => {
+    const { executor } = makeExecutor(100, 100.3);
+
+    const result = await executor.execute(makeOpportunity());
+
+    assert.equal(result.success, false);
+    assert.equal(result.error?.kind, 'slippage_exceeded');
+  });
+});
+
+describe('price impact guard', () => {
+  it('rejects a 5% price impact quote when the limit is 2%', async () => {
+    const { executor } = makeExecutor(100, 101.5, { priceImpactPct: 5 });
+
+    const result = await executor.execute(makeOpportunity());
+
+    assert.equal(result.success, false);
+    assert.equal(result.skipReason, 'high_impact');
+    assert.equal(result.error?.kind, 'high_impact');
+    assert.match(result.error!.message, /Price impact too high: 5\.00%/);
+    assert.equal(executor.getStats().skippedHighImpact, 1);
+  });
+
+  it('accepts a 2% price impact quote at the limit', async () => {
+    const { executor } = makeExecutor(100, 101.5, { priceImpactPct: 2 });
+
+    const result = await executor.execute(makeOpportunity());
+
+    assert.equal(result.success, true);
+    assert.equal(executor.getStats().skippedHighImpact, 0);
+  });
+
+  it('uses the worse of the two legs', async () => {
+    const raydium = new MockSwapApi(100, { priceImpactPct: 0.5 });
+    const orca = new MockSwapApi(101.5, { priceImpactPct: 3 });
+    const executor = new Executor(mockMintConnection(), Keypair.generate(), {
+      dryRun: true,
+      maxPriceImpactPct: 2,
+      swapOptions: { ...DEFAULT_SWAP_OPTIONS, slippageBps: 30 },
+      swapApis: { raydium, orca },
+    });
+
+    const result = await executor.execute(makeOpportunity());
+
+    assert.equal(result.skipReason, 'high_impact');
+  });
+});
+
+describe('shrinkOpportunity', () => {
+  it('recomputes a two-leg profit at the smaller size', () => {
+    const shrunk = shrinkOpportunity(makeOpportunity(), 4_000);
+
+    assert.equal(shrunk.flashAmount, 4_000);
+    assert.deepEqual(shrunk.calculation, calculateProfit(4_000, 100, 101.5, 'raydium', 'orca'));
+  });
+
+  it('keeps the middle leg fee of a triangular route', () => {
+    const opp = makeOpportunity({
+      buyDex: 'raydium',
+      sellDex: 'raydium',
+      crossLeg: { dex: 'orca', token: 'JUP', rate: 101.5, liquidity: 1_000_000 },
+    });
+
+    const shrunk = shrinkOpportunity(opp, 4_000);
+
+    const expected = calculateTriangularProfit(4_000, 100, 101.5, 'raydium', 'orca', 'raydium');
+    assert.deepEqual(shrunk.calculation, expected);
+    assert.ok(shrunk.calculation.crossFee! > 0);
+    assert.ok(shrunk.calculation.netProfit < calculateProfit(4_000, 100, 101.5, 'raydium', 'raydium').netProfit);
+    assert.deepEqual(shrunk.crossLeg, opp.crossLeg);
+  });
+});
@@ -0,0 +1,109 @@
+/**
+ * HTTP helper tests
+ * fetch is stubbed per test, no network
+ */
+
+import { afterEach, describe, it, mock } from 'node:test';
+import assert from 'node:assert/strict';
+import { ApiError, fetchJson, parseRetryAfter, toApiError } from '../src/utils/http.js';
+
+/**
+ * Stub fetch to answer with each response in turn (the last one repeats)
+ */
+function stubFetch(...responses: (() => Response)[]) {
+  let call = 0;
+  return mock.method(globalThis, 'fetch', async () => responses[Math.min(call++, responses.length - 1)]());
+}
+
+const ok = (body: unknown) => () => Response.json(body);
+const status = (code: number, body = '', headers: Record<string, string> = {}) => () => new Response(body, { status: code, headers });
+
+describe('parseRetryAfter', () => {
+  const now = Date.parse('2026-01-01T00:00:00Z');
+
+  it('reads delay-seconds', () => {
+    assert.equal(parseRetryAfter('2', now), 2000);
+    assert.equal(parseRetryAfter('0', now), 0);
+  });
+
+  it('reads an HTTP date relative to now, never negative', () => {
+    assert.equal(parseRetryAfter('Thu, 01 Jan 2026 00:00:05 GMT', now), 5000);
+    assert.equal(parseRetryAfter('Wed, 31 Dec 2025 23:59:00 GMT', now), 0);
+  });
+
+  it('ignores a missing or malformed header', () => {
+    assert.equal(parseRetryAfter(null, now), undefined);
+    assert.equal(parseRetryAfter('soon', now), undefined);
+  });
+});
+
+describe('toApiError', () => {
+  it('classifies 429 as retryable rate_limited with Retry-After', async () => {
+    const error = await toApiError(status(429, 'slow down', { 'Retry-After': '3' })());
+    assert.equal(error.kind, 'rate_limited');
+    assert.equal(error.retryAfterMs, 3000);
+    assert.equal(error.retryable, true);
+  });
+
+  it('classifies 5xx as retryable server errors', async () => {
+    for (const code of [500, 502, 503]) {
+      const error = await toApiError(status(code)());
+      assert.equal(error.kind, 'server');
+      assert.equal(error.status, code);
+      assert.equal(error.retryable, true);
+    }
+  });
+
+  it('classifies a 400 mentioning the route as fatal no_route', async () => {
+    const error = await toApiError(status(400, '{"error":"ROUTE_NOT_FOUND"}')());
+    assert.equal(error.kind, 'no_route');
+    assert.equal(error.retryable, false);
+  });
+
+  it('classifies other 4xx as fatal client errors', async () => {
+    const error = await toApiError(status(400, 'bad amount')());
+    assert.equal(error.kind, 'client');
+    assert.equal(error.retryable, false);
+  });
+});
+
+describe('fetchJson', () => {
+  afterEach(() => mock.restoreAll());
+
+  it('retries 5xx and 429 until a response succeeds', async () => {
+    const fetch = stubFetch(status(502), status(429, '', { 'Retry-After': '0' }), ok({ price: 1 }));
+
+    assert.deepEqual(await fetchJson('https://api.test/quote', {}, { maxRetries: 3, baseDelayMs: 1 }), { price: 1 });
+    assert.equal(fetch.mock.callCount(), 3);
+  });
+
+  it('throws no_route without retrying', async () => {
+    const fetch = stubFetch(status(400, 'No route found'));
+
+    await assert.rejects(fetchJson('https://api.test/quote', {}, { maxRetries: 3, baseDelayMs: 1 }), (e: ApiError) => e.kind === 'no_route');
+    assert.equal(fetch.mock.callCount(), 1);
+  });
+
+  it('gives up after maxRetries', async () => {
+    const fetch = stubFetch(status(503));
+
+    await assert.rejects(fetchJson('https://api.test/quote', {}, { maxRetries: 2, baseDelayMs: 1 }), (e: ApiError) => e.kind === 'server');
+    assert.equal(fetch.mock.callCount(), 3);
+  });
+
+  it('fails fast as rate_limited when Retry-After exceeds the timeout', async () => {
+    const fetch = stubFetch(status(429, '', { 'Retry-After': '60' }), ok({}));
+
+    await assert.rejects(
+      fetchJson('https://api.test/quote', {}, { timeoutMs: 1000, maxRetries: 3, baseDelayMs: 1 }),
+      (e: ApiError) => e.kind === 'rate_limited' && e.retryAfterMs === 60_000
+    );
+    assert.equal(fetch.mock.callCount(), 1);
+  });
+
+  it('reports an aborted request as a timeout', async () => {
+    stubFetch(() => { throw Object.assign(new Error('aborted'), { name: 'AbortError' }); });
+
+    await assert.rejects(fetchJson('https://api.test/quote', {}, { maxRetries: 0 }), (e: ApiError) => e.kind === 'timeout');
+  });
+});
@@ -0,0 +1,91 @@
+/**
+ * Kamino flash loan client tests
+ * Stub markets and reserves stand in for loaded Kamino accounts; nothing touches the network
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import BN from 'bn.js';
+import { Connection, Keypair, PublicKey } from '@solana/web3.js';
+import { KaminoMarket } from '@kamino-finance/klend-sdk';
+import { KaminoFlashLoanClient, tvlDropPercent } from '../src/kamino-flash-loan.js';
+
+const connection = {} as Connection;
+
+/**
+ * Market with a single reserve holding `available` base units of liquidity
+ */
+function stubMarket(available: bigint, { flashLoanFee = 0.001, referralFeeBps = 0 } = {}) {
+  const reserve = {
+    address: Keypair.generate().publicKey,
+    state: {
+      liquidity: { availableAmount: new BN(available.toString()), mintDecimals: new BN(6) },
+      config: { fees: { flashLoanFeeSf: new BN(Math.round(flashLoanFee * 1e12)).mul(new BN(2).pow(new BN(60))).div(new BN(1e12)) } },
+    },
+  };
+  const market = { state: { referralFeeBps }, getReserveByMint: () => reserve } as unknown as KaminoMarket;
+  return { market, reserve };
+}
+
+/**
+ * Client with the given markets already loaded
+ */
+function loadedClient(markets: KaminoMarket[], referrer?: PublicKey): KaminoFlashLoanClient {
+  const client = new KaminoFlashLoanClient(connection, { referrer });
+  const internals = client as unknown as { markets: Map<string, KaminoMarket>; initialized: boolean };
+  for (const market of markets) {
+    internals.markets.set(Keypair.generate().publicKey.toBase58(), market);
+  }
+  internals.initialized = true;
+  return client;
+}
+
+describe('KaminoFlashLoanClient reserve selection', () => {
+  it('borrows from the deeper of two reserves, whatever the load order', async () => {
+    const shallow = stubMarket(1_000_000_000n);
+    const deep = stubMarket(5_000_000_000n);
+
+    for (const order of [[shallow, deep], [deep, shallow]]) {
+      const client = loadedClient(order.map(m => m.market));
+      assert.deepEqual(await client.getFlashReserve('USDC'), deep.reserve.address);
+    }
+  });
+});
+
+describe('KaminoFlashLoanClient.getAvailableLiquidity', () => {
+  it('reports the deepest reserve in whole tokens', async () => {
+    const client = loadedClient([stubMarket(2_500_000_000_000n).market, stubMarket(1_000_000n).market]);
+    assert.equal(await client.getAvailableLiquidity('USDC'), 2_500_000);
+  });
+});
+
+describe('KaminoFlashLoanClient flash loan fee', () => {
+  const referrer = Keypair.generate().publicKey;
+
+  it('repays the full fee with a referrer; only the estimate nets out the rebate', () => {
+    const { market } = stubMarket(5_000_000_000n, { flashLoanFee: 0.001, referralFeeBps: 2_000 });
+    const withReferrer = loadedClient([market], referrer);
+    const without = loadedClient([market]);
+
+    assert.equal(withReferrer.calculateFee(1_000_000_000n), 1_000_000n);
+    assert.equal(withReferrer.calculateFee(1_000_000_000n), without.calculateFee(1_000_000_000n));
+    assert.ok(Math.abs(withReferrer.getFlashLoanFee() - 0.001) < 1e-12);
+    assert.ok(Math.abs(withReferrer.getNetFlashLoanFee() - 0.0008) < 1e-12);
+    assert.ok(Math.abs(without.getNetFlashLoanFee() - 0.001) < 1e-12);
+  });
+});
+
+describe('tvlDropPercent', () => {
+  const baseline = new Map([['main', 1_000_000_000], ['jlp', 200_000_000]]);
+
+  it('sums the markets present in both snapshots', () => {
+    const drop = tvlDropPercent(baseline, new Map([['main', 400_000_000], ['jlp', 200_000_000]]));
+    assert.deepEqual(drop, { dropPercent: 50, before: 1_200_000_000, after: 600_000_000 });
+  });
+
+  it('ignores markets missing from either snapshot', () => {
+    assert.equal(tvlDropPercent(baseline, new Map([['jlp', 200_000_000]]))!.dropPercent, 0);
+    assert.equal(tvlDropPercent(baseline, new Map([['main', 1_000_000_000], ['new', 5]]))!.dropPercent, 0);
+    assert.equal(tvlDropPercent(baseline, new Map([['new', 5]])), null);
+  });
+});
@@ -0,0 +1,44 @@
+/**
+ * memcmp filter tests
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { Keypair } from '@solana/web3.js';
+import bs58 from 'bs58';
+import {
+  DISCRIMINATOR_LENGTH,
+  accountDiscriminator,
+  afterDiscriminatorFilter,
+  discriminatorFilter,
+  offsetFilter,
+} from '../src/utils/memcmp.js';
+
+describe('accountDiscriminator', () => {
+  it('matches the Anchor discriminators of known accounts', () => {
+    assert.deepEqual([...accountDiscriminator('Whirlpool')], [63, 149, 209, 12, 225, 128, 99, 9]);
+    assert.deepEqual([...accountDiscriminator('LendingMarket')], [246, 114, 50, 98, 72, 157, 28, 120]);
+    assert.equal(accountDiscriminator('Obligation').length, DISCRIMINATOR_LENGTH);
+  });
+});
+
+describe('memcmp filters', () => {
+  const market = Keypair.generate().publicKey;
+
+  it('places the discriminator at offset 0', () => {
+    const discriminator = accountDiscriminator('LendingMarket');
+    assert.deepEqual(discriminatorFilter(discriminator), { memcmp: { offset: 0, bytes: bs58.encode(discriminator) } });
+  });
+
+  it('rejects a discriminator that is not 8 bytes', () => {
+    assert.throws(() => discriminatorFilter(new Uint8Array(7)), /must be 8 bytes, got 7/);
+  });
+
+  it('places the first field right after the discriminator', () => {
+    assert.deepEqual(afterDiscriminatorFilter(market.toBytes()), { memcmp: { offset: 8, bytes: market.toBase58() } });
+  });
+
+  it('places bytes at an absolute offset', () => {
+    assert.deepEqual(offsetFilter(41, market.toBytes()), { memcmp: { offset: 41, bytes: market.toBase58() } });
+  });
+});
@@ -0,0 +1,112 @@
+/**
+ * Priority fee tests
+ * A stub connection returns canned recent prioritization fees
+ */
+
+import { afterEach, describe, it, mock } from 'node:test';
+import assert from 'node:assert/strict';
+import { Connection, Keypair, PublicKey } from '@solana/web3.js';
+import { PriorityFeeResolver, isHeliusRpc, parsePriorityFeeStrategy } from '../src/priority-fee.js';
+
+// 100, 200, ... 1000 micro-lamports, in slot order (unsorted)
+const RECENT_FEES = [700, 100, 1000, 300, 500, 200, 900, 400, 600, 800];
+
+function feeConnection(fees: number[] | Error = RECENT_FEES) {
+  const calls: PublicKey[][] = [];
+  const connection = {
+    async getRecentPrioritizationFees({ lockedWritableAccounts }: { lockedWritableAccounts: PublicKey[] }) {
+      calls.push(lockedWritableAccounts);
+      if (fees instanceof Error) throw fees;
+      return fees.map((prioritizationFee, slot) => ({ slot, prioritizationFee }));
+    },
+  } as unknown as Connection;
+  return { connection, calls };
+}
+
+const accounts = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+
+describe('parsePriorityFeeStrategy', () => {
+  it('parses each strategy', () => {
+    assert.deepEqual(parsePriorityFeeStrategy('static', 5000, 'h'), { type: 'static', microLamports: 5000 });
+    assert.deepEqual(parsePriorityFeeStrategy('percentile', 0, 'h'), { type: 'percentile', percentile: 75 });
+    assert.deepEqual(parsePriorityFeeStrategy('percentile:90', 0, 'h'), { type: 'percentile', percentile: 90 });
+    assert.deepEqual(parsePriorityFeeStrategy('auto', 0, 'vh'), { type: 'auto', level: 'vh' });
+    assert.deepEqual(parsePriorityFeeStrategy('estimate', 0, 'h'), { type: 'estimate' });
+  });
+
+  it('rejects unknown strategies and out-of-range percentiles', () => {
+    assert.throws(() => parsePriorityFeeStrategy('dynamic', 0, 'h'), /Invalid PRIORITY_FEE_STRATEGY/);
+    assert.throws(() => parsePriorityFeeStrategy('percentile:101', 0, 'h'), /must be 0-100/);
+    assert.throws(() => parsePriorityFeeStrategy('percentile:high', 0, 'h'), /must be 0-100/);
+  });
+});
+
+describe('PriorityFeeResolver', () => {
+  afterEach(() => mock.restoreAll());
+
+  it('static returns the configured fee without any RPC call', async () => {
+    const { connection, calls } = feeConnection();
+    const resolver = new PriorityFeeResolver(connection, { type: 'static', microLamports: 5000 }, 0, 1_000_000);
+
+    assert.equal(await resolver.resolve(accounts), 5000);
+    assert.equal(calls.length, 0);
+  });
+
+  it('percentile picks the Nth percentile of recent fees on the written accounts', async () => {
+    const { connection, calls } = feeConnection();
+    const fee = (percentile: number) =>
+      new PriorityFeeResolver(connection, { type: 'percentile', percentile }, 0, 1_000_000).resolve(accounts);
+
+    assert.equal(await fee(0), 100);
+    assert.equal(await fee(50), 600);
+    assert.equal(await fee(75), 800);
+    assert.equal(await fee(100), 1000);
+    assert.deepEqual(calls[0], accounts);
+  });
+
+  it('caps the fee and falls back when recent fees are unavailable', async () => {
+    const capped = new PriorityFeeResolver(feeConnection().connection, { type: 'percentile', percentile: 90 }, 0, 750);
+    assert.equal(await capped.resolve(accounts), 750);
+
+    const empty = new PriorityFeeResolver(feeConnection([]).connection, { type: 'percentile', percentile: 75 }, 1234, 1_000_000);
+    assert.equal(await empty.resolve(accounts), 1234);
+
+    const failing = new PriorityFeeResolver(feeConnection(new Error('429')).connection, { type: 'percentile', percentile: 75 }, 1234, 1_000_000);
+    assert.equal(await failing.resolve(accounts), 1234);
+  });
+
+  it('caches per account set', async () => {
+    const { connection, calls } = feeConnection();
+    const resolver = new PriorityFeeResolver(connection, { type: 'percentile', percentile: 75 }, 0, 1_000_000);
+
+    await resolver.resolve(accounts);
+    await resolver.resolve([...accounts].reverse());
+    assert.equal(calls.length, 1);
+
+    await resolver.resolve([Keypair.generate().publicKey]);
+    assert.equal(calls.length, 2);
+  });
+
+  it('estimate bids low, medium or high by expected profit', async () => {
+    const resolver = new PriorityFeeResolver(feeConnection().connection, { type: 'estimate' }, 0, 1_000_000);
+
+    assert.equal(await resolver.resolve(accounts, 5), 300);
+    assert.equal(await resolver.resolve(accounts, 20), 600);
+    assert.equal(await resolver.resolve(accounts, 150), 800);
+  });
+
+  it('auto uses the Raydium fee for the configured tier', async () => {
+    mock.method(globalThis, 'fetch', async () => Response.json({ data: { default: { m: 20_000, h: 80_000, vh: 400_000 } } }));
+    const resolver = new PriorityFeeResolver(feeConnection().connection, { type: 'auto', level: 'h' }, 0, 1_000_000);
+
+    assert.equal(await resolver.resolve(accounts), 80_000);
+  });
+});
+
+describe('isHeliusRpc', () => {
+  it('matches Helius hosts only', () => {
+    assert.equal(isHeliusRpc('https://mainnet.helius-rpc.com/?api-key=abc'), true);
+    assert.equal(isHeliusRpc('https://api.mainnet-beta.solana.com'), false);
+    assert.equal(isHeliusRpc('not a url'), false);
+  });
+});
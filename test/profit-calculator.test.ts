@@ -0,0 +1,109 @@
+/**
+ * Profit calculator tests
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import {
+  CrossLeg,
+  calculateProfit,
+  calculateTriangularProfit,
+  describeRoute,
+  findBestTriangularOpportunity,
+  routeKey,
+  validateOpportunity,
+} from '../src/profit-calculator.js';
+import { makeOpportunity } from './support/fixtures.js';
+
+describe('validateOpportunity', () => {
+  it('accepts a consistent opportunity', () => {
+    assert.equal(validateOpportunity(makeOpportunity()), null);
+  });
+
+  it('rejects a malformed pair or one token on both sides', () => {
+    assert.match(validateOpportunity(makeOpportunity({ pair: 'SOL' }))!, /invalid pair/);
+    assert.match(validateOpportunity(makeOpportunity({ pair: 'USDC/USDC' }))!, /invalid pair/);
+  });
+
+  it('rejects buying and selling on the same DEX', () => {
+    assert.match(validateOpportunity(makeOpportunity({ buyDex: 'orca', sellDex: 'orca' }))!, /same DEX/);
+  });
+
+  it('rejects a sell price not above the buy price', () => {
+    assert.match(validateOpportunity(makeOpportunity({ sellPrice: 100 }))!, /not above buy price/);
+    assert.match(validateOpportunity(makeOpportunity({ buyPrice: 0, sellPrice: 1 }))!, /not above buy price/);
+  });
+
+  it('rejects a zero, negative or non-finite flash amount', () => {
+    for (const flashAmount of [0, -1_000, Infinity, NaN]) {
+      assert.match(validateOpportunity(makeOpportunity({ flashAmount }))!, /invalid flash amount/);
+    }
+  });
+
+  it('rejects a non-positive net profit', () => {
+    const opp = makeOpportunity();
+    for (const netProfit of [0, -5, NaN]) {
+      assert.match(validateOpportunity({ ...opp, calculation: { ...opp.calculation, netProfit } })!, /non-positive net profit/);
+    }
+  });
+});
+
+describe('triangular routes', () => {
+  const crossLeg: CrossLeg = { dex: 'orca', token: 'JUP', rate: 101.5, liquidity: 1_000_000 };
+
+  it('accepts the same buy and sell DEX, but not a cross token already in the pair', () => {
+    assert.equal(validateOpportunity(makeOpportunity({ buyDex: 'raydium', sellDex: 'raydium', crossLeg })), null);
+    assert.match(validateOpportunity(makeOpportunity({ crossLeg: { ...crossLeg, token: 'USDC' } }))!, /already in SOL\/USDC/);
+    assert.match(validateOpportunity(makeOpportunity({ crossLeg: { ...crossLeg, rate: 0 } }))!, /invalid cross leg rate/);
+  });
+
+  it('deducts the middle leg fee from the two-leg profit', () => {
+    const twoLeg = calculateProfit(10_000, 100, 102, 'raydium', 'raydium');
+    const calculation = calculateTriangularProfit(10_000, 100, 102, 'raydium', 'orca', 'raydium');
+
+    assert.ok(Math.abs(calculation.crossFee! - 30.6) < 1e-9, `${calculation.crossFee}`);
+    assert.ok(Math.abs(calculation.netProfit - (twoLeg.netProfit - 30.6)) < 1e-9);
+    assert.equal(calculation.isProfitable, true);
+  });
+
+  it('picks the best DEX triple and sizes on the shallowest leg', () => {
+    const minLiquidities: number[] = [];
+    const opp = findBestTriangularOpportunity(
+      'SOL/USDC',
+      'JUP',
+      new Map([['raydium', { price: 100, liquidity: 5_000_000 }], ['orca', { price: 100.2, liquidity: 5_000_000 }]]),
+      new Map([['raydium', { price: 100, liquidity: 200_000 }], ['orca', { price: 101, liquidity: 200_000 }]]),
+      new Map([['raydium', { price: 1.02, liquidity: 1_000_000 }], ['orca', { price: 1, liquidity: 1_000_000 }]]),
+      (_pair, minLiquidity) => {
+        minLiquidities.push(minLiquidity);
+        return 10_000;
+      }
+    );
+
+    assert.equal(opp?.buyDex, 'raydium');
+    assert.deepEqual(opp!.crossLeg, { dex: 'orca', token: 'JUP', rate: 101, liquidity: 200_000 });
+    assert.equal(opp!.sellDex, 'raydium');
+    assert.ok(Math.abs(opp!.sellPrice - 103.02) < 1e-9);
+    assert.ok(opp!.calculation.netProfit > 0);
+    assert.ok(minLiquidities.every(l => l === 200_000));
+  });
+
+  it('finds nothing when no route beats the fees', () => {
+    const flat = new Map([['raydium', { price: 100, liquidity: 5_000_000 }]]);
+    const opp = findBestTriangularOpportunity(
+      'SOL/USDC',
+      'JUP',
+      flat,
+      new Map([['raydium', { price: 100.5, liquidity: 5_000_000 }]]),
+      new Map([['orca', { price: 1, liquidity: 5_000_000 }]]),
+      () => 10_000
+    );
+    assert.equal(opp, null);
+  });
+
+  it('keys and describes the route with its middle leg', () => {
+    assert.equal(routeKey(makeOpportunity()), 'SOL/USDC:raydium:orca');
+    assert.equal(routeKey(makeOpportunity({ crossLeg })), 'SOL/USDC:raydium:orca>JUP:orca');
+    assert.equal(describeRoute(makeOpportunity({ crossLeg })), 'raydium → orca (JUP) → orca');
+  });
+});
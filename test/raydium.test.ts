@@ -0,0 +1,126 @@
+/**
+ * Raydium client tests
+ * Trade API requests and responses against a stubbed fetch
+ */
+
+import { afterEach, describe, it, mock } from 'node:test';
+import assert from 'node:assert/strict';
+import { Connection, Keypair } from '@solana/web3.js';
+import { RaydiumClient } from '../src/dex-integrations/raydium.js';
+import { DEFAULT_SWAP_OPTIONS } from '../src/dex-integrations/swap-options.js';
+import { tokenRegistry } from '../src/token-registry.js';
+
+const SOL = tokenRegistry.mint('SOL').toBase58();
+const USDC = tokenRegistry.mint('USDC').toBase58();
+
+interface Request {
+  url: string;
+  body?: any;
+}
+
+/**
+ * Stub fetch with a handler per URL substring; records every request
+ */
+function stubTradeApi(routes: Record<string, unknown>): Request[] {
+  const requests: Request[] = [];
+  mock.method(globalThis, 'fetch', async (url: string, init: RequestInit = {}) => {
+    requests.push({ url, body: init.body ? JSON.parse(String(init.body)) : undefined });
+    const route = Object.keys(routes).find(path => url.includes(path));
+    return route ? Response.json(routes[route]) : new Response('not found', { status: 404 });
+  });
+  return requests;
+}
+
+function quoteResponse(inputAmount: number, outputAmount: number, otherAmountThreshold: number) {
+  return {
+    success: true,
+    data: { inputAmount: String(inputAmount), outputAmount: String(outputAmount), otherAmountThreshold: String(otherAmountThreshold), priceImpactPct: 0.15 },
+  };
+}
+
+describe('RaydiumClient.getSwapQuote', () => {
+  const client = new RaydiumClient({} as Connection);
+  afterEach(() => mock.restoreAll());
+
+  it('requests swap-base-in for ExactIn, with the threshold as minimum output', async () => {
+    const requests = stubTradeApi({ '/compute/swap-base-in': quoteResponse(1_000_000_000, 150_000_000, 149_250_000) });
+
+    const quote = await client.getSwapQuote(SOL, USDC, 1_000_000_000, 50);
+
+    const url = new URL(requests[0].url);
+    assert.equal(url.pathname, '/compute/swap-base-in');
+    assert.equal(url.searchParams.get('amount'), '1000000000');
+    assert.equal(url.searchParams.get('slippageBps'), '50');
+    assert.equal(quote?.mode, 'ExactIn');
+    assert.equal(quote?.minOutputAmount, 149_250_000);
+    assert.equal(quote?.maxInputAmount, 1_000_000_000);
+    assert.equal(quote?.priceImpactPct, 0.15);
+  });
+
+  it('requests swap-base-out for ExactOut, with the amount as output and the threshold as maximum input', async () => {
+    const requests = stubTradeApi({ '/compute/swap-base-out': quoteResponse(1_003_000_000, 150_000_000, 1_008_015_000) });
+
+    const quote = await client.getSwapQuote(SOL, USDC, 150_000_000, 50, 'ExactOut');
+
+    const url = new URL(requests[0].url);
+    assert.equal(url.pathname, '/compute/swap-base-out');
+    assert.equal(url.searchParams.get('inputMint'), SOL);
+    assert.equal(url.searchParams.get('outputMint'), USDC);
+    assert.equal(url.searchParams.get('amount'), '150000000');
+    assert.equal(quote?.mode, 'ExactOut');
+    assert.equal(quote?.outputAmount, 150_000_000);
+    assert.equal(quote?.minOutputAmount, 150_000_000);
+    assert.equal(quote?.maxInputAmount, 1_008_015_000);
+  });
+
+  it('throws no_route when the API reports no route', async () => {
+    stubTradeApi({ '/compute/swap-base-in': { success: false, msg: 'ROUTE_NOT_FOUND' } });
+
+    await assert.rejects(client.getSwapQuote(SOL, USDC, 1_000_000_000), { name: 'ApiError', kind: 'no_route' });
+  });
+});
+
+describe('RaydiumClient.buildSwapTransaction', () => {
+  const client = new RaydiumClient({} as Connection);
+  const wallet = Keypair.generate().publicKey;
+  afterEach(() => mock.restoreAll());
+
+  function stubSwap(): Request[] {
+    return stubTradeApi({
+      '/compute/swap-base-in': quoteResponse(10_000_000, 66_000_000, 65_000_000),
+      'auto-fee': { data: { default: { m: 50_000, h: 200_000, vh: 3_000_000 } } },
+      '/transaction/swap-base-in': { success: true, data: [{ transaction: Buffer.from([1, 2, 3]).toString('base64') }] },
+    });
+  }
+
+  it('sends the configured slippage, fee tier and SOL wrapping', async () => {
+    const requests = stubSwap();
+
+    const tx = await client.buildSwapTransaction(USDC, SOL, 10_000_000, wallet, { ...DEFAULT_SWAP_OPTIONS, slippageBps: 30, priorityLevel: 'm' });
+
+    assert.deepEqual(tx, Buffer.from([1, 2, 3]));
+    assert.equal(new URL(requests[0].url).searchParams.get('slippageBps'), '30');
+    const body = requests.find(r => r.url.includes('/transaction/'))!.body;
+    assert.equal(body.computeUnitPriceMicroLamports, '50000');
+    assert.equal(body.wallet, wallet.toBase58());
+    assert.equal(body.txVersion, 'V0');
+    assert.equal(body.wrapSol, false);
+    assert.equal(body.unwrapSol, true);
+    assert.equal(body.swapResponse.data.otherAmountThreshold, '65000000');
+  });
+
+  it('caps the fee at maxPriorityMicroLamports and can leave SOL wrapped', async () => {
+    const requests = stubSwap();
+
+    await client.buildSwapTransaction(USDC, SOL, 10_000_000, wallet, {
+      slippageBps: 50,
+      priorityLevel: 'vh',
+      maxPriorityMicroLamports: 1_000_000,
+      wrapUnwrapSol: false,
+    });
+
+    const body = requests.find(r => r.url.includes('/transaction/'))!.body;
+    assert.equal(body.computeUnitPriceMicroLamports, '1000000');
+    assert.equal(body.unwrapSol, false);
+  });
+});
@@ -0,0 +1,135 @@
+/**
+ * Scanner tests
+ * Stub price sources stand in for the DEXes; nothing touches the network
+ */
+
+import { describe, it } from 'node:test';
+import assert from 'node:assert/strict';
+import { Connection } from '@solana/web3.js';
+import { Scanner, ArbitragePairConfig } from '../src/scanner.js';
+import { PriceSource, PoolPrice } from '../src/dex-integrations/price-source.js';
+
+/**
+ * Fixed prices per pair; pairs not listed have no pool, a price of 'error' throws
+ */
+class StubPriceSource implements PriceSource {
+  initialized = false;
+  requested: string[] = [];
+
+  constructor(private prices: Record<string, PoolPrice | 'error'>) {}
+
+  async initialize(): Promise<void> {
+    this.initialized = true;
+  }
+
+  async getPrice(pair: string): Promise<PoolPrice | null> {
+    this.requested.push(pair);
+    const price = this.prices[pair];
+    if (price === 'error') {
+      throw new Error(`${pair} request failed`);
+    }
+    return price ?? null;
+  }
+}
+
+// Orca pool lookup at init finds no pools
+const connection = { getMultipleAccountsInfo: async (addresses: unknown[]) => addresses.map(() => null) } as unknown as Connection;
+
+const pairs: ArbitragePairConfig[] = [
+  { pair: 'SOL/USDC', enabled: true },
+  { pair: 'JUP/USDC', enabled: true },
+  { pair: 'WIF/USDC', enabled: true },
+  { pair: 'BONK/USDC', enabled: false },
+];
+
+function makeScanner(raydium: StubPriceSource, orca: StubPriceSource, pairConfigs = pairs) {
+  return new Scanner(connection, { pairs: pairConfigs, priceSources: { raydium, orca } });
+}
+
+describe('Scanner with plugged-in price sources', () => {
+  it('initializes and quotes every enabled pair on each source', async () => {
+    const raydium = new StubPriceSource({});
+    const orca = new StubPriceSource({});
+
+    await makeScanner(raydium, orca).scanAllPairs();
+
+    assert.equal(raydium.initialized && orca.initialized, true);
+    assert.deepEqual(raydium.requested, ['SOL/USDC', 'JUP/USDC', 'WIF/USDC']);
+    assert.deepEqual(orca.requested, ['SOL/USDC', 'JUP/USDC', 'WIF/USDC']);
+  });
+
+  it('reports an opportunity per outcome and per-DEX quote counts', async () => {
+    const raydium = new StubPriceSource({
+      'SOL/USDC': { price: 100, liquidity: 5_000_000 },
+      'JUP/USDC': { price: 1, liquidity: 2_000_000 },
+      'WIF/USDC': 'error',
+    });
+    const orca = new StubPriceSource({
+      'SOL/USDC': { price: 101.5, liquidity: 5_000_000 },
+      'JUP/USDC': { price: 1, liquidity: 2_000_000 },
+      'WIF/USDC': { price: 2, liquidity: 1_000_000 },
+    });
+    const found: string[] = [];
+
+    const report = await makeScanner(raydium, orca).scanAllPairs(opp => {
+      found.push(opp.pair);
+    });
+
+    assert.deepEqual(report.outcomes, { opportunity: 1, no_spread: 1, missing_quotes: 1 });
+    assert.deepEqual(report.perDex, { raydium: { quotes: 2, misses: 1 }, orca: { quotes: 3, misses: 0 } });
+    assert.deepEqual(found, ['SOL/USDC']);
+
+    const [opp] = report.opportunities;
+    assert.equal(opp.buyDex, 'raydium');
+    assert.equal(opp.sellDex, 'orca');
+    assert.equal(opp.buyPrice, 100);
+    assert.equal(opp.sellPrice, 101.5);
+    assert.ok(opp.flashAmount > 0 && opp.calculation.netProfit > 0);
+  });
+
+  it('sizes within the pair flash amount cap', async () => {
+    const raydium = new StubPriceSource({ 'SOL/USDC': { price: 101.5, liquidity: 5_000_000 } });
+    const orca = new StubPriceSource({ 'SOL/USDC': { price: 100, liquidity: 5_000_000 } });
+    const scanner = makeScanner(raydium, orca, [{ pair: 'SOL/USDC', enabled: true, maxFlashAmount: 20_000 }]);
+
+    const opp = await scanner.scanPair('SOL/USDC');
+
+    assert.equal(opp?.buyDex, 'orca');
+    assert.ok(opp!.flashAmount <= 20_000 && opp!.flashAmount > 19_000, `${opp?.flashAmount}`);
+  });
+
+  it('needs prices from at least two sources', async () => {
+    const raydium = new StubPriceSource({ 'SOL/USDC': { price: 100, liquidity: 5_000_000 } });
+    const orca = new StubPriceSource({ 'SOL/USDC': { price: 0, liquidity: 5_000_000 } });
+    const scanner = makeScanner(raydium, orca);
+
+    const { opportunity, outcome, prices } = await scanner.inspectPair('SOL/USDC');
+
+    assert.equal(opportunity, null);
+    assert.equal(outcome, 'missing_quotes');
+    assert.deepEqual(prices, { raydium: 100 });
+  });
+
+  it('finds a triangular route through the cross pair when enabled', async () => {
+    const flat = { 'SOL/USDC': { price: 100, liquidity: 5_000_000 }, 'JUP/USDC': { price: 1, liquidity: 5_000_000 } };
+    const raydium = new StubPriceSource({ ...flat, 'SOL/JUP': { price: 103, liquidity: 5_000_000 } });
+    const orca = new StubPriceSource({ ...flat, 'SOL/JUP': { price: 100, liquidity: 5_000_000 } });
+    const scanner = new Scanner(connection, {
+      pairs: pairs.slice(0, 2),
+      priceSources: { raydium, orca },
+      triangularRoutes: true,
+    });
+
+    const report = await scanner.scanAllPairs();
+
+    assert.deepEqual(report.outcomes, { no_spread: 2 });
+    assert.deepEqual(raydium.requested, ['SOL/USDC', 'JUP/USDC', 'SOL/JUP']);
+    assert.equal(report.opportunities.length, 1);
+    const [opp] = report.opportunities;
+    assert.equal(opp.pair, 'SOL/USDC');
+    assert.equal(opp.crossLeg?.token, 'JUP');
+    assert.equal(opp.crossLeg?.dex, 'raydium');
+    assert.equal(opp.sellPrice, 103);
+    assert.ok(opp.calculation.netProfit > 0);
+  });
+});
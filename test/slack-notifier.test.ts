@@ -0,0 +1,70 @@
+/**
+ * Slack notifier tests
+ */
+
+import { afterEach, describe, it, mock } from 'node:test';
+import assert from 'node:assert/strict';
+import { SlackNotifier, toSlackMessage } from '../src/slack-notifier.js';
+import { ExecutionResult } from '../src/executor.js';
+import { makeOpportunity } from './support/fixtures.js';
+
+const landed: ExecutionResult = {
+  success: true,
+  txSignature: '5sig',
+  actualProfit: 42.5,
+  feeLamports: 15_000,
+  executionTimeMs: 900,
+};
+
+describe('toSlackMessage', () => {
+  it('builds valid Block Kit JSON with the trade summary and a Solscan link', () => {
+    const opp = makeOpportunity();
+    const message = JSON.parse(toSlackMessage(opp, landed));
+
+    assert.equal(message.text, 'SOL/USDC arbitrage landed: $42.50');
+    const [header, section, context] = message.blocks;
+    assert.deepEqual(header, { type: 'header', text: { type: 'plain_text', text: '⚡ SOL/USDC arbitrage landed' } });
+
+    assert.equal(section.type, 'section');
+    const fields: string[] = section.fields.map((f: { type: string; text: string }) => {
+      assert.equal(f.type, 'mrkdwn');
+      return f.text;
+    });
+    assert.ok(fields.includes('*Route*\nraydium → orca'));
+    assert.ok(fields.includes('*Spread*\n1.500%'));
+    assert.ok(fields.includes(`*Expected profit*\n$${opp.calculation.netProfit.toFixed(2)}`));
+    assert.ok(fields.includes('*Realized profit*\n$42.50'));
+    assert.ok(fields.includes('*Fee*\n15000 lamports'));
+    assert.ok(fields.some(f => f.startsWith('*Flash amount*\n$')));
+
+    assert.equal(context.type, 'context');
+    assert.equal(context.elements[0].text, '<https://solscan.io/tx/5sig|View on Solscan>');
+  });
+
+  it('handles a result without signature, fee or realized profit', () => {
+    const message = JSON.parse(toSlackMessage(makeOpportunity(), { success: true, executionTimeMs: 10 }));
+
+    assert.equal(message.text, 'SOL/USDC arbitrage landed: $0.00');
+    assert.ok(message.blocks[1].fields.some((f: { text: string }) => f.text === '*Fee*\nunknown'));
+    assert.equal(message.blocks[2].elements[0].text, 'No signature');
+  });
+});
+
+describe('SlackNotifier', () => {
+  afterEach(() => mock.restoreAll());
+
+  it('posts the message as JSON to the webhook and never throws', async () => {
+    const fetch = mock.method(globalThis, 'fetch', async () => new Response('ok'));
+    const notifier = new SlackNotifier('https://hooks.slack.test/T000/B000');
+
+    await notifier.notifyTrade(makeOpportunity(), landed);
+
+    const [url, init] = fetch.mock.calls[0].arguments as [string, RequestInit];
+    assert.equal(url, 'https://hooks.slack.test/T000/B000');
+    assert.equal(init.method, 'POST');
+    assert.equal(JSON.parse(String(init.body)).text, 'SOL/USDC arbitrage landed: $42.50');
+
+    fetch.mock.mockImplementation(async () => { throw new Error('offline'); });
+    await assert.doesNotReject(notifier.notifyText('Paused'));
+  });
+});
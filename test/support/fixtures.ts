@@ -0,0 +1,34 @@
+/**
+ * Test fixtures
+ * Opportunities with consistent prices and profit, overridable field by field
+ */
+
+import { ArbitrageOpportunity, calculateProfit, calculateSpread } from '../../src/profit-calculator.js';
+
+/**
+ * SOL/USDC raydium → orca at 100 / 101.5 for $10k unless overridden; calculation follows the prices
+ */
+export function makeOpportunity(overrides: Partial<ArbitrageOpportunity> = {}): ArbitrageOpportunity {
+  const buyDex = overrides.buyDex ?? 'raydium';
+  const sellDex = overrides.sellDex ?? 'orca';
+  const buyPrice = overrides.buyPrice ?? 100;
+  const sellPrice = overrides.sellPrice ?? 101.5;
+  const flashAmount = overrides.flashAmount ?? 10_000;
+  const { spread, spreadPercent } = calculateSpread(buyPrice, sellPrice);
+
+  return {
+    pair: 'SOL/USDC',
+    buyDex,
+    sellDex,
+    buyPrice,
+    sellPrice,
+    spread,
+    spreadPercent,
+    flashAmount,
+    calculation: calculateProfit(flashAmount, buyPrice, sellPrice, buyDex, sellDex),
+    buyLiquidity: 5_000_000,
+    sellLiquidity: 5_000_000,
+    timestamp: Date.now(),
+    ...overrides,
+  };
+}
@@ -0,0 +1,93 @@
+/**
+ * Mock Swap API
+ * Canned quotes at a fixed USDC price for the executor, no network or RPC
+ */
+
+import { Connection, PublicKey, TransactionInstruction } from '@solana/web3.js';
+import { MINT_SIZE, MintLayout, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { SwapApi, SwapLegQuote, SwapMode } from '../../src/dex-integrations/swap-api.js';
+import { SwapOptions } from '../../src/dex-integrations/swap-options.js';
+import { tokenRegistry } from '../../src/token-registry.js';
+
+export interface MockQuoteOptions {
+  priceImpactPct?: number;
+  // Quote nothing, as if the DEX had no route
+  noRoute?: boolean;
+}
+
+export interface MockQuoteCall {
+  inputMint: PublicKey;
+  outputMint: PublicKey;
+  amount: number;
+  slippageBps: number;
+}
+
+/**
+ * Quotes every base token at `price` USDC, in both directions, with minOut at the slippage limit
+ */
+export class MockSwapApi implements SwapApi {
+  readonly supportsExactOut = true;
+  readonly quotes: MockQuoteCall[] = [];
+  readonly builds: { inputMint: PublicKey; outputMint: PublicKey; amount: number; mode?: SwapMode }[] = [];
+
+  constructor(private price: number, private options: MockQuoteOptions = {}) {}
+
+  async initialize(): Promise<void> {}
+
+  async quote(inputMint: PublicKey, outputMint: PublicKey, amount: number, slippageBps: number): Promise<SwapLegQuote | null> {
+    this.quotes.push({ inputMint, outputMint, amount, slippageBps });
+    if (this.options.noRoute) {
+      return null;
+    }
+
+    const usdc = tokenRegistry.mint('USDC');
+    const baseMint = inputMint.equals(usdc) ? outputMint : inputMint;
+    const base = tokenRegistry.forMint(baseMint);
+    if (!base) {
+      throw new Error(`Unknown mint: ${baseMint.toBase58()}`);
+    }
+
+    const baseUnitsPerUsdcUnit = Math.pow(10, base.decimals - 6) / this.price;
+    const outAmount = Math.floor(inputMint.equals(usdc) ? amount * baseUnitsPerUsdcUnit : amount / baseUnitsPerUsdcUnit);
+    return {
+      inAmount: amount,
+      outAmount,
+      minOutAmount: Math.floor(outAmount * (1 - slippageBps / 10_000)),
+      priceImpactPct: this.options.priceImpactPct ?? 0.1,
+    };
+  }
+
+  async buildSwapInstructions(
+    inputMint: PublicKey,
+    outputMint: PublicKey,
+    amount: number,
+    _walletPubkey: PublicKey,
+    _options: SwapOptions,
+    mode?: SwapMode
+  ): Promise<TransactionInstruction[] | null> {
+    this.builds.push({ inputMint, outputMint, amount, mode });
+    return [];
+  }
+}
+
+/**
+ * Connection whose getAccountInfo returns a plain SPL mint (registry decimals, no transfer fee)
+ * for any address; every other RPC call fails
+ */
+export function mockMintConnection(): Connection {
+  return {
+    async getAccountInfo(address: PublicKey) {
+      const data = Buffer.alloc(MINT_SIZE);
+      MintLayout.encode({
+        mintAuthorityOption: 0,
+        mintAuthority: PublicKey.default,
+        supply: 0n,
+        decimals: tokenRegistry.forMint(address)?.decimals ?? 6,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: PublicKey.default,
+      }, data);
+      return { data, owner: TOKEN_PROGRAM_ID, lamports: 1_461_600, executable: false };
+    },
+  } as unknown as Connection;
+}